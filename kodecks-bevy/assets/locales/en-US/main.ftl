@@ -1,6 +1,12 @@
 menu-button-cpu-match-1 = CPU Match 1
 menu-button-cpu-match-2 = CPU Match 2
+menu-button-cpu-match-random = CPU Match (Random Deck)
+menu-button-difficulty-easy = Difficulty: Easy
+menu-button-difficulty-normal = Difficulty: Normal
+menu-button-difficulty-hard = Difficulty: Hard
 menu-button-random-match = Random Match
+menu-button-casual-match = Casual Match
+menu-button-spectate = Watch Match
 menu-button-deck-edit = Deck Edit
 
 deck-label-collection = Collection
@@ -8,13 +14,17 @@ deck-label-deck = Deck
 deck-button-quit = Quit
 
 loading-message-finding-player = Finding a player...
+loading-message-finding-game = Finding a match to watch...
+loading-message-select-game = Select a match to watch.
 loading-button-cancel = Cancel
+loading-button-spectate = #{ $game } { $players }
 
 all-attack-button = All Attack
 attack-button = Attack ({ $attackers })
 block-button = Block ({ $blockers })
 continue-button = Continue
 end-turn-button = End Turn
+undo-button = Undo
 
 your-turn = Your Turn
 opponents-turn = Opponent's Turn
@@ -34,9 +44,35 @@ result-defeat = Defeat...
     .reason-concede = You have conceded.
     .reason-deck-out = You could not draw a card from your deck.
     .reason-life-zero = Your life is zero.
+    .reason-turn-limit = You could not win within the turn limit.
+result-game-over = Game Over
+    .reason-concede = A player has conceded.
+    .reason-deck-out = A player could not draw a card from their deck.
+    .reason-life-zero = A player's life is zero.
+    .reason-turn-limit = The turn limit has been reached.
 result-draw = Draw
     .reason-simultaneous-end = Both players fulfill the winning or losing condition at the same time.
 
+puzzle-all-out-attack = Puzzle: All-Out Attack
+puzzle-all-out-attack-hint = Win this turn. Your opponent has 600 life and a creature ready to block.
+
+tutorial-basics = Tutorial: Basics
+tutorial-basics-welcome = Welcome to Kodecks! Let's learn the basics by playing a short game.
+tutorial-basics-life = Both players start with 2000 life. Reduce your opponent's life to 0 to win.
+tutorial-basics-cost = The number at the top left of a card is its cost. Cards with cost 0 can be cast right away.
+tutorial-basics-cast = Cast { card-wind-up-spider }. You can cast one creature with cost 0 for free each turn.
+tutorial-basics-end-turn = Well done! Now end your turn.
+tutorial-basics-attacked = Your opponent is attacking with { card-moonlit-gecko }.
+tutorial-basics-block = Block it with { card-wind-up-spider }. When both creatures have the same power, both are destroyed.
+tutorial-basics-cost-decay = At the start of your turn, the cost of every card in your hand decreases by 1.
+tutorial-basics-shards = When your creature was destroyed, you got a shard of its color. Shards pay the remaining cost of cards of the same color.
+tutorial-basics-cast-with-shards = Cast { card-ruby-digger } using your red shard.
+tutorial-basics-free-cast = { card-solar-beetle } now costs 0. Cast it for free.
+tutorial-basics-attack = Your opponent has no creatures to block. Attack with both creatures!
+tutorial-basics-exhausted = Creatures that attacked become exhausted and cannot block. They recover at the start of your turn.
+tutorial-basics-hex = Besides creatures, there are Hex cards. A Hex takes effect once when cast and then goes to the graveyard.
+tutorial-basics-finish = That's all for the basics. Reduce your opponent's life to 0 to finish the tutorial!
+
 message-discard-excess-cards = Discard cards until you have { $maxHandSize } cards in your hand.
 
 ability-toxic = Toxic
@@ -50,10 +86,54 @@ ability-devour = Devour
 ability-piercing = Piercing
     .description = This card can destroy a creature, regardless of its shield.
 
+modifier-cost = <<{$source}>>: Cost {$before} → {$after}
+modifier-power = <<{$source}>>: Power {$before} → {$after}
+modifier-shields = <<{$source}>>: Shields {$before} → {$after}
+modifier-ability-granted = <<{$source}>>: Grants {$ability}
+modifier-ability-removed = <<{$source}>>: Removes {$ability}
+
+unavailable-game-ended = The game has ended.
+unavailable-not-in-action = It is not your turn to act.
+unavailable-effect-resolving = An effect is being resolved.
+unavailable-wrong-phase = You cannot do that during the {$phase ->
+    [standby] Standby Phase
+    [draw] Draw Phase
+    [main] Main Phase
+    [block] Block Phase
+    [battle] Battle Phase
+    [end] End Phase
+    *[other] {$phase}
+}.
+unavailable-card-not-in-hand = The card is not in your hand.
+unavailable-card-not-on-field = The creature is not on your field.
+unavailable-insufficient-shards = You need {$required} {$color ->
+    [red] red
+    [yellow] yellow
+    [green] green
+    [blue] blue
+    *[other] colorless
+} {$required ->
+    [1] shard
+    *[other] shards
+}, but you have {$available}.
+unavailable-creature-already-free-casted = You have already cast a creature for free this turn.
+unavailable-casting-restricted = The card's effect prevents it from being cast now.
+unavailable-exhausted = The creature is exhausted.
+unavailable-not-attacking = The creature is not attacking.
+unavailable-not-selectable = The card cannot be selected.
+unavailable-debug-command-disabled = Debug commands are disabled.
+unavailable-undo-disabled = Undo is not allowed in this game.
+unavailable-nothing-to-undo = There is nothing to undo.
+unavailable-restricted-by-scenario = You cannot do that right now.
+unavailable-invalid-action = That action is not valid.
+
 error-failed-to-connect-server = Failed to connect to the server.
 error-client-version-outdated = Your client needs to be updated.　Supported client version: { $requirement }
 error-server-version-outdated = The server needs to be updated. Supported client version: { $requirement }
 error-invalid-deck = Your deck does not meet the regulations.
+error-game-not-found = The game could not be found.
+error-spectating-own-game = You cannot watch a match you are playing.
+error-permission-denied = You are not allowed to do that.
 
 log-game-started = The game has started.
 
@@ -1,6 +1,12 @@
 menu-button-cpu-match-1 = CPU対戦1
 menu-button-cpu-match-2 = CPU対戦2
+menu-button-cpu-match-random = CPU対戦（ランダムデッキ）
+menu-button-difficulty-easy = 難易度: やさしい
+menu-button-difficulty-normal = 難易度: ふつう
+menu-button-difficulty-hard = 難易度: むずかしい
 menu-button-random-match = ランダム対戦
+menu-button-casual-match = カジュアル対戦
+menu-button-spectate = 観戦
 menu-button-deck-edit = デッキ編集
 
 deck-label-collection = コレクション
@@ -8,7 +14,10 @@ deck-label-deck = デッキ
 deck-button-quit = 終了
 
 loading-message-finding-player = プレイヤーを探しています...
+loading-message-finding-game = 観戦できる対戦を探しています...
+loading-message-select-game = 観戦する対戦を選んでください。
 loading-button-cancel = キャンセル
+loading-button-spectate = #{ $game } { $players }
 
 your-turn = Your Turn
 opponents-turn = Opponent's Turn
@@ -25,6 +34,7 @@ attack-button = { $attackers }体で攻撃
 block-button = { $blockers }体でブロック
 continue-button = 続行
 end-turn-button = ターン終了
+undo-button = 元に戻す
 
 result-victory = Victory!
     .reason-concede = 相手が降参しました。
@@ -34,9 +44,35 @@ result-defeat = Defeat...
     .reason-concede = あなたが降参しました。
     .reason-deck-out = あなたがデッキからカードを引けなくなりました。
     .reason-life-zero = あなたのライフが0になりました。
+    .reason-turn-limit = ターン制限内に勝利できませんでした。
+result-game-over = Game Over
+    .reason-concede = プレイヤーが降参しました。
+    .reason-deck-out = プレイヤーがデッキからカードを引けなくなりました。
+    .reason-life-zero = プレイヤーのライフが0になりました。
+    .reason-turn-limit = ターン制限に達しました。
 result-draw = Draw
     .reason-simultaneous-end = 両プレイヤーが同時に勝利条件または敗北条件を満たしました。
 
+puzzle-all-out-attack = パズル: 総攻撃
+puzzle-all-out-attack-hint = このターン中に勝利してください。相手のライフは600で、ブロックできるクリーチャーがいます。
+
+tutorial-basics = チュートリアル: 基本
+tutorial-basics-welcome = Kodecksへようこそ！短いゲームで基本を覚えましょう。
+tutorial-basics-life = 両プレイヤーのライフは2000から始まります。相手のライフを0にすると勝利です。
+tutorial-basics-cost = カードの左上の数字はコストです。コスト0のカードはすぐに使用できます。
+tutorial-basics-cast = { card-wind-up-spider }を召喚しましょう。コスト0のクリーチャーは毎ターン1体まで無償で召喚できます。
+tutorial-basics-end-turn = よくできました！ターンを終了しましょう。
+tutorial-basics-attacked = 相手が{ card-moonlit-gecko }で攻撃してきました。
+tutorial-basics-block = { card-wind-up-spider }でブロックしましょう。パワーが同じクリーチャー同士は両方とも破壊されます。
+tutorial-basics-cost-decay = 自分のターンの開始時に、手札のすべてのカードのコストが1減ります。
+tutorial-basics-shards = クリーチャーが破壊されると、その色のカケラを得ます。カケラは同じ色のカードの残りのコストを支払います。
+tutorial-basics-cast-with-shards = 赤のカケラを使って{ card-ruby-digger }を召喚しましょう。
+tutorial-basics-free-cast = { card-solar-beetle }のコストが0になりました。無償で召喚しましょう。
+tutorial-basics-attack = 相手にはブロックできるクリーチャーがいません。2体で攻撃しましょう！
+tutorial-basics-exhausted = 攻撃したクリーチャーは疲労状態になり、ブロックできません。自分のターンの開始時に回復します。
+tutorial-basics-hex = クリーチャーの他に、ヘックスカードがあります。ヘックスは使用時に一度だけ効果を発揮し、墓地に送られます。
+tutorial-basics-finish = 基本は以上です。相手のライフを0にしてチュートリアルを完了しましょう！
+
 message-discard-excess-cards = 手札が{ $maxHandSize }枚になるまでカードを捨ててください。
 
 ability-toxic = 有毒
@@ -50,10 +86,51 @@ ability-devour = 貪食
 ability-piercing = 貫通
     .description = シールドに関係なくクリーチャーを破壊する。
 
+modifier-cost = <<{$source}>>: コスト {$before} → {$after}
+modifier-power = <<{$source}>>: パワー {$before} → {$after}
+modifier-shields = <<{$source}>>: シールド {$before} → {$after}
+modifier-ability-granted = <<{$source}>>: {$ability}を付与
+modifier-ability-removed = <<{$source}>>: {$ability}を除去
+
+unavailable-game-ended = ゲームは終了しています。
+unavailable-not-in-action = あなたが行動するタイミングではありません。
+unavailable-effect-resolving = 効果の処理中です。
+unavailable-wrong-phase = {$phase ->
+    [standby] スタンバイフェイズ
+    [draw] ドローフェイズ
+    [main] メインフェイズ
+    [block] ブロックフェイズ
+    [battle] バトルフェイズ
+    [end] エンドフェイズ
+    *[other] {$phase}
+}中はその行動を行えません。
+unavailable-card-not-in-hand = そのカードは手札にありません。
+unavailable-card-not-on-field = そのクリーチャーは場にいません。
+unavailable-insufficient-shards = {$color ->
+    [red] 赤
+    [yellow] 黄
+    [green] 緑
+    [blue] 青
+    *[other] 無色
+}のカケラが{$required}つ必要ですが、{$available}つしかありません。
+unavailable-creature-already-free-casted = このターンはすでにクリーチャーを無償で召喚しています。
+unavailable-casting-restricted = カードの効果により、今はそのカードを使用できません。
+unavailable-exhausted = そのクリーチャーは疲労しています。
+unavailable-not-attacking = そのクリーチャーは攻撃していません。
+unavailable-not-selectable = そのカードは選択できません。
+unavailable-debug-command-disabled = デバッグコマンドは無効になっています。
+unavailable-undo-disabled = このゲームでは元に戻せません。
+unavailable-nothing-to-undo = 元に戻せる操作がありません。
+unavailable-restricted-by-scenario = 今はその操作を行えません。
+unavailable-invalid-action = その操作は無効です。
+
 error-failed-to-connect-server = サーバーに接続できませんでした。
 error-client-version-outdated = クライアントのアップデートが必要です。対応クライアントバージョン: { $requirement }
 error-server-version-outdated = サーバーのアップデートが必要です。対応クライアントバージョン: { $requirement }
 error-invalid-deck = デッキがレギュレーションに適合していません。
+error-game-not-found = 対戦が見つかりませんでした。
+error-spectating-own-game = 参加中の対戦は観戦できません。
+error-permission-denied = その操作は許可されていません。
 
 log-game-started = ゲームが開始されました。
 
@@ -5,7 +5,7 @@ use futures::{
     SinkExt, StreamExt,
 };
 use kodecks_engine::{
-    game::start_game,
+    game::{start_game, start_puzzle, start_tutorial},
     message::{Command, Input, Output},
     Connection,
 };
@@ -40,6 +40,25 @@ async fn start_task(mut command_recv: Receiver<Input>, event_send: Sender<Output
                     .spawn(start_game(log_id, profile, receiver, event_send.clone()))
                     .detach();
             }
+            Input::Command(Command::StartPuzzle { log_id, puzzle }) => {
+                let (command_sender, receiver) = mpsc::channel(256);
+                sender = Some(command_sender);
+                AsyncComputeTaskPool::get()
+                    .spawn(start_puzzle(log_id, *puzzle, receiver, event_send.clone()))
+                    .detach();
+            }
+            Input::Command(Command::StartTutorial { log_id, tutorial }) => {
+                let (command_sender, receiver) = mpsc::channel(256);
+                sender = Some(command_sender);
+                AsyncComputeTaskPool::get()
+                    .spawn(start_tutorial(
+                        log_id,
+                        *tutorial,
+                        receiver,
+                        event_send.clone(),
+                    ))
+                    .detach();
+            }
             Input::GameCommand(session_command) => {
                 if let Some(sender) = &mut sender {
                     sender.send(session_command).await.unwrap();
@@ -17,7 +17,10 @@ pub enum UserAction {
     Block,
     Continue,
     Concede,
+    Undo,
+    ToggleConsole,
     ToggleDebugger,
+    ToggleBotTrace,
 }
 
 fn init(mut commands: Commands) {
@@ -25,6 +28,7 @@ fn init(mut commands: Commands) {
         (UserAction::Attack, KeyCode::KeyA),
         (UserAction::Block, KeyCode::KeyA),
         (UserAction::Continue, KeyCode::Space),
+        (UserAction::ToggleConsole, KeyCode::Backquote),
     ]);
     input_map.insert(
         UserAction::AllAttack,
@@ -34,9 +38,17 @@ fn init(mut commands: Commands) {
         UserAction::Concede,
         ButtonlikeChord::new([KeyCode::ShiftLeft, KeyCode::ControlLeft, KeyCode::KeyO]),
     );
+    input_map.insert(
+        UserAction::Undo,
+        ButtonlikeChord::new([KeyCode::ControlLeft, KeyCode::KeyZ]),
+    );
     input_map.insert(
         UserAction::ToggleDebugger,
         ButtonlikeChord::new([KeyCode::ShiftLeft, KeyCode::ControlLeft, KeyCode::KeyD]),
     );
+    input_map.insert(
+        UserAction::ToggleBotTrace,
+        ButtonlikeChord::new([KeyCode::ShiftLeft, KeyCode::ControlLeft, KeyCode::KeyB]),
+    );
     commands.spawn(InputManagerBundle::with_map(input_map));
 }
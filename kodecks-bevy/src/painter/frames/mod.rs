@@ -173,7 +173,7 @@ impl CardFrame {
         Self {
             color: attr.color,
             cost: attr.cost.value(),
-            power: attr.power.map(|p| p.value()),
+            power: attr.power().map(|p| p.value()),
             shields: attr.shields.map(|p| p.value()),
             creature_type: attr.creature_type,
         }
@@ -2,6 +2,7 @@ use base64::{engine::general_purpose::URL_SAFE, Engine as _};
 use k256::schnorr::SigningKey;
 use kodecks::archetype::ArchetypeId;
 use kodecks::deck::DeckList;
+use kodecks::profile::BotDifficulty;
 use kodecks_engine::version::VersionTag;
 use serde::{Deserialize, Serialize};
 use serde_default::DefaultFromSerde;
@@ -18,6 +19,8 @@ pub struct SaveDataV1 {
     pub decks: Decks,
     #[serde(default)]
     pub collection: Collection,
+    #[serde(default)]
+    pub settings: Settings,
 }
 
 #[derive(Debug, Clone, DefaultFromSerde, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -48,6 +51,12 @@ pub struct Collection {
     pub cards: BTreeMap<ArchetypeId, u8>,
 }
 
+#[derive(Debug, Clone, DefaultFromSerde, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub bot_difficulty: BotDifficulty,
+}
+
 #[derive(Clone, DefaultFromSerde, Serialize, Deserialize)]
 pub struct Auth {
     #[serde(
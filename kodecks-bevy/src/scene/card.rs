@@ -1,8 +1,11 @@
 use super::translator::Translator;
 use crate::scene::translator::TextPurpose;
 use bevy::{color::palettes::css, prelude::*};
+use fluent_bundle::FluentArgs;
+use fluent_content::Request;
 use kodecks::{
     card::CardSnapshot,
+    computed::ModifierKind,
     prelude::KeywordAbility,
     text::{parse_text, Section},
 };
@@ -24,7 +27,7 @@ impl UICardInfo {
             .snapshot
             .computed
             .as_ref()
-            .map(|attr| attr.abilities.as_ref())
+            .map(|attr| attr.abilities().as_ref())
             .unwrap_or_default()
             .to_vec();
 
@@ -45,7 +48,7 @@ impl UICardInfo {
             .snapshot
             .computed
             .as_ref()
-            .map(|attr| attr.abilities.as_ref())
+            .map(|attr| attr.abilities().as_ref())
             .unwrap_or_default();
 
         let mut sections = abilities
@@ -76,48 +79,111 @@ impl UICardInfo {
             ));
         }
 
-        for section in parse_text(&text) {
-            match section {
-                Section::Text(text) => {
-                    sections.push(TextSection::new(
-                        text,
-                        translator.style(TextPurpose::CardText),
-                    ));
+        sections.extend(text_to_sections(&text, translator));
+        sections
+    }
+
+    pub fn modifier_sections(
+        &self,
+        translator: &Translator,
+        catalog: &Catalog,
+    ) -> Vec<TextSection> {
+        let modifiers = self
+            .snapshot
+            .computed
+            .as_ref()
+            .map(|attr| attr.modifiers.as_slice())
+            .unwrap_or_default();
+
+        let mut sections = vec![];
+        for modifier in modifiers {
+            let mut args = FluentArgs::new();
+            if let Some(archetype) = catalog.get(modifier.archetype_id) {
+                let source = translator.get(&format!("card-{}", archetype.safe_name));
+                args.set("source", source);
+            }
+            let id = match modifier.kind {
+                ModifierKind::Cost { before, after } => {
+                    args.set("before", before);
+                    args.set("after", after);
+                    "modifier-cost"
                 }
-                Section::Card(text) => {
-                    sections.push(TextSection::new(
-                        text,
-                        TextStyle {
-                            color: css::LIGHT_BLUE.into(),
-                            ..translator.style(TextPurpose::CardText)
-                        },
-                    ));
+                ModifierKind::Power { before, after } => {
+                    args.set("before", before);
+                    args.set("after", after);
+                    "modifier-power"
                 }
-                Section::Keyword(ability) => {
-                    sections.push(TextSection::new(
-                        ability.to_string(),
-                        TextStyle {
-                            color: css::GOLD.into(),
-                            ..translator.style(TextPurpose::CardText)
-                        },
-                    ));
+                ModifierKind::Shields { before, after } => {
+                    args.set("before", before);
+                    args.set("after", after);
+                    "modifier-shields"
                 }
-                Section::Number(n) => {
-                    sections.push(TextSection::new(
-                        n.to_string(),
-                        TextStyle {
-                            color: css::LIGHT_PINK.into(),
-                            ..translator.style(TextPurpose::CardText)
-                        },
-                    ));
+                ModifierKind::AbilityGranted { ability } => {
+                    let ability = format!("ability-{}", ability.to_string().to_lowercase());
+                    args.set("ability", translator.get(&ability));
+                    "modifier-ability-granted"
                 }
+                ModifierKind::AbilityRemoved { ability } => {
+                    let ability = format!("ability-{}", ability.to_string().to_lowercase());
+                    args.set("ability", translator.get(&ability));
+                    "modifier-ability-removed"
+                }
+            };
+            if !sections.is_empty() {
+                sections.push(TextSection::new(
+                    "\n",
+                    translator.style(TextPurpose::CardText),
+                ));
             }
+            let text = translator.get(Request::new(id).args(&args));
+            sections.extend(text_to_sections(&text, translator));
         }
-
         sections
     }
 }
 
+fn text_to_sections(text: &str, translator: &Translator) -> Vec<TextSection> {
+    let mut sections = vec![];
+    for section in parse_text(text) {
+        match section {
+            Section::Text(text) => {
+                sections.push(TextSection::new(
+                    text,
+                    translator.style(TextPurpose::CardText),
+                ));
+            }
+            Section::Card(text) => {
+                sections.push(TextSection::new(
+                    text,
+                    TextStyle {
+                        color: css::LIGHT_BLUE.into(),
+                        ..translator.style(TextPurpose::CardText)
+                    },
+                ));
+            }
+            Section::Keyword(ability) => {
+                sections.push(TextSection::new(
+                    ability.to_string(),
+                    TextStyle {
+                        color: css::GOLD.into(),
+                        ..translator.style(TextPurpose::CardText)
+                    },
+                ));
+            }
+            Section::Number(n) => {
+                sections.push(TextSection::new(
+                    n.to_string(),
+                    TextStyle {
+                        color: css::LIGHT_PINK.into(),
+                        ..translator.style(TextPurpose::CardText)
+                    },
+                ));
+            }
+        }
+    }
+    sections
+}
+
 #[derive(Clone, Resource, Deref)]
 pub struct Catalog(Arc<kodecks::catalog::Catalog>);
 
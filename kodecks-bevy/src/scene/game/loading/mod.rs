@@ -1,7 +1,7 @@
 use super::{
     board, event,
     mode::{GameMode, GameModeKind},
-    server::{self, ServerConnection, ServerError},
+    server::{self, ServerConnection, ServerError, SpectatableGames},
 };
 use crate::{
     assets::AssetServerExt,
@@ -14,14 +14,16 @@ use crate::{
 };
 use bevy::prelude::*;
 use bevy_mod_picking::prelude::*;
+use fluent_bundle::FluentArgs;
+use fluent_content::Request;
 use kodecks::{
     error::Error,
     player::PlayerConfig,
-    profile::{BotConfig, GameProfile},
+    profile::{BotConfig, DebugConfig, GameProfile},
 };
 use kodecks_engine::{
     message::{Command, Input},
-    room::{RoomConfig, RoomType},
+    room::RoomConfig,
     Connection,
 };
 use std::hash::{Hash, Hasher};
@@ -43,6 +45,7 @@ impl Plugin for GameLoadingPlugin {
                 cleanup_loading_screen,
             )
             .add_systems(OnExit(GlobalState::GameLoading), cleanup_loading_screen)
+            .add_systems(OnExit(GameLoadingState::SelectGame), hide_game_picker)
             .add_systems(
                 Update,
                 (
@@ -58,6 +61,7 @@ impl Plugin for GameLoadingPlugin {
                     wait_env.run_if(resource_exists::<board::Environment>),
                     update_loading_message.run_if(state_changed::<GameLoadingState>),
                     receive_error.run_if(resource_exists_and_changed::<ServerError>),
+                    show_game_picker.run_if(resource_exists_and_changed::<SpectatableGames>),
                 )
                     .run_if(in_state(GlobalState::GameInit)),
             );
@@ -70,6 +74,10 @@ enum GameLoadingState {
     Idle,
     BotMatch,
     RandomMatch,
+    Spectate,
+    SelectGame,
+    Puzzle,
+    Tutorial,
     Error(Error),
 }
 
@@ -82,6 +90,9 @@ struct UiButton;
 #[derive(Component)]
 struct LoadingMessage;
 
+#[derive(Component)]
+struct GamePicker;
+
 fn receive_error(
     mut next_loading_state: ResMut<NextState<GameLoadingState>>,
     error: Res<ServerError>,
@@ -176,6 +187,20 @@ fn init_loading_screen(
                         Label,
                         LoadingMessage,
                     ));
+
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                margin: UiRect::top(Val::Px(20.)),
+                                align_items: AlignItems::Center,
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(10.),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        GamePicker,
+                    ));
                 });
 
             parent
@@ -244,13 +269,18 @@ fn update_loading_message(
     let mut text = query.single_mut();
     let message = match loading_state.get() {
         GameLoadingState::RandomMatch => translator.get("loading-message-finding-player"),
+        GameLoadingState::Spectate => translator.get("loading-message-finding-game"),
+        GameLoadingState::SelectGame => translator.get("loading-message-select-game"),
         GameLoadingState::Error(error) => translator.get(error.clone()),
         _ => "".into(),
     };
     text.sections[0].value = message.to_string();
 
     let visible = match loading_state.get() {
-        GameLoadingState::RandomMatch | GameLoadingState::Error(_) => Visibility::Visible,
+        GameLoadingState::RandomMatch
+        | GameLoadingState::Spectate
+        | GameLoadingState::SelectGame
+        | GameLoadingState::Error(_) => Visibility::Visible,
         _ => Visibility::Hidden,
     };
     button_query.iter_mut().for_each(|mut visibility| {
@@ -258,6 +288,94 @@ fn update_loading_message(
     });
 }
 
+/// Lists the games from the server so that the user can pick one to spectate.
+fn show_game_picker(
+    mut commands: Commands,
+    mut next_loading_state: ResMut<NextState<GameLoadingState>>,
+    query: Query<Entity, With<GamePicker>>,
+    games: Res<SpectatableGames>,
+    mode: Res<GameMode>,
+    translator: Res<Translator>,
+    asset_server: Res<AssetServer>,
+) {
+    let GameModeKind::Spectate { mode, .. } = mode.kind else {
+        return;
+    };
+    next_loading_state.set(GameLoadingState::SelectGame);
+
+    let slicer = TextureSlicer {
+        border: BorderRect::square(5.0),
+        center_scale_mode: SliceScaleMode::Stretch,
+        sides_scale_mode: SliceScaleMode::Stretch,
+        max_corner_scale: 1.0,
+    };
+    let button = asset_server.load_with_cache("ui/button.png");
+
+    let picker = query.single();
+    commands.entity(picker).despawn_descendants();
+    commands.entity(picker).with_children(|parent| {
+        for game in games.iter() {
+            let game_id = game.game_id;
+            let mut args = FluentArgs::new();
+            args.set("game", game_id);
+            args.set(
+                "players",
+                game.players
+                    .iter()
+                    .map(|player| player.to_string().chars().take(10).collect::<String>())
+                    .collect::<Vec<_>>()
+                    .join(" / "),
+            );
+            let label = translator
+                .get(Request::new("loading-button-spectate").args(&args))
+                .to_string();
+
+            parent
+                .spawn((
+                    ImageBundle {
+                        style: Style {
+                            width: Val::Px(400.),
+                            height: Val::Px(50.),
+                            padding: UiRect::all(Val::Px(15.)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        image: button.clone().into(),
+                        ..default()
+                    },
+                    ImageScaleMode::Sliced(slicer.clone()),
+                    On::<Pointer<Click>>::commands_mut(move |_, commands| {
+                        commands.add(move |w: &mut World| {
+                            if let Some(mut conn) = w.get_resource_mut::<ServerConnection>() {
+                                info!("Joining game {} as a spectator", game_id);
+                                conn.send(Input::Command(Command::Spectate { game_id, mode }));
+                            }
+                            w.remove_resource::<SpectatableGames>();
+                            if let Some(mut next_loading_state) =
+                                w.get_resource_mut::<NextState<GameLoadingState>>()
+                            {
+                                next_loading_state.set(GameLoadingState::Spectate);
+                            }
+                        });
+                    }),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(label, translator.style(TextPurpose::Button)),
+                        Label,
+                    ));
+                });
+        }
+    });
+}
+
+fn hide_game_picker(mut commands: Commands, query: Query<Entity, With<GamePicker>>) {
+    query.iter().for_each(|entity| {
+        commands.entity(entity).despawn_descendants();
+    });
+}
+
 fn init_game_mode(
     mut commands: Commands,
     mut next_loading_state: ResMut<NextState<GameLoadingState>>,
@@ -265,13 +383,20 @@ fn init_game_mode(
     save_data: Res<save_data::SaveData>,
 ) {
     match &mode.kind {
-        GameModeKind::BotMatch { bot_deck } => {
+        GameModeKind::BotMatch {
+            bot_deck,
+            difficulty,
+            personality,
+        } => {
             let mut hasher = fnv::FnvHasher::default();
             save_data.hash(&mut hasher);
             let profile = GameProfile {
                 regulation: mode.regulation.clone(),
                 card_pool: mode.card_pool.clone(),
-                debug: None,
+                debug: Some(DebugConfig {
+                    flags: mode.debug_flags(),
+                    ..Default::default()
+                }),
                 players: vec![
                     PlayerConfig {
                         deck: mode.player_deck.clone(),
@@ -280,7 +405,11 @@ fn init_game_mode(
                         deck: bot_deck.clone(),
                     },
                 ],
-                bots: vec![BotConfig { player: 1 }],
+                bots: vec![BotConfig {
+                    difficulty: *difficulty,
+                    personality: *personality,
+                    ..BotConfig::new(1)
+                }],
                 rng_seed: Some(hasher.finish()),
             };
 
@@ -295,14 +424,14 @@ fn init_game_mode(
 
             next_loading_state.set(GameLoadingState::BotMatch);
         }
-        GameModeKind::RandomMatch { server } => {
+        GameModeKind::RandomMatch { server, room_type } => {
             let key = save_data.auth.private_key.clone();
             let mut conn = ServerConnection::new_websocket(server.clone(), key);
             conn.send(Input::Command(Command::CreateRoom {
                 config: RoomConfig {
                     regulation: mode.regulation.clone(),
                     card_pool: mode.card_pool.clone(),
-                    room_type: RoomType::RandomMatch,
+                    room_type: *room_type,
                 },
                 host_player: PlayerConfig {
                     deck: mode.player_deck.clone(),
@@ -312,6 +441,44 @@ fn init_game_mode(
 
             next_loading_state.set(GameLoadingState::RandomMatch);
         }
+        GameModeKind::Spectate { server, .. } => {
+            let key = save_data.auth.private_key.clone();
+            let mut conn = ServerConnection::new_websocket(server.clone(), key);
+            conn.send(Input::Command(Command::ListGames));
+            commands.insert_resource(conn);
+
+            next_loading_state.set(GameLoadingState::Spectate);
+        }
+        GameModeKind::Puzzle { puzzle } => {
+            let log_id = format!(
+                "{}-{}",
+                chrono::Local::now().format("%Y%m%d%H%M%S"),
+                nanoid::nanoid!()
+            );
+            let mut conn = ServerConnection::new_local();
+            conn.send(Input::Command(Command::StartPuzzle {
+                log_id,
+                puzzle: puzzle.clone(),
+            }));
+            commands.insert_resource(conn);
+
+            next_loading_state.set(GameLoadingState::Puzzle);
+        }
+        GameModeKind::Tutorial { tutorial } => {
+            let log_id = format!(
+                "{}-{}",
+                chrono::Local::now().format("%Y%m%d%H%M%S"),
+                nanoid::nanoid!()
+            );
+            let mut conn = ServerConnection::new_local();
+            conn.send(Input::Command(Command::StartTutorial {
+                log_id,
+                tutorial: tutorial.clone(),
+            }));
+            commands.insert_resource(conn);
+
+            next_loading_state.set(GameLoadingState::Tutorial);
+        }
     }
 }
 
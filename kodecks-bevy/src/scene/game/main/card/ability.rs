@@ -58,7 +58,7 @@ pub fn update_ability_overlay(
                 if let Ok((mut visibility, mut material, mut overlay)) =
                     overlay_query.get_mut(*child)
                 {
-                    let ability = computed.abilities.as_ref().get(overlay.index);
+                    let ability = computed.abilities().as_ref().get(overlay.index);
 
                     if let Ok(zone) = env.find_zone(card.id) {
                         overlay.ability = ability.cloned().filter(|_| zone.zone == Zone::Field);
@@ -0,0 +1,176 @@
+use crate::{
+    input::UserAction,
+    scene::{
+        game::{board::Environment, mode::GameMode, server::SendCommand},
+        translator::{TextPurpose, Translator},
+        GlobalState,
+    },
+};
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+use bevy_mod_picking::prelude::*;
+use kodecks::{action::Action, console, profile::DebugFlags};
+use kodecks_catalog::CATALOG;
+use leafwing_input_manager::prelude::*;
+
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConsoleState>()
+            .add_systems(
+                Update,
+                (
+                    toggle,
+                    handle_input.run_if(console_open),
+                    update_ui.run_if(resource_changed::<ConsoleState>),
+                )
+                    .chain()
+                    .run_if(in_state(GlobalState::GameMain).and_then(console_enabled)),
+            )
+            .add_systems(OnEnter(GlobalState::GameLoading), init)
+            .add_systems(OnEnter(GlobalState::GameCleanup), cleanup);
+    }
+}
+
+/// The input line of the debug command console.
+#[derive(Debug, Resource, Default)]
+pub struct ConsoleState {
+    pub open: bool,
+    input: String,
+    error: Option<String>,
+}
+
+#[derive(Component)]
+struct ConsoleRoot;
+
+#[derive(Component)]
+struct ConsoleText;
+
+fn console_enabled(mode: Option<Res<GameMode>>) -> bool {
+    mode.is_some_and(|mode| mode.debug_flags().contains(DebugFlags::DEBUG_COMMAND))
+}
+
+fn console_open(state: Res<ConsoleState>) -> bool {
+    state.open
+}
+
+fn toggle(mut state: ResMut<ConsoleState>, action_query: Query<&ActionState<UserAction>>) {
+    if action_query
+        .single()
+        .just_pressed(&UserAction::ToggleConsole)
+    {
+        state.open = !state.open;
+    }
+}
+
+fn handle_input(
+    mut commands: Commands,
+    mut state: ResMut<ConsoleState>,
+    mut events: EventReader<KeyboardInput>,
+    env: Res<Environment>,
+) {
+    for event in events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => {
+                let input = std::mem::take(&mut state.input);
+                match console::parse(&input, &env, &CATALOG) {
+                    Ok(list) if list.is_empty() => {}
+                    Ok(list) => {
+                        commands.add(SendCommand(Action::DebugCommand { commands: list }));
+                        state.error = None;
+                    }
+                    Err(err) => {
+                        state.error = Some(err.to_string());
+                        state.input = input;
+                    }
+                }
+            }
+            Key::Backspace => {
+                state.input.pop();
+            }
+            Key::Escape => {
+                state.open = false;
+            }
+            Key::Space => {
+                state.input.push(' ');
+            }
+            Key::Character(text) if text.as_str() != "`" => {
+                state.input.push_str(text);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_ui(
+    state: Res<ConsoleState>,
+    mut root_query: Query<&mut Style, With<ConsoleRoot>>,
+    mut text_query: Query<&mut Text, With<ConsoleText>>,
+) {
+    root_query.single_mut().display = if state.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    let mut text = text_query.single_mut();
+    text.sections[0].value = format!("> {}_", state.input);
+    text.sections[1].value = state
+        .error
+        .as_ref()
+        .map(|error| format!("\n{error}"))
+        .unwrap_or_default();
+}
+
+fn init(mut commands: Commands, mut state: ResMut<ConsoleState>, translator: Res<Translator>) {
+    *state = ConsoleState::default();
+    commands
+        .spawn((
+            NodeBundle {
+                z_index: ZIndex::Global(3),
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(0.),
+                    width: Val::Percent(100.0),
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.8).into(),
+                ..default()
+            },
+            Pickable::IGNORE,
+            ConsoleRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new("", translator.style(TextPurpose::CardText)),
+                    TextSection::new(
+                        "",
+                        TextStyle {
+                            color: Color::srgb(1.0, 0.4, 0.4),
+                            ..translator.style(TextPurpose::CardText)
+                        },
+                    ),
+                ]),
+                Pickable::IGNORE,
+                Label,
+                ConsoleText,
+            ));
+        });
+}
+
+fn cleanup(mut commands: Commands, query: Query<Entity, With<ConsoleRoot>>) {
+    query.iter().for_each(|entity| {
+        commands.entity(entity).despawn_recursive();
+    });
+}
@@ -1,7 +1,9 @@
+use super::console::ConsoleState;
 use super::ui::ActionButton;
 use crate::input::UserAction;
 use crate::scene::game::board::{AvailableActionList, Board, Environment};
-use crate::scene::game::server::SendCommand;
+use crate::scene::game::mode::GameMode;
+use crate::scene::game::server::{PredictedState, SendCommand};
 use crate::scene::GlobalState;
 use bevy::prelude::*;
 use kodecks::action::{Action, AvailableAction};
@@ -41,6 +43,8 @@ fn handle_player_events(
     mut board: ResMut<Board>,
     env: Res<Environment>,
     list: Res<AvailableActionList>,
+    mode: Res<GameMode>,
+    console: Res<ConsoleState>,
     mut events: EventReader<PlayerEvent>,
     mut finished: EventWriter<PlayerEventFinished>,
     action_query: Query<&ActionState<UserAction>>,
@@ -49,6 +53,7 @@ fn handle_player_events(
     let mut action = events.read().find_map(|event| match event {
         PlayerEvent::ButtonPressed(button) => match button {
             ActionButton::EndTurn => Some(Action::EndTurn),
+            ActionButton::Undo => Some(Action::Undo),
             ActionButton::Block(_) | ActionButton::Continue => Some(Action::Block {
                 pairs: board.blocking_pairs().copied().collect(),
             }),
@@ -90,7 +95,8 @@ fn handle_player_events(
     });
 
     let action_state = action_query.single();
-    if action_state.just_pressed(&UserAction::Continue) {
+    let pressed = |action| !console.open && action_state.just_pressed(&action);
+    if pressed(UserAction::Continue) {
         if !list.blockers().is_empty() {
             action = Some(Action::Block { pairs: vec![] });
         } else {
@@ -98,12 +104,12 @@ fn handle_player_events(
         }
         board.clear_battle();
     }
-    if action_state.just_pressed(&UserAction::Attack) && board.attackers().next().is_some() {
+    if pressed(UserAction::Attack) && board.attackers().next().is_some() {
         action = Some(Action::Attack {
             attackers: board.attackers().copied().collect(),
         });
     }
-    if action_state.just_pressed(&UserAction::AllAttack) {
+    if pressed(UserAction::AllAttack) {
         if let Some(attackers) = list.iter().find_map(|action| match action {
             AvailableAction::Attack { attackers } => Some(attackers.clone()),
             _ => None,
@@ -111,24 +117,34 @@ fn handle_player_events(
             action = Some(Action::Attack { attackers });
         }
     }
-    if action_state.just_pressed(&UserAction::Block) && !list.blockers().is_empty() {
+    if pressed(UserAction::Block) && !list.blockers().is_empty() {
         action = Some(Action::Block {
             pairs: board.blocking_pairs().copied().collect(),
         });
     }
-    if action_state.just_pressed(&UserAction::Concede) {
+    if pressed(UserAction::Concede) {
         action = Some(Action::Concede);
     }
+    if pressed(UserAction::Undo) && mode.undo_enabled() {
+        action = Some(Action::Undo);
+    }
 
     if let Some(action) = action {
         commands.add(SendCommand(action.clone()));
 
         let mut env = env.clone();
         let available_actions = env.tick(action.clone()).available_actions;
+        if matches!(action, Action::CastCard { .. }) {
+            commands.insert_resource(PredictedState::new(&env));
+        }
 
         if matches!(
             action,
-            Action::Attack { .. } | Action::Block { .. } | Action::EndTurn | Action::Concede
+            Action::Attack { .. }
+                | Action::Block { .. }
+                | Action::EndTurn
+                | Action::Concede
+                | Action::Undo
         ) {
             commands.insert_resource(AvailableActionList::new(
                 available_actions
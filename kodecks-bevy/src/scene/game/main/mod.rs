@@ -3,6 +3,7 @@ use bevy::prelude::*;
 mod animation;
 mod battle;
 mod card;
+mod console;
 mod deck;
 mod dialog;
 mod event;
@@ -11,6 +12,7 @@ mod pointer;
 mod setup;
 mod shard;
 mod stack;
+mod trace;
 mod turn;
 mod ui;
 
@@ -31,6 +33,8 @@ impl Plugin for GameMainPlugin {
             .add_plugins(stack::StackPlugin)
             .add_plugins(pointer::PointerPlugin)
             .add_plugins(dialog::DialogPlugin)
+            .add_plugins(console::ConsolePlugin)
+            .add_plugins(trace::BotTracePlugin)
             .add_plugins(turn::TurnPlugin)
             .add_systems(Startup, setup::setup);
     }
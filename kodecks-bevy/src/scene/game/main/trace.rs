@@ -0,0 +1,163 @@
+use crate::{
+    input::UserAction,
+    scene::{
+        game::{mode::GameMode, server::BotDecision},
+        translator::{TextPurpose, Translator},
+        GlobalState,
+    },
+};
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+use kodecks::{action::Action, profile::DebugFlags};
+use kodecks_bot::{BotDecisionTrace, CandidateTrace};
+use leafwing_input_manager::prelude::*;
+
+pub struct BotTracePlugin;
+
+impl Plugin for BotTracePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BotTraceState>()
+            .add_systems(
+                Update,
+                (
+                    toggle,
+                    update_ui.run_if(
+                        resource_changed::<BotTraceState>
+                            .or_else(resource_exists_and_changed::<BotDecision>),
+                    ),
+                )
+                    .chain()
+                    .run_if(in_state(GlobalState::GameMain).and_then(trace_enabled)),
+            )
+            .add_systems(OnEnter(GlobalState::GameLoading), init)
+            .add_systems(OnEnter(GlobalState::GameCleanup), cleanup);
+    }
+}
+
+/// Whether the panel showing the latest decision of the bot is open.
+#[derive(Debug, Resource, Default)]
+pub struct BotTraceState {
+    pub open: bool,
+}
+
+#[derive(Component)]
+struct BotTraceRoot;
+
+#[derive(Component)]
+struct BotTraceText;
+
+fn trace_enabled(mode: Option<Res<GameMode>>) -> bool {
+    mode.is_some_and(|mode| mode.debug_flags().contains(DebugFlags::BOT_TRACE))
+}
+
+fn toggle(mut state: ResMut<BotTraceState>, action_query: Query<&ActionState<UserAction>>) {
+    if action_query
+        .single()
+        .just_pressed(&UserAction::ToggleBotTrace)
+    {
+        state.open = !state.open;
+    }
+}
+
+fn update_ui(
+    state: Res<BotTraceState>,
+    decision: Option<Res<BotDecision>>,
+    mut root_query: Query<&mut Style, With<BotTraceRoot>>,
+    mut text_query: Query<&mut Text, With<BotTraceText>>,
+) {
+    root_query.single_mut().display = if state.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    text_query.single_mut().sections[0].value = decision
+        .map(|decision| format_trace(&decision))
+        .unwrap_or_else(|| "No decision yet".to_string());
+}
+
+fn format_trace(trace: &BotDecisionTrace) -> String {
+    let mut candidates = trace.candidates.iter().collect::<Vec<_>>();
+    candidates.sort_by_key(|candidate| -candidate.score());
+    let mut text = format!("Turn {} / Player {}", trace.turn, trace.player);
+    for candidate in candidates {
+        let chosen = trace
+            .chosen
+            .as_ref()
+            .is_some_and(|action| same_action(action, &candidate.action));
+        text.push_str(&format!(
+            "\n{} {:>5} ({} - {}) {}",
+            if chosen { "*" } else { " " },
+            candidate.score(),
+            candidate.action_score,
+            candidate.base_score,
+            format_candidate(candidate)
+        ));
+    }
+    text
+}
+
+fn format_candidate(candidate: &CandidateTrace) -> String {
+    let action = match &candidate.action {
+        Action::CastCard { .. } => "Cast",
+        Action::SelectCard { .. } => "Select",
+        Action::Attack { .. } => "Attack",
+        Action::Block { .. } => "Block",
+        Action::EndTurn => "End turn",
+        Action::Continue => "Continue",
+        _ => "Other",
+    };
+    let outcome = candidate
+        .outcome
+        .players
+        .iter()
+        .map(|player| {
+            format!(
+                "P{}: life {} hand {} field {}",
+                player.id, player.life, player.hand, player.field
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{action} [{}] -> {outcome}", candidate.cards.join(", "))
+}
+
+fn same_action(a: &Action, b: &Action) -> bool {
+    format!("{a:?}") == format!("{b:?}")
+}
+
+fn init(mut commands: Commands, mut state: ResMut<BotTraceState>, translator: Res<Translator>) {
+    *state = BotTraceState::default();
+    commands
+        .spawn((
+            NodeBundle {
+                z_index: ZIndex::Global(3),
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.),
+                    right: Val::Px(0.),
+                    max_width: Val::Percent(60.0),
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.8).into(),
+                ..default()
+            },
+            Pickable::IGNORE,
+            BotTraceRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section("", translator.style(TextPurpose::CardText)),
+                Pickable::IGNORE,
+                Label,
+                BotTraceText,
+            ));
+        });
+}
+
+fn cleanup(mut commands: Commands, query: Query<Entity, With<BotTraceRoot>>) {
+    query.iter().for_each(|entity| {
+        commands.entity(entity).despawn_recursive();
+    });
+}
@@ -6,6 +6,7 @@ use crate::{
         game::{
             board::{self, AvailableActionList, Board},
             event::InstructionsUpdated,
+            mode::GameMode,
         },
         translator::{TextPurpose, Translator},
         GlobalState,
@@ -66,6 +67,7 @@ pub enum ActionButton {
     Attack(u32),
     Block(u32),
     Continue,
+    Undo,
 }
 
 impl ActionButton {
@@ -87,6 +89,7 @@ impl ActionButton {
                     .into()
             }
             Self::Continue => translator.get("continue-button").into(),
+            Self::Undo => translator.get("undo-button").into(),
         }
     }
 }
@@ -116,8 +119,10 @@ fn update_ui(
 fn update_action_list(
     board: Res<Board>,
     list: Res<AvailableActionList>,
+    mode: Res<GameMode>,
     mut state: ResMut<UIState>,
 ) {
+    let undo = (mode.undo_enabled() && !list.is_empty()).then_some(ActionButton::Undo);
     state.available_buttons = list
         .iter()
         .filter_map(|action| match action {
@@ -140,6 +145,7 @@ fn update_action_list(
             AvailableAction::EndTurn => Some(ActionButton::EndTurn),
             _ => None,
         })
+        .chain(undo)
         .collect();
 }
 
@@ -211,6 +217,7 @@ fn update_card_info(
                     translator.style(TextPurpose::CardName),
                 )],
                 CardInfo::Text => card.text_sections(&translator, &catalog),
+                CardInfo::Modifiers => card.modifier_sections(&translator, &catalog),
                 _ => vec![],
             }
         } else {
@@ -328,6 +335,7 @@ pub enum CardInfo {
     Image,
     Name,
     Text,
+    Modifiers,
 }
 
 pub fn init(mut commands: Commands, translator: Res<Translator>, asset_server: Res<AssetServer>) {
@@ -482,6 +490,25 @@ pub fn init(mut commands: Commands, translator: Res<Translator>, asset_server: R
                                             ));
                                         });
 
+                                    parent
+                                        .spawn((NodeBundle {
+                                            style: Style {
+                                                padding: UiRect::all(Val::Px(5.)),
+                                                ..default()
+                                            },
+                                            ..default()
+                                        },))
+                                        .with_children(|parent| {
+                                            parent.spawn((
+                                                TextBundle::from_section(
+                                                    "",
+                                                    translator.style(TextPurpose::CardText),
+                                                ),
+                                                Label,
+                                                CardInfo::Modifiers,
+                                            ));
+                                        });
+
                                     parent.spawn((
                                         NodeBundle {
                                             style: Style {
@@ -703,6 +730,40 @@ pub fn init(mut commands: Commands, translator: Res<Translator>, asset_server: R
                                         Label,
                                     ));
                                 });
+
+                            parent
+                                .spawn((
+                                    ImageBundle {
+                                        style: Style {
+                                            width: Val::Percent(100.),
+                                            height: Val::Px(50.),
+                                            padding: UiRect::all(Val::Px(15.)),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..default()
+                                        },
+                                        image: button.clone().into(),
+                                        ..default()
+                                    },
+                                    ImageScaleMode::Sliced(slicer.clone()),
+                                    On::<Pointer<Click>>::commands_mut(move |_, commands| {
+                                        commands.add(move |w: &mut World| {
+                                            w.send_event(PlayerEvent::ButtonPressed(
+                                                ActionButton::Undo,
+                                            ));
+                                        });
+                                    }),
+                                    ActionButton::Undo,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        TextBundle::from_section(
+                                            "",
+                                            translator.style(TextPurpose::Button),
+                                        ),
+                                        Label,
+                                    ));
+                                });
                         });
                 });
 
@@ -1,5 +1,12 @@
 use bevy::prelude::Resource;
-use kodecks::{deck::DeckList, pool::CardPool, regulation::Regulation};
+use kodecks::{
+    deck::DeckList,
+    pool::CardPool,
+    profile::{BotDifficulty, BotPersonality, DebugFlags},
+    regulation::Regulation,
+    scenario::{Puzzle, Tutorial},
+};
+use kodecks_engine::{room::RoomType, spectate::SpectateMode};
 use url::Url;
 
 #[derive(Debug, Clone, Resource)]
@@ -12,6 +19,46 @@ pub struct GameMode {
 
 #[derive(Debug, Clone)]
 pub enum GameModeKind {
-    BotMatch { bot_deck: DeckList },
-    RandomMatch { server: Url },
+    BotMatch {
+        bot_deck: DeckList,
+        difficulty: BotDifficulty,
+        personality: BotPersonality,
+    },
+    RandomMatch {
+        server: Url,
+        room_type: RoomType,
+    },
+    Spectate {
+        server: Url,
+        mode: SpectateMode,
+    },
+    Puzzle {
+        puzzle: Box<Puzzle>,
+    },
+    Tutorial {
+        tutorial: Box<Tutorial>,
+    },
+}
+
+impl GameMode {
+    /// Returns the debug flags of the games started in this mode.
+    /// Debug commands and bot traces are only available to bot matches in debug builds,
+    /// while undo is also available to casual matches.
+    pub fn debug_flags(&self) -> DebugFlags {
+        match self.kind {
+            GameModeKind::BotMatch { .. } if cfg!(debug_assertions) => {
+                DebugFlags::UNDO | DebugFlags::DEBUG_COMMAND | DebugFlags::BOT_TRACE
+            }
+            GameModeKind::RandomMatch {
+                room_type: RoomType::Casual,
+                ..
+            } => DebugFlags::UNDO,
+            _ => DebugFlags::empty(),
+        }
+    }
+
+    /// Returns true if the player may take back their actions.
+    pub fn undo_enabled(&self) -> bool {
+        self.debug_flags().contains(DebugFlags::UNDO)
+    }
 }
@@ -1,4 +1,4 @@
-use super::{super::GlobalState, board::Environment};
+use super::{super::GlobalState, board::Environment, server::Session};
 use crate::scene::translator::{TextPurpose, Translator};
 use bevy::prelude::*;
 use bevy_mod_picking::prelude::*;
@@ -33,6 +33,7 @@ enum State {
 fn init(
     mut commands: Commands,
     env: Res<Environment>,
+    session: Option<Res<Session>>,
     translator: Res<Translator>,
     mut next_state: ResMut<NextState<State>>,
 ) {
@@ -45,9 +46,13 @@ fn init(
                 EndgameReason::DeckOut => "reason-deck-out",
                 EndgameReason::LifeZero => "reason-life-zero",
                 EndgameReason::SimultaneousEnd => "reason-simultaneous-end",
+                EndgameReason::TurnLimit => "reason-turn-limit",
             };
+            let spectator = session.is_some_and(|session| session.is_spectator());
             let request = if let Some(winner) = winner {
-                if env.player == winner {
+                if spectator {
+                    Request::new("result-game-over")
+                } else if env.player == winner {
                     Request::new("result-victory")
                 } else {
                     Request::new("result-defeat")
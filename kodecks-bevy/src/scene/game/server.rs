@@ -1,3 +1,4 @@
+use super::mode::{GameMode, GameModeKind};
 use crate::scene::{spinner::SpinnerState, GlobalState};
 use bevy::{ecs::world::Command, prelude::*};
 use futures::{
@@ -9,10 +10,17 @@ use futures::{
 };
 use futures_util::SinkExt;
 use k256::{ecdsa::signature::SignerMut, schnorr::SigningKey};
-use kodecks::{action::Action, env::LocalGameState, error::Error};
+use kodecks::{
+    action::Action,
+    env::{LocalEnvironment, LocalGameState},
+    error::Error,
+};
+use kodecks_bot::BotDecisionTrace;
 use kodecks_engine::{
+    delta::StateDecoder,
     login::{LoginRequest, LoginResponse, LoginType},
     message::{self, GameEventKind, Input, Output},
+    spectate::{GameSummary, SpectateMode},
     well_known::WellKnown,
     Connection,
 };
@@ -37,6 +45,9 @@ impl Plugin for ServerPlugin {
 
 fn cleanup(mut commands: Commands) {
     commands.remove_resource::<Session>();
+    commands.remove_resource::<BotDecision>();
+    commands.remove_resource::<PredictedState>();
+    commands.remove_resource::<SpectatableGames>();
     commands.remove_resource::<ServerConnection>();
 }
 
@@ -289,17 +300,71 @@ async fn connect_websocket(server: Url, key: SigningKey) -> anyhow::Result<WebSo
 }
 
 #[derive(Resource)]
-struct Session {
+pub struct Session {
     id: u32,
     player: u8,
+    spectator: bool,
+}
+
+impl Session {
+    pub fn is_spectator(&self) -> bool {
+        self.spectator
+    }
+}
+
+/// The state predicted by [`LocalEnvironment::tick`] while the action is sent to the server.
+#[derive(Resource)]
+pub struct PredictedState(LocalEnvironment);
+
+impl PredictedState {
+    pub fn new(env: &LocalEnvironment) -> Self {
+        Self(env.clone())
+    }
+
+    /// Returns `true` if the prediction matches the state the server ended up in.
+    ///
+    /// The server takes more steps than the prediction for the same action,
+    /// so the timestamp is not compared.
+    fn matches(&self, timestamp: u32, checksum: u64) -> bool {
+        let mut env = self.0.clone();
+        env.timestamp = timestamp;
+        env.checksum() == checksum
+    }
 }
 
+/// The games listed by the server for the user to pick one to spectate.
+#[derive(Resource, Deref)]
+pub struct SpectatableGames(Vec<GameSummary>);
+
+/// The trace of the latest decision of the bot.
+#[derive(Resource, Deref)]
+pub struct BotDecision(Box<BotDecisionTrace>);
+
 fn recv_events(
     mut commands: Commands,
     mut server: ResMut<ServerConnection>,
     mut events: EventWriter<ServerEvent>,
     mut next_spinner_state: ResMut<NextState<SpinnerState>>,
+    mut decoder: Local<StateDecoder>,
+    mut spectating: Local<Option<SpectateMode>>,
+    mode: Option<Res<GameMode>>,
+    prediction: Option<Res<PredictedState>>,
 ) {
+    let mut prediction = prediction.map(Res::into_inner);
+    let mut verify_prediction = |commands: &mut Commands, timestamp: u32, checksum: u64| {
+        // States sent before the predicted action was processed are skipped.
+        if let Some(predicted) = prediction.filter(|predicted| timestamp >= predicted.0.timestamp) {
+            if !predicted.matches(timestamp, checksum) {
+                info!(
+                    "Predicted state replaced by the server state at timestamp {}",
+                    timestamp
+                );
+            }
+            commands.remove_resource::<PredictedState>();
+            prediction = None;
+        }
+    };
+
     while let Some(event) = server.recv() {
         commands.remove_resource::<ServerError>();
 
@@ -309,18 +374,95 @@ fn recv_events(
                     commands.insert_resource(Session {
                         id: event.game_id,
                         player: event.player,
+                        spectator: false,
+                    });
+                    decoder.clear();
+                    *spectating = None;
+                }
+                GameEventKind::Spectating { mode, .. } => {
+                    info!("Spectating game {} ({:?})", event.game_id, mode);
+                    commands.insert_resource(Session {
+                        id: event.game_id,
+                        player: event.player,
+                        spectator: true,
                     });
+                    decoder.clear();
+                    *spectating = Some(mode);
                 }
-                GameEventKind::StateUpdated { state } => {
+                GameEventKind::StateUpdated { state, checksum } => {
+                    // A corrupted snapshot must not replace the predicted state,
+                    // so the full state is requested again instead.
+                    if state.env.checksum() != checksum {
+                        warn!(
+                            "State checksum mismatch at timestamp {}",
+                            state.env.timestamp
+                        );
+                        let input = if let Some(mode) = *spectating {
+                            Input::Command(message::Command::Spectate {
+                                game_id: event.game_id,
+                                mode,
+                            })
+                        } else {
+                            Input::GameCommand(message::GameCommand {
+                                game_id: event.game_id,
+                                player: event.player,
+                                kind: message::GameCommandKind::Resync,
+                            })
+                        };
+                        server.send(input);
+                        continue;
+                    }
+                    decoder.push(&state.env);
+                    if spectating.is_none() {
+                        verify_prediction(&mut commands, state.env.timestamp, checksum);
+                        server.send(Input::GameCommand(message::GameCommand {
+                            game_id: event.game_id,
+                            player: event.player,
+                            kind: message::GameCommandKind::Acknowledge {
+                                timestamp: state.env.timestamp,
+                                checksum,
+                            },
+                        }));
+                    }
                     events.send(ServerEvent(state));
                     next_spinner_state.set(SpinnerState::Off);
                 }
+                GameEventKind::StateDelta { delta, checksum } => {
+                    let kind = match decoder.apply(*delta, checksum) {
+                        Ok(state) => {
+                            let timestamp = state.env.timestamp;
+                            verify_prediction(&mut commands, timestamp, checksum);
+                            events.send(ServerEvent(Box::new(state)));
+                            next_spinner_state.set(SpinnerState::Off);
+                            message::GameCommandKind::Acknowledge {
+                                timestamp,
+                                checksum,
+                            }
+                        }
+                        Err(err) => {
+                            warn!("Failed to apply state delta: {}", err);
+                            message::GameCommandKind::Resync
+                        }
+                    };
+                    server.send(Input::GameCommand(message::GameCommand {
+                        game_id: event.game_id,
+                        player: event.player,
+                        kind,
+                    }));
+                }
                 GameEventKind::PlayerThinking { thinking, timeout } => {
                     info!("Player {} is thinking: {:?}", thinking, timeout);
                     if thinking != event.player {
                         next_spinner_state.set(SpinnerState::On);
                     }
                 }
+                GameEventKind::ActionRejected { action, reason } => {
+                    warn!("Action rejected: {:?} ({})", action, reason);
+                    next_spinner_state.set(SpinnerState::Off);
+                }
+                GameEventKind::BotDecision { trace } => {
+                    commands.insert_resource(BotDecision(trace));
+                }
             },
             Output::RoomEvent(event) => match event.event {
                 message::RoomEventKind::Created => {
@@ -334,6 +476,19 @@ fn recv_events(
                     }));
                 }
             },
+            Output::GameList(games) => {
+                if !matches!(
+                    mode.as_ref().map(|mode| &mode.kind),
+                    Some(GameModeKind::Spectate { .. })
+                ) {
+                    continue;
+                }
+                if games.is_empty() {
+                    commands.insert_resource(ServerError(Error::GameNotFound));
+                } else {
+                    commands.insert_resource(SpectatableGames(games));
+                }
+            }
             Output::Error(err) => {
                 error!("Error: {}", err);
                 commands.insert_resource(ServerError(err));
@@ -347,6 +502,9 @@ pub struct SendCommand(pub Action);
 impl Command for SendCommand {
     fn apply(self, world: &mut World) {
         if let Some(session) = world.get_resource::<Session>() {
+            if session.spectator {
+                return;
+            }
             let id = session.id;
             let player = session.player;
             if let Some(mut conn) = world.get_resource_mut::<ServerConnection>() {
@@ -7,8 +7,20 @@ use super::{
 use crate::{app_version, assets::AssetServerExt, config::GlobalConfig, save_data};
 use bevy::prelude::*;
 use bevy_mod_picking::prelude::*;
-use kodecks::{deck::DeckList, pool::CardPool, regulation::Regulation};
-use kodecks_catalog::decks::{blue_deck, red_deck};
+use kodecks::{
+    deck::DeckList,
+    pool::CardPool,
+    profile::{BotDifficulty, BotPersonality},
+    regulation::Regulation,
+    scenario::{Puzzle, Tutorial},
+};
+use kodecks_bot::deckbuild::DeckBuilder;
+use kodecks_catalog::{
+    decks::{blue_deck, red_deck},
+    puzzles::PUZZLE_LIST,
+    tutorials::TUTORIAL_LIST,
+};
+use kodecks_engine::{room::RoomType, spectate::SpectateMode};
 
 pub struct MenuPlugin;
 
@@ -24,10 +36,26 @@ impl Plugin for MenuPlugin {
 #[derive(Component)]
 struct UiRoot;
 
+#[derive(Component)]
+struct DifficultyText;
+
 #[derive(Event)]
 enum MenuEvent {
-    StartBotMatch { deck_list: DeckList },
+    StartBotMatch {
+        deck_list: DeckList,
+        personality: BotPersonality,
+    },
+    StartRandomDeckMatch,
+    ChangeDifficulty,
     StartRandomMatch,
+    StartCasualMatch,
+    Spectate,
+    StartPuzzle {
+        puzzle: Box<Puzzle>,
+    },
+    StartTutorial {
+        tutorial: Box<Tutorial>,
+    },
     EditDeck,
 }
 
@@ -36,6 +64,7 @@ fn init(
     translator: Res<Translator>,
     asset_server: Res<AssetServer>,
     catalog: Res<Catalog>,
+    save_data: Res<save_data::SaveData>,
 ) {
     let catalog = catalog.clone();
     let slicer = TextureSlicer {
@@ -107,6 +136,41 @@ fn init(
                     ..default()
                 })
                 .with_children(|parent| {
+                    for tutorial in TUTORIAL_LIST.iter() {
+                        parent
+                            .spawn((
+                                ImageBundle {
+                                    style: Style {
+                                        width: Val::Px(280.),
+                                        height: Val::Px(50.),
+                                        padding: UiRect::all(Val::Px(15.)),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..default()
+                                    },
+                                    image: button.clone().into(),
+                                    ..default()
+                                },
+                                ImageScaleMode::Sliced(slicer.clone()),
+                                On::<Pointer<Click>>::commands_mut(move |_, commands| {
+                                    commands.add(move |w: &mut World| {
+                                        w.send_event(MenuEvent::StartTutorial {
+                                            tutorial: Box::new(tutorial.clone()),
+                                        });
+                                    });
+                                }),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle::from_section(
+                                        translator.get(&tutorial.title),
+                                        translator.style(TextPurpose::Button),
+                                    ),
+                                    Label,
+                                ));
+                            });
+                    }
+
                     parent
                         .spawn((
                             ImageBundle {
@@ -127,6 +191,7 @@ fn init(
                                 commands.add(move |w: &mut World| {
                                     w.send_event(MenuEvent::StartBotMatch {
                                         deck_list: red_deck(&catalog),
+                                        personality: BotPersonality::Aggressive,
                                     });
                                 });
                             }),
@@ -161,6 +226,7 @@ fn init(
                                 commands.add(move |w: &mut World| {
                                     w.send_event(MenuEvent::StartBotMatch {
                                         deck_list: blue_deck(&catalog),
+                                        personality: BotPersonality::Control,
                                     });
                                 });
                             }),
@@ -175,6 +241,70 @@ fn init(
                             ));
                         });
 
+                    parent
+                        .spawn((
+                            ImageBundle {
+                                style: Style {
+                                    width: Val::Px(280.),
+                                    height: Val::Px(50.),
+                                    padding: UiRect::all(Val::Px(15.)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                image: button.clone().into(),
+                                ..default()
+                            },
+                            ImageScaleMode::Sliced(slicer.clone()),
+                            On::<Pointer<Click>>::commands_mut(move |_, commands| {
+                                commands.add(move |w: &mut World| {
+                                    w.send_event(MenuEvent::StartRandomDeckMatch);
+                                });
+                            }),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    translator.get("menu-button-cpu-match-random"),
+                                    translator.style(TextPurpose::Button),
+                                ),
+                                Label,
+                            ));
+                        });
+
+                    parent
+                        .spawn((
+                            ImageBundle {
+                                style: Style {
+                                    width: Val::Px(280.),
+                                    height: Val::Px(50.),
+                                    padding: UiRect::all(Val::Px(15.)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                image: button.clone().into(),
+                                ..default()
+                            },
+                            ImageScaleMode::Sliced(slicer.clone()),
+                            On::<Pointer<Click>>::commands_mut(move |_, commands| {
+                                commands.add(move |w: &mut World| {
+                                    w.send_event(MenuEvent::ChangeDifficulty);
+                                });
+                            }),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    translator
+                                        .get(difficulty_label(save_data.settings.bot_difficulty)),
+                                    translator.style(TextPurpose::Button),
+                                ),
+                                Label,
+                                DifficultyText,
+                            ));
+                        });
+
                     parent
                         .spawn((
                             ImageBundle {
@@ -206,6 +336,103 @@ fn init(
                             ));
                         });
 
+                    parent
+                        .spawn((
+                            ImageBundle {
+                                style: Style {
+                                    width: Val::Px(280.),
+                                    height: Val::Px(50.),
+                                    padding: UiRect::all(Val::Px(15.)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                image: button.clone().into(),
+                                ..default()
+                            },
+                            ImageScaleMode::Sliced(slicer.clone()),
+                            On::<Pointer<Click>>::commands_mut(move |_, commands| {
+                                commands.add(move |w: &mut World| {
+                                    w.send_event(MenuEvent::StartCasualMatch);
+                                });
+                            }),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    translator.get("menu-button-casual-match"),
+                                    translator.style(TextPurpose::Button),
+                                ),
+                                Label,
+                            ));
+                        });
+
+                    parent
+                        .spawn((
+                            ImageBundle {
+                                style: Style {
+                                    width: Val::Px(280.),
+                                    height: Val::Px(50.),
+                                    padding: UiRect::all(Val::Px(15.)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                image: button.clone().into(),
+                                ..default()
+                            },
+                            ImageScaleMode::Sliced(slicer.clone()),
+                            On::<Pointer<Click>>::commands_mut(move |_, commands| {
+                                commands.add(move |w: &mut World| {
+                                    w.send_event(MenuEvent::Spectate);
+                                });
+                            }),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    translator.get("menu-button-spectate"),
+                                    translator.style(TextPurpose::Button),
+                                ),
+                                Label,
+                            ));
+                        });
+
+                    for puzzle in PUZZLE_LIST.iter() {
+                        parent
+                            .spawn((
+                                ImageBundle {
+                                    style: Style {
+                                        width: Val::Px(280.),
+                                        height: Val::Px(50.),
+                                        padding: UiRect::all(Val::Px(15.)),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..default()
+                                    },
+                                    image: button.clone().into(),
+                                    ..default()
+                                },
+                                ImageScaleMode::Sliced(slicer.clone()),
+                                On::<Pointer<Click>>::commands_mut(move |_, commands| {
+                                    commands.add(move |w: &mut World| {
+                                        w.send_event(MenuEvent::StartPuzzle {
+                                            puzzle: Box::new(puzzle.clone()),
+                                        });
+                                    });
+                                }),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle::from_section(
+                                        translator.get(&puzzle.title),
+                                        translator.style(TextPurpose::Button),
+                                    ),
+                                    Label,
+                                ));
+                            });
+                    }
+
                     parent
                         .spawn((
                             ImageBundle {
@@ -274,12 +501,23 @@ fn init(
         });
 }
 
+fn difficulty_label(difficulty: BotDifficulty) -> &'static str {
+    match difficulty {
+        BotDifficulty::Easy => "menu-button-difficulty-easy",
+        BotDifficulty::Normal => "menu-button-difficulty-normal",
+        BotDifficulty::Hard => "menu-button-difficulty-hard",
+    }
+}
+
 fn handle_menu_events(
     mut commands: Commands,
     mut events: EventReader<MenuEvent>,
     mut next_state: ResMut<NextState<GlobalState>>,
-    save_data: Res<save_data::SaveData>,
+    mut save_data: ResMut<save_data::SaveData>,
+    mut text_query: Query<&mut Text, With<DifficultyText>>,
+    translator: Res<Translator>,
     config: Res<GlobalConfig>,
+    catalog: Res<Catalog>,
 ) {
     let event = if let Some(event) = events.read().next() {
         event
@@ -288,18 +526,78 @@ fn handle_menu_events(
     };
 
     let (kind, deck) = match &event {
-        MenuEvent::StartBotMatch { deck_list } => (
+        MenuEvent::StartBotMatch {
+            deck_list,
+            personality,
+        } => (
             GameModeKind::BotMatch {
                 bot_deck: deck_list.clone(),
+                difficulty: save_data.settings.bot_difficulty,
+                personality: *personality,
             },
-            save_data.decks.get_default("offline").unwrap(),
+            save_data.decks.get_default("offline").unwrap().clone(),
         ),
+        MenuEvent::StartRandomDeckMatch => {
+            let card_pool = CardPool::default();
+            let mut builder = DeckBuilder::new(&catalog, &card_pool, &Regulation::STANDARD);
+            let (Some(bot_deck), Some(player_deck)) = (builder.build(), builder.build()) else {
+                return;
+            };
+            (
+                GameModeKind::BotMatch {
+                    bot_deck,
+                    difficulty: save_data.settings.bot_difficulty,
+                    personality: BotPersonality::Balanced,
+                },
+                player_deck,
+            )
+        }
         MenuEvent::StartRandomMatch => (
             GameModeKind::RandomMatch {
                 server: config.server.clone(),
+                room_type: RoomType::RandomMatch,
+            },
+            save_data.decks.get_default("online").unwrap().clone(),
+        ),
+        MenuEvent::StartCasualMatch => (
+            GameModeKind::RandomMatch {
+                server: config.server.clone(),
+                room_type: RoomType::Casual,
             },
-            save_data.decks.get_default("online").unwrap(),
+            save_data.decks.get_default("online").unwrap().clone(),
         ),
+        MenuEvent::Spectate => (
+            GameModeKind::Spectate {
+                server: config.server.clone(),
+                mode: SpectateMode::Observer,
+            },
+            save_data.decks.get_default("online").unwrap().clone(),
+        ),
+        MenuEvent::StartPuzzle { puzzle } => (
+            GameModeKind::Puzzle {
+                puzzle: puzzle.clone(),
+            },
+            save_data.decks.get_default("offline").unwrap().clone(),
+        ),
+        MenuEvent::StartTutorial { tutorial } => (
+            GameModeKind::Tutorial {
+                tutorial: tutorial.clone(),
+            },
+            save_data.decks.get_default("offline").unwrap().clone(),
+        ),
+        MenuEvent::ChangeDifficulty => {
+            let difficulty = save_data.settings.bot_difficulty;
+            let index = BotDifficulty::ALL
+                .iter()
+                .position(|d| *d == difficulty)
+                .unwrap_or_default();
+            let difficulty = BotDifficulty::ALL[(index + 1) % BotDifficulty::ALL.len()];
+            save_data.settings.bot_difficulty = difficulty;
+            for mut text in text_query.iter_mut() {
+                text.sections[0].value = translator.get(difficulty_label(difficulty)).to_string();
+            }
+            return;
+        }
         MenuEvent::EditDeck => {
             next_state.set(GlobalState::DeckMain);
             return;
@@ -309,7 +607,7 @@ fn handle_menu_events(
     let mode = GameMode {
         regulation: Regulation::STANDARD,
         card_pool: CardPool::default(),
-        player_deck: deck.clone(),
+        player_deck: deck,
         kind,
     };
 
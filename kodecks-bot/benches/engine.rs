@@ -0,0 +1,104 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use kodecks::{
+    action::Action, env::Environment, player::PlayerConfig, profile::GameProfile, replay::Replay,
+};
+use kodecks_bot::{Bot, BotView, DefaultBot, MctsBot, MctsConfig};
+use kodecks_catalog::{decks, CATALOG};
+use rand::{rngs::SmallRng, SeedableRng};
+use std::sync::Arc;
+
+fn profile() -> GameProfile {
+    GameProfile {
+        players: vec![
+            PlayerConfig {
+                deck: decks::starter_deck(&CATALOG),
+            },
+            PlayerConfig {
+                deck: decks::red_deck(&CATALOG),
+            },
+        ],
+        rng_seed: Some(0),
+        ..Default::default()
+    }
+}
+
+/// Plays a game between two default bots and records it.
+fn play_game(profile: GameProfile) -> Replay {
+    let mut replay = Replay::new(profile.clone());
+    let mut env = Arc::new(Environment::new(profile, CATALOG.clone()));
+    let mut bots = (0..2)
+        .map(|_| {
+            DefaultBot::builder()
+                .with_rng(SmallRng::seed_from_u64(0))
+                .build()
+        })
+        .collect::<Vec<_>>();
+
+    let mut player = 0;
+    let mut action = None;
+    loop {
+        replay.push(player, action.clone());
+        let report = Arc::make_mut(&mut env).process(player, action.take());
+        if report.endgame.is_ended() {
+            return replay;
+        }
+        if let Some(actions) = report.available_actions {
+            player = actions.player;
+            let view = BotView::new(&env, player);
+            action = bots[player as usize].compute_best_action(&view, &actions);
+        }
+    }
+}
+
+/// Returns the state right before the first card is cast in the recorded game.
+fn mid_game(replay: &Replay) -> Environment {
+    let index = replay
+        .steps
+        .iter()
+        .position(|step| matches!(step.action, Some(Action::CastCard { .. })))
+        .expect("no card is cast in the game");
+    let mut replay = replay.clone();
+    replay.steps.truncate(index);
+    replay.play(CATALOG.clone())
+}
+
+fn bench_engine(c: &mut Criterion) {
+    let replay = play_game(profile());
+    let env = mid_game(&replay);
+
+    c.bench_function("environment clone", |b| b.iter(|| black_box(&env).clone()));
+
+    c.bench_function("environment process", |b| {
+        b.iter(|| black_box(&replay).play(CATALOG.clone()))
+    });
+
+    let actions = env.last_available_actions().unwrap().clone();
+    let view = BotView::new(&env, actions.player);
+    c.bench_function("default bot compute", |b| {
+        b.iter(|| {
+            let mut bot = DefaultBot::builder()
+                .with_rng(SmallRng::seed_from_u64(0))
+                .build();
+            bot.compute(&view, black_box(&actions))
+        })
+    });
+
+    c.bench_function("mcts bot compute", |b| {
+        b.iter(|| {
+            let mut bot = MctsBot::builder()
+                .with_config(MctsConfig {
+                    iterations: 50,
+                    determinizations: 2,
+                    ..Default::default()
+                })
+                .with_rng(SmallRng::seed_from_u64(0))
+                .build();
+            bot.compute(&view, black_box(&actions))
+        })
+    });
+
+    c.bench_function("full game", |b| b.iter(|| play_game(black_box(profile()))));
+}
+
+criterion_group!(benches, bench_engine);
+criterion_main!(benches);
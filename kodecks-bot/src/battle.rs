@@ -1,14 +1,16 @@
 use super::BotContext;
 use crate::{
     score::{get_score, ComputedScore},
-    Bot, SimpleBot,
+    SimpleBot,
 };
 use kodecks::{action::Action, env::Environment, id::ObjectId, phase::Phase};
-use std::sync::Arc;
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
+/// Enumerations are cut off at this size, dropping the combinations with the most cards.
+const MAX_COMBINATIONS: usize = 256;
+
 pub fn find_attacker_combination(
     ctx: BotContext,
     attackers: &[ObjectId],
@@ -17,37 +19,48 @@ pub fn find_attacker_combination(
         return vec![];
     }
 
-    let base_score = evaluate_battle(&ctx.env, ctx.player, None);
+    let base_score = evaluate_battle(&ctx, None);
+    let evaluate = |attackers: Vec<ObjectId>| {
+        (
+            attackers.clone(),
+            ComputedScore {
+                base: base_score,
+                action: evaluate_battle(&ctx, Some(Action::Attack { attackers })),
+            },
+        )
+    };
+
+    // The all-out attack comes first, so it is evaluated before the budget runs out.
+    let mut combinations = possible_attacker_combinations(attackers);
+    let rest = combinations.split_off(1);
+    let mut scored = combinations
+        .into_iter()
+        .filter(|_| ctx.budget.consume())
+        .map(evaluate)
+        .collect::<Vec<_>>();
 
-    let combinations = possible_attacker_combinations(attackers);
     #[cfg(feature = "rayon")]
-    let scored_combinations = combinations.into_par_iter();
+    let scored_combinations = rest.into_par_iter();
     #[cfg(not(feature = "rayon"))]
-    let scored_combinations = combinations.into_iter();
+    let scored_combinations = rest.into_iter();
 
-    scored_combinations
-        .map(|attackers| {
-            (
-                attackers.clone(),
-                ComputedScore {
-                    base: base_score,
-                    action: evaluate_battle(
-                        &ctx.env,
-                        ctx.player,
-                        Some(Action::Attack { attackers }),
-                    ),
-                },
-            )
-        })
-        .collect()
+    scored.extend(
+        scored_combinations
+            .filter(|_| ctx.budget.consume())
+            .map(evaluate)
+            .collect::<Vec<_>>(),
+    );
+    scored
 }
 
 fn possible_attacker_combinations(attackers: &[ObjectId]) -> Vec<Vec<ObjectId>> {
-    let mut combinations = vec![];
-    for k in 0..=attackers.len() {
+    // The all-out attack is always considered.
+    let mut combinations = vec![attackers.to_vec()];
+    for k in 0..attackers.len() {
         let mut result = Vec::new();
         let mut current = Vec::new();
-        backtrack(attackers, k, 0, &mut current, &mut result);
+        let limit = MAX_COMBINATIONS - combinations.len();
+        backtrack(attackers, k, 0, limit, &mut current, &mut result);
         combinations.extend(result);
     }
     combinations
@@ -57,9 +70,13 @@ fn backtrack<T: Clone>(
     arr: &[T],
     k: usize,
     start: usize,
+    limit: usize,
     current: &mut Vec<T>,
     result: &mut Vec<Vec<T>>,
 ) {
+    if result.len() >= limit {
+        return;
+    }
     if current.len() == k {
         result.push(current.clone());
         return;
@@ -67,7 +84,7 @@ fn backtrack<T: Clone>(
 
     for i in start..arr.len() {
         current.push(arr[i].clone());
-        backtrack(arr, k, i + 1, current, result);
+        backtrack(arr, k, i + 1, limit, current, result);
         current.pop();
     }
 }
@@ -81,7 +98,7 @@ pub fn find_blocker_combination(
         return vec![];
     }
 
-    let base_score = evaluate_battle(&ctx.env, ctx.player, None);
+    let base_score = evaluate_battle(&ctx, None);
 
     let combinations = possible_battle_combinations(attackers, blockers);
 
@@ -90,16 +107,17 @@ pub fn find_blocker_combination(
     #[cfg(not(feature = "rayon"))]
     let comb = combinations.into_iter();
 
-    comb.map(|pairs| {
-        (
-            pairs.clone(),
-            ComputedScore {
-                base: base_score,
-                action: evaluate_battle(&ctx.env, ctx.player, Some(Action::Block { pairs })),
-            },
-        )
-    })
-    .collect()
+    comb.filter(|_| ctx.budget.consume())
+        .map(|pairs| {
+            (
+                pairs.clone(),
+                ComputedScore {
+                    base: base_score,
+                    action: evaluate_battle(&ctx, Some(Action::Block { pairs })),
+                },
+            )
+        })
+        .collect()
 }
 
 fn possible_battle_combinations(
@@ -117,7 +135,8 @@ fn possible_battle_combinations(
     for k in 0..=pairs.len() {
         let mut result = Vec::new();
         let mut current = Vec::new();
-        backtrack_pair(&pairs, k, 0, &mut current, &mut result);
+        let limit = MAX_COMBINATIONS - combinations.len();
+        backtrack_pair(&pairs, k, 0, limit, &mut current, &mut result);
         combinations.extend(result);
     }
 
@@ -128,9 +147,13 @@ fn backtrack_pair(
     arr: &[(ObjectId, ObjectId)],
     k: usize,
     start: usize,
+    limit: usize,
     current: &mut Vec<(ObjectId, ObjectId)>,
     result: &mut Vec<Vec<(ObjectId, ObjectId)>>,
 ) {
+    if result.len() >= limit {
+        return;
+    }
     if current.len() == k {
         result.push(current.clone());
         return;
@@ -140,26 +163,28 @@ fn backtrack_pair(
         let (a, b) = arr[i];
         if current.iter().all(|&(x, y)| x != a && y != b) {
             current.push(arr[i]);
-            backtrack_pair(arr, k, i + 1, current, result);
+            backtrack_pair(arr, k, i + 1, limit, current, result);
             current.pop();
         }
     }
 }
 
-fn evaluate_battle(env: &Environment, player: u8, action: Option<Action>) -> i32 {
+fn evaluate_battle(ctx: &BotContext, action: Option<Action>) -> i32 {
+    let env: &Environment = &ctx.env;
+    let player = ctx.player;
     let mut next_action = action;
     let mut current_player = player;
 
     if next_action.is_none() {
         if let Some(actions) = env.last_available_actions() {
-            next_action = actions.actions.default_action(env);
+            next_action = actions.actions.default_action(&env.local(actions.player));
             current_player = actions.player;
         }
     }
 
     let initial_turn = env.state.turn;
     let mut env = env.clone();
-    while !(matches!(env.state.phase, Phase::End) && env.state.turn > initial_turn) {
+    while !(matches!(env.state.phase, Phase::End) && env.state.turn >= initial_turn + ctx.depth) {
         let report = env.process(current_player, next_action.take());
         if report.endgame.is_ended() {
             break;
@@ -167,13 +192,79 @@ fn evaluate_battle(env: &Environment, player: u8, action: Option<Action>) -> i32
         if let Some(available_actions) = &report.available_actions {
             current_player = available_actions.player;
             if current_player == player && !matches!(env.state.phase, Phase::Block) {
-                next_action = available_actions.actions.default_action(&env);
+                next_action = available_actions
+                    .actions
+                    .default_action(&env.local(current_player));
             } else {
-                next_action =
-                    SimpleBot.compute_best_action(Arc::new(env.clone()), available_actions);
+                next_action = SimpleBot::best_action(&env, available_actions);
             }
         }
     }
 
-    get_score(&env, player)
+    get_score(&env, player, &ctx.weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ComputeBudget;
+    use kodecks::field::FieldState;
+    use kodecks_catalog::CATALOG;
+    use rand::{rngs::SmallRng, SeedableRng};
+    use std::sync::Arc;
+
+    fn ids(range: std::ops::RangeInclusive<u32>) -> Vec<ObjectId> {
+        range.map(|id| ObjectId::try_from(id).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_attacker_combinations_are_capped() {
+        for n in [1, 3, 8, 12, 20] {
+            let attackers = ids(1..=n);
+            let combinations = possible_attacker_combinations(&attackers);
+            assert!(combinations.len() <= MAX_COMBINATIONS);
+            assert_eq!(combinations[0], attackers);
+        }
+    }
+
+    #[test]
+    fn test_max_evaluations() {
+        let mut env = Environment::builder(CATALOG.clone())
+            .with_player(0, |player| {
+                (1..=5)
+                    .fold(player, |player, id| {
+                        player.with_field(id, "halo", FieldState::Active)
+                    })
+                    .with_deck(6, "wind")
+            })
+            .with_player(1, |player| player.with_deck(7, "wind"))
+            .build();
+        env.advance(0, None);
+
+        for n in [0, 1, 3] {
+            let ctx = BotContext {
+                rng: SmallRng::seed_from_u64(0),
+                flags: Default::default(),
+                player: 0,
+                env: Arc::new(env.clone()),
+                depth: 1,
+                weights: Default::default(),
+                budget: ComputeBudget {
+                    max_evaluations: Some(n),
+                    ..Default::default()
+                }
+                .start(),
+            };
+            let attackers = ids(1..=5);
+            assert!(n < possible_attacker_combinations(&attackers).len());
+            let scored = find_attacker_combination(ctx, &attackers);
+            assert_eq!(scored.len(), n);
+            // The all-out attack is evaluated even if the budget covers a single combination.
+            if n > 0 {
+                assert!(scored
+                    .iter()
+                    .any(|(combination, _)| *combination == attackers));
+            }
+        }
+    }
 }
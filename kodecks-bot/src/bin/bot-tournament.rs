@@ -0,0 +1,460 @@
+//! Plays round-robin matches between decks and bots and reports their strength.
+//!
+//! Every pair of participants plays the given number of seeded games,
+//! swapping the first player each game.
+//!
+//! Run with `cargo run -p kodecks-bot --features cli --bin bot-tournament`.
+
+use bpaf::Bpaf;
+use kodecks::{
+    deck::DeckList,
+    env::Environment,
+    player::PlayerConfig,
+    profile::{BotDifficulty, BotPersonality, DebugConfig, GameProfile},
+};
+use kodecks_bot::{selfplay::play_game, Bot, DefaultBot, MctsBot, SimpleBot};
+use kodecks_catalog::{decks, CATALOG};
+use rand::{rngs::SmallRng, SeedableRng};
+use serde::Serialize;
+use std::{fmt::Write as _, fs, path::PathBuf};
+
+/// The z-score of the 95% confidence intervals.
+const Z: f64 = 1.96;
+
+fn main() {
+    let opts = options().run();
+
+    let decks = if opts.decks.is_empty() {
+        vec![
+            decks::starter_deck(&CATALOG),
+            decks::red_deck(&CATALOG),
+            decks::blue_deck(&CATALOG),
+        ]
+    } else {
+        opts.decks
+            .iter()
+            .map(|path| {
+                let text = fs::read_to_string(path)
+                    .unwrap_or_else(|err| panic!("failed to read {}: {err}", path.display()));
+                let mut deck = DeckList::parse(&text, &CATALOG).unwrap();
+                if deck.name.is_empty() {
+                    deck.name = path.file_stem().unwrap().to_string_lossy().to_string();
+                }
+                deck
+            })
+            .collect()
+    };
+    let bots = if opts.bots.is_empty() {
+        vec![BotSpec::default()]
+    } else {
+        opts.bots
+            .iter()
+            .map(|spec| BotSpec::parse(spec).unwrap_or_else(|| panic!("invalid bot: {spec}")))
+            .collect()
+    };
+    let participants = decks
+        .iter()
+        .flat_map(|deck| bots.iter().map(move |bot| (deck.clone(), *bot)))
+        .collect::<Vec<_>>();
+    assert!(
+        participants.len() >= 2,
+        "at least two participants are required"
+    );
+
+    let mut games = vec![];
+    let mut seed = opts.seed;
+    for a in 0..participants.len() {
+        for b in a + 1..participants.len() {
+            for game in 0..opts.games {
+                // Player 0 always goes first, so the seats are swapped instead.
+                let seats = if game % 2 == 0 { [a, b] } else { [b, a] };
+                let profile = GameProfile {
+                    debug: Some(DebugConfig {
+                        no_player_shuffle: true,
+                        ..Default::default()
+                    }),
+                    players: seats
+                        .iter()
+                        .map(|&i| PlayerConfig {
+                            deck: participants[i].0.clone(),
+                        })
+                        .collect(),
+                    rng_seed: Some(seed),
+                    ..Default::default()
+                };
+                let [mut first, mut second] = seats.map(|i| participants[i].1.build(seed));
+                let bots: &mut [&mut dyn Bot] = &mut [first.as_mut(), second.as_mut()];
+                let result = play_game(
+                    Environment::new(profile, CATALOG.clone()),
+                    bots,
+                    opts.max_turns,
+                );
+                games.push(GameRecord {
+                    seats,
+                    winner: result.winner.map(|player| seats[player as usize]),
+                    turns: result.turns,
+                });
+                seed += 1;
+            }
+            eprintln!(
+                "{} vs {}: {} games played",
+                name(&participants[a]),
+                name(&participants[b]),
+                opts.games
+            );
+        }
+    }
+
+    let report = Report::new(&participants, &games);
+    print!("{}", report.table());
+    if let Some(path) = &opts.csv {
+        fs::write(path, report.csv()).expect("failed to write CSV");
+    }
+    if let Some(path) = &opts.json {
+        fs::write(path, serde_json::to_string_pretty(&report).unwrap())
+            .expect("failed to write JSON");
+    }
+}
+
+fn name((deck, bot): &(DeckList, BotSpec)) -> String {
+    format!("{}/{}", deck.name, bot)
+}
+
+/// A bot given on the command line as `simple`, `mcts` or `default[:difficulty[:personality]]`.
+#[derive(Debug, Clone, Copy)]
+enum BotSpec {
+    Simple,
+    Mcts,
+    Default(BotDifficulty, BotPersonality),
+}
+
+impl Default for BotSpec {
+    fn default() -> Self {
+        Self::Default(BotDifficulty::default(), BotPersonality::default())
+    }
+}
+
+impl BotSpec {
+    fn parse(s: &str) -> Option<Self> {
+        let mut args = s.split(':');
+        match args.next()? {
+            "simple" => Some(Self::Simple),
+            "mcts" => Some(Self::Mcts),
+            "default" => {
+                let difficulty = match args.next() {
+                    None | Some("normal") => BotDifficulty::Normal,
+                    Some("easy") => BotDifficulty::Easy,
+                    Some("hard") => BotDifficulty::Hard,
+                    _ => return None,
+                };
+                let personality = match args.next() {
+                    None | Some("balanced") => BotPersonality::Balanced,
+                    Some("aggressive") => BotPersonality::Aggressive,
+                    Some("control") => BotPersonality::Control,
+                    _ => return None,
+                };
+                Some(Self::Default(difficulty, personality))
+            }
+            _ => None,
+        }
+    }
+
+    fn build(&self, seed: u64) -> Box<dyn Bot> {
+        let rng = SmallRng::seed_from_u64(seed);
+        match *self {
+            Self::Simple => Box::new(SimpleBot),
+            Self::Mcts => Box::new(MctsBot::builder().with_rng(rng).build()),
+            Self::Default(difficulty, personality) => Box::new(
+                DefaultBot::builder()
+                    .with_difficulty(difficulty)
+                    .with_personality(personality)
+                    .with_rng(rng)
+                    .build(),
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for BotSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Simple => write!(f, "simple"),
+            Self::Mcts => write!(f, "mcts"),
+            Self::Default(difficulty, personality) => write!(
+                f,
+                "default:{}:{}",
+                format!("{difficulty:?}").to_lowercase(),
+                format!("{personality:?}").to_lowercase()
+            ),
+        }
+    }
+}
+
+struct GameRecord {
+    /// The participants in the seats, the first of which goes first.
+    seats: [usize; 2],
+    winner: Option<usize>,
+    turns: u16,
+}
+
+#[derive(Serialize)]
+struct Report {
+    games: usize,
+    average_turns: f64,
+    /// The score of the player going first, counting draws as half a win.
+    first_player_score: Interval,
+    participants: Vec<ParticipantStats>,
+    pairings: Vec<PairingStats>,
+}
+
+#[derive(Serialize)]
+struct ParticipantStats {
+    name: String,
+    games: usize,
+    wins: usize,
+    losses: usize,
+    draws: usize,
+    /// The score counting draws as half a win.
+    score: Interval,
+    elo: f64,
+}
+
+#[derive(Serialize)]
+struct PairingStats {
+    first: String,
+    second: String,
+    games: usize,
+    first_wins: usize,
+    second_wins: usize,
+    draws: usize,
+    average_turns: f64,
+}
+
+/// A rate with its Wilson score interval.
+#[derive(Serialize)]
+struct Interval {
+    rate: f64,
+    low: f64,
+    high: f64,
+}
+
+impl Interval {
+    fn new(score: f64, n: usize) -> Self {
+        if n == 0 {
+            return Self {
+                rate: 0.0,
+                low: 0.0,
+                high: 1.0,
+            };
+        }
+        let n = n as f64;
+        let p = score / n;
+        let denominator = 1.0 + Z * Z / n;
+        let center = (p + Z * Z / (2.0 * n)) / denominator;
+        let margin = Z * (p * (1.0 - p) / n + Z * Z / (4.0 * n * n)).sqrt() / denominator;
+        Self {
+            rate: p,
+            low: (center - margin).max(0.0),
+            high: (center + margin).min(1.0),
+        }
+    }
+}
+
+impl Report {
+    fn new(participants: &[(DeckList, BotSpec)], games: &[GameRecord]) -> Self {
+        let total_turns = games.iter().map(|game| game.turns as f64).sum::<f64>();
+        let first_score = games
+            .iter()
+            .map(|game| match game.winner {
+                Some(winner) if winner == game.seats[0] => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            })
+            .sum::<f64>();
+        let elo = elo_ratings(participants.len(), games);
+
+        let participant_stats = participants
+            .iter()
+            .enumerate()
+            .map(|(i, participant)| {
+                let played = games
+                    .iter()
+                    .filter(|game| game.seats.contains(&i))
+                    .collect::<Vec<_>>();
+                let wins = played.iter().filter(|game| game.winner == Some(i)).count();
+                let draws = played.iter().filter(|game| game.winner.is_none()).count();
+                ParticipantStats {
+                    name: name(participant),
+                    games: played.len(),
+                    wins,
+                    losses: played.len() - wins - draws,
+                    draws,
+                    score: Interval::new(wins as f64 + draws as f64 / 2.0, played.len()),
+                    elo: elo[i],
+                }
+            })
+            .collect();
+
+        let mut pairings = vec![];
+        for a in 0..participants.len() {
+            for b in a + 1..participants.len() {
+                let played = games
+                    .iter()
+                    .filter(|game| game.seats.contains(&a) && game.seats.contains(&b))
+                    .collect::<Vec<_>>();
+                pairings.push(PairingStats {
+                    first: name(&participants[a]),
+                    second: name(&participants[b]),
+                    games: played.len(),
+                    first_wins: played.iter().filter(|game| game.winner == Some(a)).count(),
+                    second_wins: played.iter().filter(|game| game.winner == Some(b)).count(),
+                    draws: played.iter().filter(|game| game.winner.is_none()).count(),
+                    average_turns: played.iter().map(|game| game.turns as f64).sum::<f64>()
+                        / played.len().max(1) as f64,
+                });
+            }
+        }
+
+        Self {
+            games: games.len(),
+            average_turns: total_turns / games.len().max(1) as f64,
+            first_player_score: Interval::new(first_score, games.len()),
+            participants: participant_stats,
+            pairings,
+        }
+    }
+
+    fn table(&self) -> String {
+        let mut out = String::new();
+        let width = self
+            .participants
+            .iter()
+            .map(|p| p.name.len())
+            .max()
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "{:width$}  {:>5}  {:>4}  {:>4}  {:>4}  {:>6}  {:>15}  {:>6}",
+            "participant", "games", "win", "loss", "draw", "score", "95% CI", "elo"
+        )
+        .unwrap();
+        for p in &self.participants {
+            writeln!(
+                out,
+                "{:width$}  {:>5}  {:>4}  {:>4}  {:>4}  {:>6.3}  {:>6.3} - {:>6.3}  {:>6.0}",
+                p.name,
+                p.games,
+                p.wins,
+                p.losses,
+                p.draws,
+                p.score.rate,
+                p.score.low,
+                p.score.high,
+                p.elo
+            )
+            .unwrap();
+        }
+        writeln!(out).unwrap();
+        writeln!(out, "games: {}", self.games).unwrap();
+        writeln!(out, "average turns: {:.1}", self.average_turns).unwrap();
+        writeln!(
+            out,
+            "first player score: {:.3} ({:.3} - {:.3})",
+            self.first_player_score.rate, self.first_player_score.low, self.first_player_score.high
+        )
+        .unwrap();
+        out
+    }
+
+    fn csv(&self) -> String {
+        let mut out =
+            String::from("participant,games,wins,losses,draws,score,score_low,score_high,elo\n");
+        for p in &self.participants {
+            writeln!(
+                out,
+                "\"{}\",{},{},{},{},{:.4},{:.4},{:.4},{:.1}",
+                p.name.replace('"', "\"\""),
+                p.games,
+                p.wins,
+                p.losses,
+                p.draws,
+                p.score.rate,
+                p.score.low,
+                p.score.high,
+                p.elo
+            )
+            .unwrap();
+        }
+        out
+    }
+}
+
+/// Fits Elo ratings to all games at once, so the order of the games does not matter.
+///
+/// Every participant also draws a virtual game against the average,
+/// which keeps the ratings finite when a participant wins every game.
+/// Ratings are centered on 1500.
+fn elo_ratings(len: usize, games: &[GameRecord]) -> Vec<f64> {
+    let expected = |a: f64, b: f64| 1.0 / (1.0 + 10f64.powf((b - a) / 400.0));
+    let mut ratings = vec![0.0; len];
+    for _ in 0..200 {
+        let mut delta = ratings
+            .iter()
+            .map(|&rating| 0.5 - expected(rating, 0.0))
+            .collect::<Vec<_>>();
+        let mut counts = vec![1usize; len];
+        for game in games {
+            let [a, b] = game.seats;
+            let actual = match game.winner {
+                Some(winner) if winner == a => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            };
+            let residual = actual - expected(ratings[a], ratings[b]);
+            delta[a] += residual;
+            delta[b] -= residual;
+            counts[a] += 1;
+            counts[b] += 1;
+        }
+        for i in 0..len {
+            ratings[i] += 400.0 * delta[i] / counts[i] as f64;
+        }
+    }
+    let mean = ratings.iter().sum::<f64>() / len as f64;
+    ratings
+        .iter()
+        .map(|rating| rating - mean + 1500.0)
+        .collect()
+}
+
+#[derive(Debug, Clone, Bpaf)]
+#[bpaf(options)]
+pub struct Options {
+    #[bpaf(long("deck"), argument("PATH"), many)]
+    /// Deck list file, the catalog decks are used if none is given
+    decks: Vec<PathBuf>,
+
+    #[bpaf(long("bot"), argument("BOT"), many)]
+    /// Bot as simple, mcts or default[:easy|normal|hard[:balanced|aggressive|control]]
+    bots: Vec<String>,
+
+    #[bpaf(long, fallback(20))]
+    /// Number of games for each pairing
+    games: u64,
+
+    #[bpaf(long, fallback(0))]
+    /// Seed of the first game
+    seed: u64,
+
+    #[bpaf(long, fallback(100))]
+    /// Turn limit of a game, after which it counts as a draw
+    max_turns: u16,
+
+    #[bpaf(long, argument("PATH"))]
+    /// File to write the participant results to as CSV
+    csv: Option<PathBuf>,
+
+    #[bpaf(long, argument("PATH"))]
+    /// File to write the full results to as JSON
+    json: Option<PathBuf>,
+}
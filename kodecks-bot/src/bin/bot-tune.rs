@@ -0,0 +1,169 @@
+//! Tunes the evaluation weights of the default bot by self-play.
+//!
+//! Each iteration plays a bot with randomly perturbed weights against one
+//! perturbed in the opposite direction and moves the weights toward the winner (SPSA).
+//!
+//! Run with `cargo run -p kodecks-bot --features cli --bin bot-tune`.
+
+use bpaf::Bpaf;
+use kodecks::{env::Environment, player::PlayerConfig, profile::GameProfile};
+use kodecks_bot::{
+    selfplay::{play_game, GameResult},
+    Bot, DefaultBot, EvalWeights,
+};
+use kodecks_catalog::{decks, CATALOG};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use std::{fs, path::PathBuf};
+
+fn main() {
+    let opts = options().run();
+    let initial = match &opts.weights {
+        Some(path) => {
+            let json = fs::read_to_string(path).expect("failed to read weights");
+            serde_json::from_str(&json).expect("failed to parse weights")
+        }
+        None => EvalWeights::default(),
+    };
+
+    let mut rng = SmallRng::seed_from_u64(opts.seed);
+    let mut theta = initial.to_array();
+    let scale = theta.map(|value| value.abs().max(0.1));
+    let stability = opts.iterations as f32 / 10.0;
+
+    for k in 0..opts.iterations {
+        let c = opts.perturbation / (k as f32 + 1.0).powf(0.101);
+        let a = opts.learning_rate / (k as f32 + 1.0 + stability).powf(0.602);
+        let delta: [f32; EvalWeights::LEN] =
+            std::array::from_fn(|_| if rng.gen_bool(0.5) { 1.0 } else { -1.0 });
+        let plus = std::array::from_fn(|i| (theta[i] + c * scale[i] * delta[i]).max(0.0));
+        let minus = std::array::from_fn(|i| (theta[i] - c * scale[i] * delta[i]).max(0.0));
+
+        let seed = opts.seed + k as u64 * match_games(&opts);
+        let result = match_score(
+            &EvalWeights::from_array(plus),
+            &EvalWeights::from_array(minus),
+            seed,
+            &opts,
+        );
+        for i in 0..EvalWeights::LEN {
+            theta[i] = (theta[i] + a * scale[i] * result * delta[i] / (2.0 * c)).max(0.0);
+        }
+        println!("iteration {}: {:+.3}", k + 1, result);
+    }
+
+    let tuned = EvalWeights::from_array(theta);
+    let seed = opts.seed + opts.iterations as u64 * match_games(&opts);
+    let result = match_score(&tuned, &initial, seed, &opts);
+    println!("tuned weights against initial weights: {:+.3}", result);
+    println!("{tuned:#?}");
+    if result <= 0.0 {
+        println!(
+            "no improvement found, {} is not written",
+            opts.out.display()
+        );
+        return;
+    }
+
+    let json = serde_json::to_string_pretty(&tuned).unwrap();
+    fs::write(&opts.out, json).expect("failed to write weights");
+    println!("weights written to {}", opts.out.display());
+}
+
+/// The pairs of the starter, red and blue decks, including mirror matches.
+const DECK_PAIRS: u64 = 9;
+
+/// Returns the number of games in a match.
+fn match_games(opts: &Options) -> u64 {
+    DECK_PAIRS * opts.games
+}
+
+/// Returns the difference between the win rates of the two weights, from -1 to 1.
+///
+/// Every pair of the catalog decks is played the given number of times,
+/// and the weights swap sides every game.
+fn match_score(a: &EvalWeights, b: &EvalWeights, seed: u64, opts: &Options) -> f32 {
+    let deck_list = [
+        decks::starter_deck(&CATALOG),
+        decks::red_deck(&CATALOG),
+        decks::blue_deck(&CATALOG),
+    ];
+    let mut score = 0;
+    for game in 0..match_games(opts) {
+        let seed = seed + game;
+        let side = (game % 2) as u8;
+        let pair = (game / opts.games) as usize;
+        let decks = [
+            deck_list[pair % deck_list.len()].clone(),
+            deck_list[pair / deck_list.len()].clone(),
+        ];
+        let profile = GameProfile {
+            players: decks
+                .into_iter()
+                .map(|deck| PlayerConfig { deck })
+                .collect(),
+            rng_seed: Some(seed),
+            ..Default::default()
+        };
+
+        let mut weights = [a, b];
+        if side == 1 {
+            weights.reverse();
+        }
+        let mut bots = weights.map(|weights| {
+            DefaultBot::builder()
+                .with_weights(*weights)
+                .with_rng(SmallRng::seed_from_u64(seed))
+                .build()
+        });
+        let [first, second] = &mut bots;
+        let bots: &mut [&mut dyn Bot] = &mut [first, second];
+        let env = Environment::new(profile, CATALOG.clone());
+        match play_game(env, bots, opts.max_turns) {
+            GameResult {
+                winner: Some(winner),
+                ..
+            } if winner == side => score += 1,
+            GameResult {
+                winner: Some(_), ..
+            } => score -= 1,
+            _ => {}
+        }
+    }
+    score as f32 / match_games(opts) as f32
+}
+
+#[derive(Debug, Clone, Bpaf)]
+#[bpaf(options)]
+pub struct Options {
+    #[bpaf(long, fallback(50))]
+    /// Number of SPSA iterations
+    iterations: usize,
+
+    #[bpaf(long, fallback(2))]
+    /// Number of games played for each pair of decks in each iteration
+    games: u64,
+
+    #[bpaf(long, fallback(0))]
+    /// Seed of the first game
+    seed: u64,
+
+    #[bpaf(long, fallback(100))]
+    /// Turn limit of a game
+    max_turns: u16,
+
+    #[bpaf(long, fallback(0.2))]
+    /// Size of the weight perturbations, relative to each weight
+    perturbation: f32,
+
+    #[bpaf(long, fallback(0.1))]
+    /// Step size of the weight updates
+    learning_rate: f32,
+
+    #[bpaf(long)]
+    /// JSON file of the initial weights
+    weights: Option<PathBuf>,
+
+    #[bpaf(long, fallback(PathBuf::from("eval-weights.json")))]
+    /// File to write the tuned weights to
+    out: PathBuf,
+}
@@ -0,0 +1,84 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use web_time::Instant;
+
+/// Limits the time and the number of evaluations spent on a decision.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComputeBudget {
+    pub time_limit: Option<Duration>,
+    pub max_evaluations: Option<usize>,
+}
+
+impl ComputeBudget {
+    pub const UNLIMITED: Self = Self {
+        time_limit: None,
+        max_evaluations: None,
+    };
+
+    pub fn with_time_limit(time_limit: Duration) -> Self {
+        Self {
+            time_limit: Some(time_limit),
+            ..Self::UNLIMITED
+        }
+    }
+
+    /// Divides the budget evenly between the given number of decisions.
+    pub fn split(&self, parts: usize) -> Self {
+        let parts = parts.max(1);
+        Self {
+            time_limit: self.time_limit.map(|limit| limit / parts as u32),
+            max_evaluations: self.max_evaluations.map(|n| n / parts),
+        }
+    }
+
+    /// Starts counting down the budget from now.
+    pub fn start(&self) -> BudgetTracker {
+        BudgetTracker {
+            deadline: self.time_limit.map(|limit| Instant::now() + limit),
+            remaining: self.max_evaluations.map(|n| Arc::new(AtomicUsize::new(n))),
+        }
+    }
+}
+
+/// The remaining budget of a decision, shared between the threads evaluating it.
+#[derive(Debug, Clone, Default)]
+pub struct BudgetTracker {
+    deadline: Option<Instant>,
+    remaining: Option<Arc<AtomicUsize>>,
+}
+
+impl BudgetTracker {
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+            || self
+                .remaining
+                .as_ref()
+                .is_some_and(|remaining| remaining.load(Ordering::Relaxed) == 0)
+    }
+
+    /// Takes one evaluation from the budget, returning `false` if nothing is left.
+    pub fn consume(&self) -> bool {
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return false;
+        }
+        match &self.remaining {
+            Some(remaining) => remaining
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                .is_ok(),
+            None => true,
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use super::BotContext;
 use crate::{
     score::{get_score, ComputedScore},
-    Bot, SimpleBot,
+    SimpleBot,
 };
 use kodecks::{action::Action, id::ObjectId, phase::Phase};
 use std::sync::Arc;
@@ -21,6 +21,7 @@ pub fn find_cast_combination(
     let scored_combinations = cards.into_iter();
 
     scored_combinations
+        .filter(|_| ctx.budget.consume())
         .map(|card| {
             (
                 card,
@@ -41,13 +42,13 @@ fn evaluate_cast(mut ctx: BotContext, action: Option<Action>) -> i32 {
 
     if next_action.is_none() {
         if let Some(actions) = env.last_available_actions() {
-            next_action = actions.actions.default_action(env);
+            next_action = actions.actions.default_action(&env.local(actions.player));
             current_player = actions.player;
         }
     }
 
     let initial_turn = env.state.turn;
-    while !(matches!(env.state.phase, Phase::End) && env.state.turn > initial_turn) {
+    while !(matches!(env.state.phase, Phase::End) && env.state.turn >= initial_turn + ctx.depth) {
         let report = env.process(current_player, next_action.take());
         if report.endgame.is_ended() {
             break;
@@ -55,13 +56,14 @@ fn evaluate_cast(mut ctx: BotContext, action: Option<Action>) -> i32 {
         if let Some(available_actions) = &report.available_actions {
             current_player = available_actions.player;
             if current_player == ctx.player {
-                next_action = available_actions.actions.default_action(env);
+                next_action = available_actions
+                    .actions
+                    .default_action(&env.local(current_player));
             } else {
-                next_action =
-                    SimpleBot.compute_best_action(Arc::new(env.clone()), available_actions);
+                next_action = SimpleBot::best_action(env, available_actions);
             }
         }
     }
 
-    get_score(env, ctx.player)
+    get_score(env, ctx.player, &ctx.weights)
 }
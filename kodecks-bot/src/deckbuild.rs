@@ -0,0 +1,445 @@
+use crate::{
+    selfplay::{play_game, GameResult},
+    Bot, DefaultBot,
+};
+use kodecks::{
+    archetype::CardArchetype,
+    card::CardEntry,
+    catalog::Catalog,
+    color::Color,
+    deck::{DeckItem, DeckList},
+    env::Environment,
+    player::PlayerConfig,
+    pool::CardPool,
+    profile::GameProfile,
+    regulation::Regulation,
+    score::Score,
+};
+use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+use std::{collections::HashMap, sync::Arc};
+
+/// How much a card is favored for each missing card at its cost.
+const CURVE_WEIGHT: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy)]
+pub struct DeckBuildConfig {
+    /// The colors of the deck. Chosen randomly from the pool if empty.
+    pub colors: Color,
+    /// The number of colors chosen when `colors` is empty.
+    pub max_colors: usize,
+    /// The relative share of cards costing 0, 1, 2, 3, 4, and 5 or more.
+    pub cost_curve: [f32; 6],
+    /// The bonus of a card for each card in the deck sharing its creature type or an ability.
+    pub synergy: f32,
+    /// The size of the random noise added to the card scores.
+    pub noise: f32,
+}
+
+impl Default for DeckBuildConfig {
+    fn default() -> Self {
+        Self {
+            colors: Color::empty(),
+            max_colors: 1,
+            cost_curve: [3.0, 4.0, 5.0, 4.0, 2.0, 2.0],
+            synergy: 0.05,
+            noise: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RefineConfig {
+    /// The number of card swaps tried.
+    pub iterations: usize,
+    /// The number of games played to evaluate a deck.
+    pub games: u64,
+    /// Turn limit of a game.
+    pub max_turns: u16,
+}
+
+impl Default for RefineConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 20,
+            games: 6,
+            max_turns: 100,
+        }
+    }
+}
+
+/// Builds decks from the cards allowed by a card pool and a regulation.
+///
+/// Cards are added one at a time, preferring efficient cards,
+/// costs below the target curve and cards working with the ones already chosen.
+pub struct DeckBuilder<'a> {
+    catalog: &'a Arc<Catalog>,
+    pool: &'a CardPool,
+    regulation: &'a Regulation,
+    config: DeckBuildConfig,
+    rng: SmallRng,
+}
+
+impl<'a> DeckBuilder<'a> {
+    pub fn new(catalog: &'a Arc<Catalog>, pool: &'a CardPool, regulation: &'a Regulation) -> Self {
+        Self {
+            catalog,
+            pool,
+            regulation,
+            config: Default::default(),
+            rng: SmallRng::from_entropy(),
+        }
+    }
+
+    pub fn with_config(mut self, config: DeckBuildConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn with_rng(mut self, rng: SmallRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// Builds a new deck, or returns `None` if the pool does not have enough cards of the colors.
+    pub fn build(&mut self) -> Option<DeckList> {
+        let size = self.deck_size();
+        let candidates = self.candidates();
+        let colors = if self.config.colors.is_empty() {
+            self.choose_colors(&candidates, size)
+        } else {
+            self.config.colors
+        };
+        let mut remaining = candidates
+            .into_iter()
+            .filter(|(card, _)| colors.contains(card.attribute.color))
+            .collect::<Vec<_>>();
+
+        let total = self.config.cost_curve.iter().sum::<f32>().max(f32::EPSILON);
+        let targets = self
+            .config
+            .cost_curve
+            .map(|share| share / total * size as f32);
+        let mut counts = [0.0; 6];
+        let mut deck: Vec<Arc<CardArchetype>> = Vec::with_capacity(size);
+        while deck.len() < size {
+            let (index, _) = remaining
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, copies))| *copies > 0)
+                .map(|(i, (card, _))| {
+                    let curve = targets[cost_bucket(card)] - counts[cost_bucket(card)];
+                    let score = card_value(card)
+                        + self.config.synergy * synergy(card, &deck)
+                        + CURVE_WEIGHT * curve
+                        + self.config.noise * self.rng.gen::<f32>();
+                    (i, score)
+                })
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+            let (card, copies) = &mut remaining[index];
+            *copies -= 1;
+            counts[cost_bucket(card)] += 1.0;
+            deck.push(card.clone());
+        }
+
+        deck.sort();
+        Some(deck_list(format!("{colors} deck"), &deck))
+    }
+
+    /// Improves the deck by swapping cards and keeping the swaps that win more games.
+    ///
+    /// The deck is played by [`DefaultBot`] against each of the opponent decks in turn.
+    /// Every iteration plays full games without a compute budget,
+    /// so this is meant for offline tools and must not be called from UI paths.
+    pub fn refine(
+        &mut self,
+        deck: DeckList,
+        opponents: &[DeckList],
+        config: RefineConfig,
+    ) -> DeckList {
+        if opponents.is_empty() {
+            return deck;
+        }
+        let colors = deck.cards.iter().fold(Color::empty(), |colors, item| {
+            colors | self.catalog[item.card.archetype_id].attribute.color
+        });
+        let candidates = self
+            .candidates()
+            .into_iter()
+            .filter(|(card, _)| colors.contains(card.attribute.color))
+            .collect::<Vec<_>>();
+
+        let seed = self.rng.gen();
+        let mut best = deck;
+        let mut best_score = self.evaluate(&best, opponents, seed, &config);
+        for _ in 0..config.iterations {
+            let Some(next) = self.mutate(&best, &candidates) else {
+                break;
+            };
+            let score = self.evaluate(&next, opponents, seed, &config);
+            if score > best_score {
+                best = next;
+                best_score = score;
+            }
+        }
+        best
+    }
+
+    fn deck_size(&self) -> usize {
+        self.regulation
+            .min_deck_size
+            .max(1)
+            .min(self.regulation.max_deck_size) as usize
+    }
+
+    /// Returns the cards that can be added to a deck with their number of copies allowed.
+    fn candidates(&self) -> Vec<(Arc<CardArchetype>, u8)> {
+        self.catalog
+            .iter()
+            .filter(|card| !card.attribute.is_token)
+            .map(|card| {
+                let copies = self
+                    .pool
+                    .max_copies(card.id, self.catalog)
+                    .min(self.regulation.max_same_cards);
+                (card.clone(), copies)
+            })
+            .filter(|(_, copies)| *copies > 0)
+            .collect()
+    }
+
+    /// Chooses random colors, adding more than `max_colors` only if the deck cannot be filled.
+    fn choose_colors(&mut self, candidates: &[(Arc<CardArchetype>, u8)], size: usize) -> Color {
+        let mut order = Color::iter_all().collect::<Vec<_>>();
+        order.shuffle(&mut self.rng);
+        let copies = |colors: Color| {
+            candidates
+                .iter()
+                .filter(|(card, _)| colors.contains(card.attribute.color))
+                .map(|(_, copies)| *copies as usize)
+                .sum::<usize>()
+        };
+        let mut colors = Color::empty();
+        for color in order {
+            if colors.iter().count() >= self.config.max_colors.max(1) && copies(colors) >= size {
+                break;
+            }
+            if copies(color) > 0 {
+                colors |= color;
+            }
+        }
+        colors
+    }
+
+    /// Replaces a random card of the deck with a different card.
+    fn mutate(
+        &mut self,
+        deck: &DeckList,
+        candidates: &[(Arc<CardArchetype>, u8)],
+    ) -> Option<DeckList> {
+        let mut cards = deck
+            .cards
+            .iter()
+            .map(|item| self.catalog[item.card.archetype_id].clone())
+            .collect::<Vec<_>>();
+        let removed = cards.swap_remove(self.rng.gen_range(0..cards.len()));
+        let added = candidates
+            .iter()
+            .filter(|(card, copies)| {
+                card.id != removed.id
+                    && cards.iter().filter(|c| c.id == card.id).count() < *copies as usize
+            })
+            .collect::<Vec<_>>()
+            .choose(&mut self.rng)
+            .map(|(card, _)| card.clone())?;
+        cards.push(added);
+        cards.sort();
+        Some(DeckList {
+            name: deck.name.clone(),
+            ..deck_list(String::new(), &cards)
+        })
+    }
+
+    /// Returns the win rate of the deck minus its loss rate.
+    fn evaluate(
+        &self,
+        deck: &DeckList,
+        opponents: &[DeckList],
+        seed: u64,
+        config: &RefineConfig,
+    ) -> f32 {
+        let mut score = 0;
+        for game in 0..config.games {
+            let seed = seed.wrapping_add(game);
+            let side = (game % 2) as u8;
+            let opponent = &opponents[game as usize % opponents.len()];
+            let mut decks = [deck.clone(), opponent.clone()];
+            if side == 1 {
+                decks.reverse();
+            }
+            let profile = GameProfile {
+                regulation: self.regulation.clone(),
+                card_pool: self.pool.clone(),
+                players: decks
+                    .into_iter()
+                    .map(|deck| PlayerConfig { deck })
+                    .collect(),
+                rng_seed: Some(seed),
+                ..Default::default()
+            };
+            let mut bots = [0, 1].map(|_| {
+                DefaultBot::builder()
+                    .with_rng(SmallRng::seed_from_u64(seed))
+                    .build()
+            });
+            let [first, second] = &mut bots;
+            let bots: &mut [&mut dyn Bot] = &mut [first, second];
+            let env = Environment::new(profile, self.catalog.clone());
+            match play_game(env, bots, config.max_turns) {
+                GameResult {
+                    winner: Some(winner),
+                    ..
+                } if winner == side => score += 1,
+                GameResult {
+                    winner: Some(_), ..
+                } => score -= 1,
+                _ => {}
+            }
+        }
+        score as f32 / config.games.max(1) as f32
+    }
+}
+
+fn deck_list(name: String, cards: &[Arc<CardArchetype>]) -> DeckList {
+    DeckList {
+        id: nanoid::nanoid!(),
+        name,
+        cards: cards
+            .iter()
+            .map(|card| DeckItem {
+                card: CardEntry {
+                    archetype_id: card.id,
+                    style: 0,
+                },
+                base_id: None,
+            })
+            .collect(),
+    }
+}
+
+fn cost_bucket(card: &CardArchetype) -> usize {
+    (card.attribute.cost as usize).min(5)
+}
+
+/// Returns the stats of the card per shard spent on it.
+fn card_value(card: &CardArchetype) -> f32 {
+    let attribute = &card.attribute;
+    let stats = attribute.power.unwrap_or(0) as f32 / 100.0
+        + attribute.shields.unwrap_or(0) as f32
+        + attribute.abilities.iter().map(|a| a.score()).sum::<i32>() as f32
+        + attribute
+            .anon_abilities
+            .iter()
+            .map(|a| a.score())
+            .sum::<i32>() as f32;
+    stats / (attribute.cost as f32 + 1.0)
+}
+
+/// Counts the cards sharing the creature type or a keyword ability with the card.
+fn synergy(card: &CardArchetype, deck: &[Arc<CardArchetype>]) -> f32 {
+    let mut abilities = HashMap::new();
+    for other in deck {
+        for ability in &other.attribute.abilities {
+            *abilities.entry(*ability).or_insert(0) += 1;
+        }
+    }
+    let creature_type = card.attribute.creature_type;
+    let same_type = deck
+        .iter()
+        .filter(|other| creature_type.is_some() && other.attribute.creature_type == creature_type)
+        .count();
+    let same_ability = card
+        .attribute
+        .abilities
+        .iter()
+        .map(|ability| abilities.get(ability).copied().unwrap_or(0))
+        .sum::<usize>();
+    (same_type + same_ability) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::pool::{CardPoolEntry, PoolStatus};
+    use kodecks_catalog::CATALOG;
+
+    fn assert_legal_decks(pool: &CardPool) {
+        let regulation = Regulation::STANDARD;
+        for seed in 0..20 {
+            let deck = DeckBuilder::new(&CATALOG, pool, &regulation)
+                .with_rng(SmallRng::seed_from_u64(seed))
+                .build()
+                .unwrap();
+            assert!(regulation.verify(&deck, &CATALOG), "{:?}", deck);
+            assert!(pool.verify(&deck, &CATALOG), "{:?}", deck);
+        }
+    }
+
+    #[test]
+    fn test_build_legal_deck() {
+        assert_legal_decks(&CardPool::default());
+    }
+
+    #[test]
+    fn test_build_legal_deck_from_limited_pool() {
+        // A single copy of each card, so every deck needs several colors.
+        let singles = CATALOG
+            .iter()
+            .map(|card| (CardPoolEntry::Card(card.id), PoolStatus::Limited(1)))
+            .collect::<CardPool>();
+        assert_legal_decks(&singles);
+
+        // Some cards are banned or restricted, and the rest of the core set is legal.
+        let restricted = CATALOG
+            .iter()
+            .step_by(3)
+            .enumerate()
+            .map(|(i, card)| {
+                let status = if i % 2 == 0 {
+                    PoolStatus::Illegal
+                } else {
+                    PoolStatus::Limited(1)
+                };
+                (CardPoolEntry::Card(card.id), status)
+            })
+            .chain([(CardPoolEntry::CoreSet, PoolStatus::Legal)])
+            .collect::<CardPool>();
+        assert_legal_decks(&restricted);
+    }
+
+    #[test]
+    fn test_refine_legal_deck() {
+        let pool = CardPool::default();
+        let regulation = Regulation::STANDARD;
+        let mut builder = DeckBuilder::new(&CATALOG, &pool, &regulation)
+            .with_config(DeckBuildConfig {
+                colors: Color::RED,
+                ..Default::default()
+            })
+            .with_rng(SmallRng::seed_from_u64(0));
+        let deck = builder.build().unwrap();
+        let opponent = builder.build().unwrap();
+
+        let config = RefineConfig {
+            iterations: 3,
+            games: 2,
+            max_turns: 10,
+        };
+        let refined = builder.refine(deck, &[opponent], config);
+        assert!(regulation.verify(&refined, &CATALOG), "{:?}", refined);
+        assert!(pool.verify(&refined, &CATALOG), "{:?}", refined);
+        assert!(refined
+            .cards
+            .iter()
+            .all(|item| Color::RED.contains(CATALOG[item.card.archetype_id].attribute.color)));
+    }
+}
@@ -1,19 +1,32 @@
 use crate::{
-    battle, cast, score::ComputedScore, select::find_select_combination, Bot, BotContext, BotFlags,
+    battle, cast,
+    score::{ComputedScore, EvalWeights},
+    select::find_select_combination,
+    Bot, BotContext, BotDecisionTrace, BotFlags, BotView, CardTracker, ComputeBudget,
+    DifficultyPreset,
 };
 use kodecks::{
     action::{Action, PlayerAvailableActions},
     env::Environment,
+    log::GameLog,
+    profile::{BotDifficulty, BotPersonality},
     score::Score,
 };
-use rand::{rngs::SmallRng, SeedableRng};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
 use std::sync::Arc;
 use tracing::*;
 
+type Candidates = Vec<(Action, ComputedScore)>;
+
 #[derive(Debug, Clone)]
 pub struct DefaultBot {
     rng: SmallRng,
     flags: BotFlags,
+    preset: DifficultyPreset,
+    personality: BotPersonality,
+    weights: EvalWeights,
+    budget: ComputeBudget,
+    tracker: CardTracker,
 }
 
 pub struct BotBuilder {
@@ -31,6 +44,31 @@ impl BotBuilder {
         self
     }
 
+    pub fn with_difficulty(mut self, difficulty: BotDifficulty) -> Self {
+        self.inner.preset = difficulty.into();
+        self
+    }
+
+    pub fn with_preset(mut self, preset: DifficultyPreset) -> Self {
+        self.inner.preset = preset;
+        self
+    }
+
+    pub fn with_personality(mut self, personality: BotPersonality) -> Self {
+        self.inner.personality = personality;
+        self
+    }
+
+    pub fn with_weights(mut self, weights: EvalWeights) -> Self {
+        self.inner.weights = weights;
+        self
+    }
+
+    pub fn with_budget(mut self, budget: ComputeBudget) -> Self {
+        self.inner.budget = budget;
+        self
+    }
+
     pub fn build(self) -> DefaultBot {
         self.inner
     }
@@ -42,6 +80,11 @@ impl DefaultBot {
             inner: DefaultBot {
                 rng: SmallRng::from_entropy(),
                 flags: Default::default(),
+                preset: Default::default(),
+                personality: Default::default(),
+                weights: Default::default(),
+                budget: Default::default(),
+                tracker: Default::default(),
             },
         }
     }
@@ -49,130 +92,264 @@ impl DefaultBot {
     pub fn flags(&self) -> BotFlags {
         self.flags
     }
+
+    pub fn preset(&self) -> &DifficultyPreset {
+        &self.preset
+    }
+
+    pub fn personality(&self) -> BotPersonality {
+        self.personality
+    }
+
+    pub fn weights(&self) -> &EvalWeights {
+        &self.weights
+    }
+
+    pub fn budget(&self) -> &ComputeBudget {
+        &self.budget
+    }
+
+    /// Adds the evaluation noise of the preset to the score.
+    fn perturb(&mut self, mut score: ComputedScore) -> ComputedScore {
+        if self.preset.noise > 0 {
+            score.action += self.rng.gen_range(-self.preset.noise..=self.preset.noise);
+        }
+        score
+    }
 }
 
 impl Bot for DefaultBot {
     fn compute(
         &mut self,
-        env: Arc<Environment>,
+        view: &BotView,
         actions: &PlayerAvailableActions,
     ) -> Vec<(Action, ComputedScore)> {
+        self.compute_within(view, actions, self.budget)
+    }
+
+    fn compute_within(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+        budget: ComputeBudget,
+    ) -> Vec<(Action, ComputedScore)> {
+        let env = self.sample(view, actions);
+        self.compute_candidates(env, actions, budget).0
+    }
+
+    fn compute_trace(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+    ) -> BotDecisionTrace {
+        let env = self.sample(view, actions);
+        let (candidates, evaluated) = self.compute_candidates(env.clone(), actions, self.budget);
+        let chosen = candidates
+            .into_iter()
+            .max_by_key(|(_, score)| *score)
+            .map(|(action, _)| action);
+        BotDecisionTrace {
+            chosen,
+            ..BotDecisionTrace::new(&env, actions.player, evaluated)
+        }
+    }
+
+    fn observe(&mut self, logs: &[GameLog]) {
+        self.tracker.observe_logs(logs);
+    }
+}
+
+impl DefaultBot {
+    /// Returns the game as the player sees it, with the hidden cards guessed.
+    fn sample(&mut self, view: &BotView, actions: &PlayerAvailableActions) -> Arc<Environment> {
+        self.tracker.observe(&view.local);
+        Arc::new(self.tracker.sample(view, actions, &mut self.rng))
+    }
+
+    /// Returns the best candidate of each kind, and every candidate evaluated.
+    fn compute_candidates(
+        &mut self,
+        env: Arc<Environment>,
+        actions: &PlayerAvailableActions,
+        budget: ComputeBudget,
+    ) -> (Candidates, Candidates) {
         let ctx: BotContext = BotContext {
             rng: self.rng.clone(),
             player: actions.player,
             env: env.clone(),
             flags: self.flags,
+            depth: self.preset.depth,
+            weights: self.weights.with_personality(self.personality),
+            budget: Default::default(),
         };
 
         let selectable_card = actions.actions.selectable_cards();
-        let select = find_select_combination(ctx.clone(), selectable_card);
-        for (card, score) in &select {
-            let name = env
-                .state
-                .find_card(*card)
-                .ok()
-                .map(|card| card.archetype().name.clone())
-                .unwrap_or_default();
-            debug!("Select: {} score: {:?}", name, score);
-        }
-        let select = select
-            .into_iter()
-            .map(|(card, score)| (Action::SelectCard { card }, score))
-            .max_by_key(|(_, score)| *score);
-
         let cast_candidates = actions.actions.castable_cards();
-        let cast = cast::find_cast_combination(ctx.clone(), cast_candidates);
-        for (card, score) in &cast {
-            let name = env
-                .state
-                .find_card(*card)
-                .ok()
-                .map(|card| card.archetype().name.clone())
-                .unwrap_or_default();
-            debug!("Cast: {} score: {:?}", name, score);
-        }
-        let cast = cast
-            .into_iter()
-            .map(|(card, score)| (Action::CastCard { card }, score))
-            .filter(|(_, score)| score.score() > 0)
-            .max_by_key(|(_, score)| *score);
-
-        let battle = battle::find_attacker_combination(ctx.clone(), &actions.actions.attackers());
-        for (attackers, score) in &battle {
-            let attackers = attackers
-                .iter()
-                .filter_map(|id| env.state.find_card(*id).ok())
-                .map(|card| card.archetype().name.clone())
-                .collect::<Vec<_>>()
-                .join(", ");
-            debug!("Battle: {} score: {:?}", attackers, score);
-        }
-
-        let battle = battle
-            .into_iter()
-            .map(|(attackers, score)| {
-                (
-                    Action::Attack {
-                        attackers: attackers.clone(),
-                    },
-                    score,
-                )
-            })
-            .filter(|(_, score)| score.score() > 0)
-            .max_by_key(|(_, score)| *score);
-
+        let attack_candidates = actions.actions.attackers();
         let opponent = env.state.players().next_player(actions.player);
         let attackers = opponent
             .field
             .attacking_cards()
             .map(|card| card.id())
             .collect::<Vec<_>>();
-        let block =
-            battle::find_blocker_combination(ctx.clone(), &attackers, &actions.actions.blockers());
-        for (pairs, score) in &block {
-            let attackers = pairs
-                .iter()
-                .map(|(attacker, blocker)| {
-                    format!(
-                        "{} -> {}",
-                        env.state
-                            .find_card(*attacker)
-                            .ok()
-                            .map(|card| card.archetype().name.clone())
-                            .unwrap_or_default(),
-                        env.state
-                            .find_card(*blocker)
-                            .ok()
-                            .map(|card| card.archetype().name.clone())
-                            .unwrap()
-                    )
-                })
-                .collect::<Vec<_>>()
-                .join(", ");
-            debug!("Block: {} score: {:?}", attackers, score);
-        }
-        let block = block
+        let blockers = actions.actions.blockers();
+
+        // Each kind of decision gets its own share of the budget,
+        // so that casting cannot use up the time for attacking.
+        let kinds = [
+            !selectable_card.is_empty(),
+            !cast_candidates.is_empty(),
+            !attack_candidates.is_empty(),
+            !attackers.is_empty() && !blockers.is_empty(),
+        ];
+        let share = budget.split(kinds.into_iter().filter(|&kind| kind).count());
+        let ctx_with_share = || BotContext {
+            budget: share.start(),
+            ..ctx.clone()
+        };
+
+        let select = find_select_combination(ctx_with_share(), selectable_card)
             .into_iter()
-            .map(|(pairs, score)| (Action::Block { pairs }, score))
-            .max_by_key(|(_, score)| *score);
-
-        actions
-            .actions
-            .default_action(&env)
-            .map(|action| {
-                (
-                    action,
-                    ComputedScore {
-                        base: 0,
-                        action: -100,
-                    },
-                )
-            })
+            .map(|(card, score)| (Action::SelectCard { card }, self.perturb(score)))
+            .collect::<Vec<_>>();
+
+        let cast = cast::find_cast_combination(ctx_with_share(), cast_candidates)
+            .into_iter()
+            .map(|(card, score)| (Action::CastCard { card }, self.perturb(score)))
+            .collect::<Vec<_>>();
+
+        let battle = battle::find_attacker_combination(ctx_with_share(), &attack_candidates)
+            .into_iter()
+            .map(|(attackers, score)| (Action::Attack { attackers }, self.perturb(score)))
+            .collect::<Vec<_>>();
+
+        let block = battle::find_blocker_combination(ctx_with_share(), &attackers, &blockers)
+            .into_iter()
+            .map(|(pairs, score)| (Action::Block { pairs }, self.perturb(score)))
+            .collect::<Vec<_>>();
+
+        let local = env.local(actions.player);
+        let default_action = actions.actions.default_action(&local).map(|action| {
+            (
+                action,
+                ComputedScore {
+                    base: 0,
+                    action: -100,
+                },
+            )
+        });
+        let best = |list: &[(Action, ComputedScore)], positive: bool| {
+            list.iter()
+                .filter(|(_, score)| !positive || score.score() > 0)
+                .max_by_key(|(_, score)| *score)
+                .cloned()
+        };
+        let mut candidates = default_action
+            .clone()
+            .into_iter()
+            .chain(best(&select, false))
+            .chain(best(&cast, true))
+            .chain(best(&block, false))
+            .chain(best(&battle, true))
+            .collect::<Vec<_>>();
+        let evaluated = default_action
             .into_iter()
             .chain(select)
             .chain(cast)
             .chain(block)
             .chain(battle)
-            .collect()
+            .collect::<Vec<_>>();
+
+        if candidates.len() > 1 && self.rng.gen_bool(self.preset.blunder_rate) {
+            let best = candidates
+                .iter()
+                .map(|(_, score)| score.score())
+                .max()
+                .unwrap_or_default();
+            let index = self.rng.gen_range(0..candidates.len());
+            debug!("Blunder: {:?}", candidates[index].0);
+            candidates[index].1 = ComputedScore {
+                base: 0,
+                action: best + 1,
+            };
+        }
+
+        (candidates, evaluated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::{field::FieldState, id::ObjectId};
+    use kodecks_catalog::CATALOG;
+
+    fn id(id: u32) -> ObjectId {
+        ObjectId::try_from(id).unwrap()
+    }
+
+    /// Attacking with both creatures wins the game, which is the only good move.
+    fn lethal_attack() -> (Environment, PlayerAvailableActions) {
+        let mut env = Environment::builder(CATALOG.clone())
+            .with_player(0, |player| {
+                player
+                    .with_field(1, "halo", FieldState::Active)
+                    .with_field(2, "halo", FieldState::Active)
+                    .with_deck(3, "wind")
+            })
+            .with_player(1, |player| player.with_life(800).with_deck(4, "wind"))
+            .build();
+        let actions = env.advance(0, None).available_actions.unwrap();
+        (env, actions)
+    }
+
+    fn count_deviations(difficulty: BotDifficulty) -> usize {
+        let (env, actions) = lethal_attack();
+        (0..40)
+            .filter(|&seed| {
+                let mut bot = DefaultBot::builder()
+                    .with_difficulty(difficulty)
+                    .with_rng(SmallRng::seed_from_u64(seed))
+                    .build();
+                let action = bot.compute_best_action(&BotView::new(&env, 0), &actions);
+                !matches!(action, Some(Action::Attack { attackers }) if attackers == [id(1), id(2)])
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_budget_is_split_between_decisions() {
+        let mut env = Environment::builder(CATALOG.clone())
+            .with_player(0, |player| {
+                player
+                    .with_field(1, "halo", FieldState::Active)
+                    .with_hand(2, "wind")
+                    .with_hand(3, "wind")
+                    .with_deck(4, "wind")
+            })
+            .with_player(1, |player| player.with_deck(5, "wind"))
+            .build();
+        let actions = env.advance(0, None).available_actions.unwrap();
+        let budget = ComputeBudget {
+            max_evaluations: Some(2),
+            ..Default::default()
+        };
+
+        let mut bot = DefaultBot::builder()
+            .with_rng(SmallRng::seed_from_u64(0))
+            .build();
+        let (_, evaluated) = bot.compute_candidates(Arc::new(env), &actions, budget);
+        assert!(evaluated
+            .iter()
+            .any(|(action, _)| matches!(action, Action::CastCard { .. })));
+        assert!(evaluated
+            .iter()
+            .any(|(action, _)| matches!(action, Action::Attack { .. })));
+    }
+
+    #[test]
+    fn test_easy_bot_blunders() {
+        assert!(count_deviations(BotDifficulty::Easy) > 0);
+        assert_eq!(count_deviations(BotDifficulty::Normal), 0);
     }
 }
@@ -0,0 +1,286 @@
+use crate::{score::ComputedScore, Bot, BotView, ComputeBudget};
+use kodecks::{
+    action::{Action, PlayerAvailableActions},
+    env::LocalGameState,
+    log::GameLog,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+use tracing::*;
+
+/// The version of the protocol, sent to the process when it starts.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The time a process may take to answer unless a limit is given.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// The time a process is given to exit after [`ExternalRequest::Quit`] before it is killed.
+const QUIT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// A message from the engine to an external bot, written as a line of JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExternalRequest {
+    /// Sent once before the first request.
+    Start { protocol: u32, player: u8 },
+    /// Asks for an action to be answered with the same id.
+    ///
+    /// The state includes the available actions and the logs since the previous request.
+    Action { id: u64, state: Box<LocalGameState> },
+    /// Sent when the game is over, right before the process is stopped.
+    Quit,
+}
+
+/// A message from an external bot to the engine, written as a line of JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExternalResponse {
+    Action { id: u64, action: Action },
+}
+
+/// A bot played by another process speaking JSON lines on its stdin and stdout.
+///
+/// The engine writes an [`ExternalRequest`] per line and reads [`ExternalResponse`]s back.
+/// Lines that cannot be parsed are ignored, and stderr is passed through for logging.
+/// If the process does not answer in time, answers with an unavailable action
+/// or exits, the bot takes the default action instead.
+pub struct ExternalBot {
+    child: Child,
+    stdin: ChildStdin,
+    responses: Receiver<ExternalResponse>,
+    timeout: Duration,
+    started: bool,
+    next_id: u64,
+    logs: Vec<GameLog>,
+}
+
+impl ExternalBot {
+    /// Starts the program with the arguments.
+    pub fn spawn<I, S>(program: &str, args: I) -> io::Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<std::ffi::OsStr>,
+    {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+
+        let (sender, responses) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str(&line) {
+                    Ok(response) => {
+                        if sender.send(response).is_err() {
+                            break;
+                        }
+                    }
+                    Err(err) => warn!("Ignoring invalid message from external bot: {err}"),
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            stdin,
+            responses,
+            timeout: DEFAULT_TIMEOUT,
+            started: false,
+            next_id: 0,
+            logs: vec![],
+        })
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn send(&mut self, request: &ExternalRequest) -> io::Result<()> {
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes())?;
+        self.stdin.flush()
+    }
+
+    /// Sends the state and waits for the answer, returning `None` if none came in time.
+    fn request(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+        timeout: Duration,
+    ) -> io::Result<Option<Action>> {
+        if !self.started {
+            self.send(&ExternalRequest::Start {
+                protocol: PROTOCOL_VERSION,
+                player: actions.player,
+            })?;
+            self.started = true;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let state = LocalGameState {
+            env: view.local.clone(),
+            logs: std::mem::take(&mut self.logs),
+            available_actions: Some(actions.clone()),
+        };
+        self.send(&ExternalRequest::Action {
+            id,
+            state: Box::new(state),
+        })?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.responses.recv_timeout(remaining) {
+                Ok(ExternalResponse::Action {
+                    id: response_id,
+                    action,
+                }) if response_id == id => return Ok(Some(action)),
+                // Answers to requests that have already timed out.
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => return Ok(None),
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(io::ErrorKind::UnexpectedEof.into())
+                }
+            }
+        }
+    }
+}
+
+impl Bot for ExternalBot {
+    fn compute(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+    ) -> Vec<(Action, ComputedScore)> {
+        self.compute_within(view, actions, ComputeBudget::UNLIMITED)
+    }
+
+    fn compute_within(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+        budget: ComputeBudget,
+    ) -> Vec<(Action, ComputedScore)> {
+        let timeout = budget
+            .time_limit
+            .map_or(self.timeout, |limit| limit.min(self.timeout));
+        let action = match self.request(view, actions, timeout) {
+            Ok(Some(action)) if actions.validate(actions.player, &action) => Some(action),
+            Ok(Some(action)) => {
+                warn!("External bot chose an unavailable action: {action:?}");
+                None
+            }
+            Ok(None) => {
+                warn!("External bot did not answer within {timeout:?}");
+                None
+            }
+            Err(err) => {
+                warn!("Failed to communicate with external bot: {err}");
+                None
+            }
+        };
+        action
+            .or_else(|| actions.actions.default_action(&view.local))
+            .map(|action| (action, ComputedScore::default()))
+            .into_iter()
+            .collect()
+    }
+
+    fn observe(&mut self, logs: &[GameLog]) {
+        self.logs.extend_from_slice(logs);
+    }
+}
+
+impl Drop for ExternalBot {
+    fn drop(&mut self) {
+        let _ = self.send(&ExternalRequest::Quit);
+        let deadline = Instant::now() + QUIT_TIMEOUT;
+        while Instant::now() < deadline {
+            match self.child.try_wait() {
+                Ok(Some(_)) => return,
+                Ok(None) => thread::sleep(Duration::from_millis(10)),
+                Err(_) => break,
+            }
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use kodecks::env::Environment;
+    use kodecks_catalog::CATALOG;
+
+    /// Answers every request with the end of the turn, and exits when asked to.
+    const END_TURN_SCRIPT: &str = r#"
+        while read -r line; do
+            case "$line" in
+                *'"type":"action"'*)
+                    id=$(printf '%s' "$line" | sed 's/.*"type":"action","id":\([0-9]*\).*/\1/')
+                    printf '{"type":"action","id":%s,"action":{"name":"end_turn"}}\n' "$id";;
+                *'"type":"quit"'*)
+                    exit 0;;
+            esac
+        done
+    "#;
+
+    /// Reads every request without ever answering.
+    const SILENT_SCRIPT: &str = r#"while read -r line; do :; done"#;
+
+    fn view() -> (BotView, PlayerAvailableActions) {
+        let mut env = Environment::builder(CATALOG.clone())
+            .with_player(0, |player| player.with_hand(1, "wind").with_deck(2, "wind"))
+            .with_player(1, |player| player.with_deck(3, "wind"))
+            .build();
+        let actions = env.advance(0, None).available_actions.unwrap();
+        (BotView::new(&env, 0), actions)
+    }
+
+    #[test]
+    fn test_script_answers_requests() {
+        let (view, actions) = view();
+        let mut bot = ExternalBot::spawn("sh", ["-c", END_TURN_SCRIPT]).unwrap();
+        for _ in 0..2 {
+            let action = bot.compute_best_action(&view, &actions);
+            assert!(matches!(action, Some(Action::EndTurn)), "{action:?}");
+        }
+        assert_eq!(bot.next_id, 2);
+
+        let started = Instant::now();
+        drop(bot);
+        assert!(started.elapsed() < QUIT_TIMEOUT);
+    }
+
+    #[test]
+    fn test_timeout_falls_back_to_default_action() {
+        let (view, actions) = view();
+        let mut bot = ExternalBot::spawn("sh", ["-c", SILENT_SCRIPT])
+            .unwrap()
+            .with_timeout(Duration::from_millis(100));
+        let action = bot.compute_best_action(&view, &actions);
+        let default_action = actions.actions.default_action(&view.local);
+        assert_eq!(format!("{action:?}"), format!("{default_action:?}"));
+        assert!(action.is_some());
+    }
+}
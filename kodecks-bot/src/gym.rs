@@ -0,0 +1,662 @@
+//! A reinforcement learning environment in which an agent plays against a bot.
+//!
+//! The agent always plays as player 0 and sees only its [`LocalEnvironment`].
+//! Observations are vectors of [`OBSERVATION_SIZE`] numbers and actions are indices
+//! below [`ACTION_SPACE_SIZE`], laid out as follows.
+//!
+//! Observation:
+//! - Turn, phase (one-hot), whether the agent is in turn and the size of the stack.
+//! - For the agent and then the opponent: life, deck, hand and graveyard sizes,
+//!   and shards of each color, colorless first.
+//! - [`CARD_FEATURES`] numbers for each card slot: the hand and the field of the agent,
+//!   then the hand and the field of the opponent. Empty slots are all zeros.
+//!
+//! Card features: present, archetype (1-based index in the catalog, 0 if hidden), cost, power,
+//! shields, color bits, exhausted, battle state (1 attacking, 2 blocking, 3 attacked)
+//! and whether the card is chosen for the pending attack or block.
+//!
+//! Actions:
+//! - [`END_TURN`], [`CONTINUE`].
+//! - [`SUBMIT`]: declares the attackers or the blockers chosen so far, possibly none.
+//! - [`CAST`] + hand slot.
+//! - [`SELECT`] + card slot.
+//! - [`ATTACK`] + field slot: chooses an attacker.
+//! - [`BLOCK`] + attacker slot in the opponent field × [`FIELD_SLOTS`] + blocker slot.
+//!
+//! Choosing attackers and blockers does not advance the game, so a battle is declared
+//! in several steps. When no action of the agent can be encoded, it takes the default action.
+//!
+//! Hands are cut down to [`Regulation::max_hand_size`] at the end of each turn, and
+//! [`HAND_SLOTS`] leaves room for the cards drawn in a turn on top of that. Should a hand
+//! still outgrow its slots, the cards past the last slot are left out of the observation
+//! apart from the hand size, and are always masked: they have no index to be cast or selected.
+
+use crate::{Bot, BotView, DefaultBot};
+use kodecks::{
+    action::{Action, AvailableAction, PlayerAvailableActions},
+    archetype::ArchetypeId,
+    card::CardSnapshot,
+    catalog::Catalog,
+    color::Color,
+    deck::DeckList,
+    env::{EndgameState, Environment, LocalEnvironment},
+    field::{FieldBattleState, FieldState},
+    id::ObjectId,
+    phase::Phase,
+    player::PlayerConfig,
+    profile::GameProfile,
+    regulation::Regulation,
+};
+use rand::{rngs::SmallRng, SeedableRng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::{collections::HashMap, sync::Arc};
+use thiserror::Error;
+
+pub const HAND_SLOTS: usize = 10;
+const _: () = assert!(HAND_SLOTS > Regulation::STANDARD.max_hand_size as usize);
+pub const FIELD_SLOTS: usize = 10;
+pub const CARD_SLOTS: usize = (HAND_SLOTS + FIELD_SLOTS) * 2;
+pub const CARD_FEATURES: usize = 9;
+const GLOBAL_FEATURES: usize = 9;
+const PLAYER_FEATURES: usize = 9;
+pub const OBSERVATION_SIZE: usize =
+    GLOBAL_FEATURES + PLAYER_FEATURES * 2 + CARD_SLOTS * CARD_FEATURES;
+
+pub const END_TURN: usize = 0;
+pub const CONTINUE: usize = 1;
+pub const SUBMIT: usize = 2;
+pub const CAST: usize = 3;
+pub const SELECT: usize = CAST + HAND_SLOTS;
+pub const ATTACK: usize = SELECT + CARD_SLOTS;
+pub const BLOCK: usize = ATTACK + FIELD_SLOTS;
+pub const ACTION_SPACE_SIZE: usize = BLOCK + FIELD_SLOTS * FIELD_SLOTS;
+
+const AGENT: u8 = 0;
+const OPPONENT: u8 = 1;
+
+pub type OpponentFactory = Arc<dyn Fn(u64) -> Box<dyn Bot + Send> + Send + Sync>;
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum GymError {
+    #[error("The game has not been reset")]
+    NotStarted,
+    #[error("The game is over")]
+    GameOver,
+    #[error("Action {0} is not legal")]
+    IllegalAction(usize),
+}
+
+/// The state of the game after an action of the agent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub observation: Vec<f32>,
+    /// Whether each action index is legal.
+    pub mask: Vec<bool>,
+    /// 1 for a win and -1 for a loss, given when the game ends.
+    pub reward: f32,
+    /// Whether the game has ended or reached the turn limit.
+    pub done: bool,
+}
+
+/// An action index resolved against the current state.
+enum Resolved {
+    Play(Action),
+    AddAttacker(ObjectId),
+    AddBlocker(ObjectId, ObjectId),
+}
+
+/// A two-player game in which the agent plays against a bot.
+pub struct GymEnv {
+    catalog: Arc<Catalog>,
+    archetypes: HashMap<ArchetypeId, usize>,
+    opponent_factory: OpponentFactory,
+    opponent: Option<Box<dyn Bot + Send>>,
+    max_turns: u16,
+    env: Option<Arc<Environment>>,
+    actions: Option<PlayerAvailableActions>,
+    attackers: Vec<ObjectId>,
+    blockers: Vec<(ObjectId, ObjectId)>,
+    done: bool,
+}
+
+impl GymEnv {
+    pub fn new(catalog: Arc<Catalog>) -> Self {
+        let archetypes = catalog
+            .iter()
+            .enumerate()
+            .map(|(i, card)| (card.id, i + 1))
+            .collect();
+        Self {
+            catalog,
+            archetypes,
+            opponent_factory: Arc::new(|seed| {
+                Box::new(
+                    DefaultBot::builder()
+                        .with_rng(SmallRng::seed_from_u64(seed))
+                        .build(),
+                )
+            }),
+            opponent: None,
+            max_turns: 100,
+            env: None,
+            actions: None,
+            attackers: vec![],
+            blockers: vec![],
+            done: false,
+        }
+    }
+
+    /// Sets the bot playing against the agent, created with the seed of each game.
+    pub fn with_opponent(mut self, factory: OpponentFactory) -> Self {
+        self.opponent_factory = factory;
+        self
+    }
+
+    /// Sets the turn limit, after which the game ends without a reward.
+    pub fn with_max_turns(mut self, max_turns: u16) -> Self {
+        self.max_turns = max_turns;
+        self
+    }
+
+    /// Starts a new game in which the agent plays the first deck.
+    pub fn reset(&mut self, seed: u64, decks: [DeckList; 2]) -> Step {
+        let profile = GameProfile {
+            players: decks
+                .into_iter()
+                .map(|deck| PlayerConfig { deck })
+                .collect(),
+            rng_seed: Some(seed),
+            ..Default::default()
+        };
+        self.env = Some(Arc::new(Environment::new(profile, self.catalog.clone())));
+        self.opponent = Some((self.opponent_factory)(seed));
+        self.actions = None;
+        self.done = false;
+        let reward = self.advance(AGENT, None);
+        self.step_result(reward)
+    }
+
+    /// Takes the action and lets the game run until the agent has to act again.
+    pub fn step(&mut self, action: usize) -> Result<Step, GymError> {
+        if self.env.is_none() {
+            return Err(GymError::NotStarted);
+        }
+        if self.done {
+            return Err(GymError::GameOver);
+        }
+        let reward = match self.resolve(action) {
+            Some(Resolved::Play(action)) => self.advance(AGENT, Some(action)),
+            Some(Resolved::AddAttacker(attacker)) => {
+                self.attackers.push(attacker);
+                0.0
+            }
+            Some(Resolved::AddBlocker(attacker, blocker)) => {
+                self.blockers.push((attacker, blocker));
+                0.0
+            }
+            None => return Err(GymError::IllegalAction(action)),
+        };
+        Ok(self.step_result(reward))
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Returns the encoded state as seen by the agent.
+    pub fn observation(&self) -> Vec<f32> {
+        let mut obs = Vec::with_capacity(OBSERVATION_SIZE);
+        let Some(env) = &self.env else {
+            obs.resize(OBSERVATION_SIZE, 0.0);
+            return obs;
+        };
+        let local = env.local(AGENT);
+
+        obs.push(local.turn as f32);
+        let phase = match local.phase {
+            Phase::Standby => 0,
+            Phase::Draw => 1,
+            Phase::Main => 2,
+            Phase::Block => 3,
+            Phase::Battle => 4,
+            Phase::End => 5,
+        };
+        obs.extend((0..6).map(|i| if i == phase { 1.0 } else { 0.0 }));
+        obs.push(flag(local.players.player_in_turn().id == AGENT));
+        obs.push(local.stack.iter().count() as f32);
+
+        for player in [AGENT, OPPONENT] {
+            let player = local.players.get(player);
+            obs.push(player.stats.life as f32);
+            obs.push(player.deck as f32);
+            obs.push(player.hand.len() as f32);
+            obs.push(player.graveyard.len() as f32);
+            obs.extend(
+                std::iter::once(Color::COLORLESS)
+                    .chain(Color::iter_all())
+                    .map(|color| player.shards.get(color) as f32),
+            );
+        }
+
+        for player in [AGENT, OPPONENT] {
+            let player = local.players.get(player);
+            for slot in 0..HAND_SLOTS {
+                match player.hand.get(slot) {
+                    Some(item) => self.encode_card(&mut obs, &item.card, item.cost_delta, None),
+                    None => obs.extend([0.0; CARD_FEATURES]),
+                }
+            }
+            for slot in 0..FIELD_SLOTS {
+                match player.field.get(slot) {
+                    Some(item) => {
+                        let exhausted = item.state == FieldState::Exhausted;
+                        let battle = match item.battle {
+                            None => 0.0,
+                            Some(FieldBattleState::Attacking) => 1.0,
+                            Some(FieldBattleState::Blocking { .. }) => 2.0,
+                            Some(FieldBattleState::Attacked) => 3.0,
+                        };
+                        self.encode_card(&mut obs, &item.card, 0, Some((exhausted, battle)))
+                    }
+                    None => obs.extend([0.0; CARD_FEATURES]),
+                }
+            }
+        }
+        obs
+    }
+
+    /// Returns whether each action index is legal.
+    pub fn action_mask(&self) -> Vec<bool> {
+        match self.decision() {
+            Some(decision) => (0..ACTION_SPACE_SIZE)
+                .map(|action| decision.resolve(action).is_some())
+                .collect(),
+            None => vec![false; ACTION_SPACE_SIZE],
+        }
+    }
+
+    fn encode_card(
+        &self,
+        obs: &mut Vec<f32>,
+        card: &CardSnapshot,
+        cost_delta: i8,
+        field: Option<(bool, f32)>,
+    ) {
+        let archetype = (!card.archetype_id.is_empty()).then(|| &self.catalog[card.archetype_id]);
+        let (cost, power, shields, color) = match (&card.computed, archetype) {
+            (Some(computed), _) => (
+                computed.cost.value() as f32,
+                computed.power().map_or(0, |power| power.value()) as f32,
+                computed.shields.map_or(0, |shields| shields.value()) as f32,
+                computed.color.bits() as f32,
+            ),
+            (None, Some(archetype)) => (
+                archetype.attribute.cost as f32,
+                archetype.attribute.power.unwrap_or(0) as f32,
+                archetype.attribute.shields.unwrap_or(0) as f32,
+                archetype.attribute.color.bits() as f32,
+            ),
+            (None, None) => (0.0, 0.0, 0.0, 0.0),
+        };
+        let cost = if archetype.is_some() {
+            (cost + cost_delta as f32).max(0.0)
+        } else {
+            0.0
+        };
+        let (exhausted, battle) = field.unwrap_or_default();
+        let pending = self.attackers.contains(&card.id)
+            || self.blockers.iter().any(|(_, blocker)| *blocker == card.id);
+        obs.extend([
+            1.0,
+            archetype.map_or(0, |archetype| {
+                self.archetypes.get(&archetype.id).copied().unwrap_or(0)
+            }) as f32,
+            cost,
+            power,
+            shields,
+            color,
+            flag(exhausted),
+            battle,
+            flag(pending),
+        ]);
+    }
+
+    fn decision(&self) -> Option<Decision<'_>> {
+        let env = self.env.as_ref()?;
+        let actions = self.actions.as_ref().filter(|_| !self.done)?;
+        Some(Decision::new(
+            env.local(AGENT),
+            actions,
+            &self.attackers,
+            &self.blockers,
+        ))
+    }
+
+    fn resolve(&self, action: usize) -> Option<Resolved> {
+        self.decision()?.resolve(action)
+    }
+
+    /// Runs the game until the agent has an encodable decision or the game ends,
+    /// returning the reward.
+    fn advance(&mut self, mut player: u8, mut action: Option<Action>) -> f32 {
+        let (Some(env), Some(opponent)) = (&mut self.env, &mut self.opponent) else {
+            return 0.0;
+        };
+        self.actions = None;
+        self.attackers.clear();
+        self.blockers.clear();
+        loop {
+            let report = Arc::make_mut(env).process(player, action.take());
+            let logs = report
+                .logs
+                .iter()
+                .map(|log| log.clone().redacted(OPPONENT))
+                .collect::<Vec<_>>();
+            opponent.observe(&logs);
+
+            if let EndgameState::Finished { winner, .. } = report.endgame {
+                self.done = true;
+                return match winner {
+                    Some(AGENT) => 1.0,
+                    Some(_) => -1.0,
+                    None => 0.0,
+                };
+            }
+            if env.state.turn > self.max_turns {
+                self.done = true;
+                return 0.0;
+            }
+            let Some(actions) = report.available_actions else {
+                continue;
+            };
+            player = actions.player;
+            action = if player == AGENT {
+                let decision = Decision::new(env.local(AGENT), &actions, &[], &[]);
+                if (0..ACTION_SPACE_SIZE).any(|action| decision.resolve(action).is_some()) {
+                    self.actions = Some(actions);
+                    return 0.0;
+                }
+                actions.actions.default_action(&env.local(AGENT))
+            } else {
+                opponent.compute_best_action(&BotView::new(env, player), &actions)
+            };
+        }
+    }
+
+    fn step_result(&self, reward: f32) -> Step {
+        Step {
+            observation: self.observation(),
+            mask: self.action_mask(),
+            reward,
+            done: self.done,
+        }
+    }
+}
+
+/// A decision of the agent, against which action indices are resolved.
+struct Decision<'a> {
+    local: LocalEnvironment,
+    /// The cards in the order of the card slots.
+    slots: Vec<Option<ObjectId>>,
+    actions: &'a PlayerAvailableActions,
+    attackers: &'a [ObjectId],
+    blockers: &'a [(ObjectId, ObjectId)],
+}
+
+impl<'a> Decision<'a> {
+    fn new(
+        local: LocalEnvironment,
+        actions: &'a PlayerAvailableActions,
+        attackers: &'a [ObjectId],
+        blockers: &'a [(ObjectId, ObjectId)],
+    ) -> Self {
+        // Hand cards past the last slot get no slot, so no index can refer to them.
+        let slots = [AGENT, OPPONENT]
+            .into_iter()
+            .flat_map(|player| {
+                let player = local.players.get(player);
+                let hand = (0..HAND_SLOTS).map(|i| player.hand.get(i).map(|item| item.card.id));
+                let field = (0..FIELD_SLOTS).map(|i| player.field.get(i).map(|item| item.card.id));
+                hand.chain(field).collect::<Vec<_>>()
+            })
+            .collect();
+        Self {
+            local,
+            slots,
+            actions,
+            attackers,
+            blockers,
+        }
+    }
+
+    fn resolve(&self, action: usize) -> Option<Resolved> {
+        let list = &self.actions.actions;
+        let agent_field = |slot: usize| self.slots[HAND_SLOTS + slot];
+        let opponent_field = |slot: usize| self.slots[HAND_SLOTS * 2 + FIELD_SLOTS + slot];
+        let can_attack = list
+            .iter()
+            .any(|action| matches!(action, AvailableAction::Attack { .. }));
+        let can_block = list
+            .iter()
+            .any(|action| matches!(action, AvailableAction::Block { .. }));
+
+        let resolved = match action {
+            END_TURN if list.can_end_turn() => Resolved::Play(Action::EndTurn),
+            CONTINUE if list.can_continue() => Resolved::Play(Action::Continue),
+            SUBMIT if can_attack => Resolved::Play(Action::Attack {
+                attackers: self.attackers.to_vec(),
+            }),
+            SUBMIT if can_block => Resolved::Play(Action::Block {
+                pairs: self.blockers.to_vec(),
+            }),
+            action if (CAST..SELECT).contains(&action) => {
+                let card = self.slots[action - CAST]?;
+                if !list.castable_cards().contains(&card) {
+                    return None;
+                }
+                Resolved::Play(Action::CastCard { card })
+            }
+            action if (SELECT..ATTACK).contains(&action) => {
+                let card = self.slots[action - SELECT]?;
+                if !list.selectable_cards().contains(&card) {
+                    return None;
+                }
+                Resolved::Play(Action::SelectCard { card })
+            }
+            action if (ATTACK..BLOCK).contains(&action) => {
+                let card = agent_field(action - ATTACK)?;
+                if !list.attackers().contains(&card) || self.attackers.contains(&card) {
+                    return None;
+                }
+                Resolved::AddAttacker(card)
+            }
+            action if (BLOCK..ACTION_SPACE_SIZE).contains(&action) => {
+                let index = action - BLOCK;
+                let attacker = opponent_field(index / FIELD_SLOTS)?;
+                let blocker = agent_field(index % FIELD_SLOTS)?;
+                let attacking = self.local.players.get(OPPONENT).field.iter().any(|item| {
+                    item.card.id == attacker && item.battle == Some(FieldBattleState::Attacking)
+                });
+                let used = self
+                    .blockers
+                    .iter()
+                    .any(|(a, b)| *a == attacker || *b == blocker);
+                if !attacking || used || !list.blockers().contains(&blocker) {
+                    return None;
+                }
+                Resolved::AddBlocker(attacker, blocker)
+            }
+            _ => return None,
+        };
+        if let Resolved::Play(action) = &resolved {
+            if !self.actions.validate(AGENT, action) {
+                return None;
+            }
+        }
+        Some(resolved)
+    }
+}
+
+fn flag(value: bool) -> f32 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Runs several games at once, in parallel if the `rayon` feature is enabled.
+///
+/// A game that ends is reset with the next seed and the same decks, so the step reporting
+/// the end of a game carries the observation of the next one.
+pub struct GymBatch {
+    envs: Vec<GymEnv>,
+    decks: Option<[DeckList; 2]>,
+    next_seed: u64,
+}
+
+impl GymBatch {
+    pub fn new(envs: Vec<GymEnv>) -> Self {
+        Self {
+            envs,
+            decks: None,
+            next_seed: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.envs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.envs.is_empty()
+    }
+
+    /// Resets every game, using consecutive seeds starting from `seed`.
+    pub fn reset(&mut self, seed: u64, decks: [DeckList; 2]) -> Vec<Step> {
+        self.next_seed = seed + self.envs.len() as u64;
+        self.decks = Some(decks.clone());
+
+        #[cfg(feature = "rayon")]
+        let envs = self.envs.par_iter_mut();
+        #[cfg(not(feature = "rayon"))]
+        let envs = self.envs.iter_mut();
+
+        envs.enumerate()
+            .map(|(i, env)| env.reset(seed + i as u64, decks.clone()))
+            .collect()
+    }
+
+    /// Takes an action in each game.
+    pub fn step(&mut self, actions: &[usize]) -> Vec<Result<Step, GymError>> {
+        assert_eq!(actions.len(), self.envs.len(), "one action per game");
+        let seeds = (0..self.envs.len() as u64)
+            .map(|i| self.next_seed + i)
+            .collect::<Vec<_>>();
+        self.next_seed += self.envs.len() as u64;
+        let decks = &self.decks;
+
+        #[cfg(feature = "rayon")]
+        let envs = self.envs.par_iter_mut().zip(actions.par_iter()).zip(seeds);
+        #[cfg(not(feature = "rayon"))]
+        let envs = self.envs.iter_mut().zip(actions.iter()).zip(seeds);
+
+        envs.map(|((env, &action), seed)| {
+            let step = env.step(action)?;
+            match decks {
+                Some(decks) if step.done => {
+                    let next = env.reset(seed, decks.clone());
+                    Ok(Step {
+                        observation: next.observation,
+                        mask: next.mask,
+                        ..step
+                    })
+                }
+                _ => Ok(step),
+            }
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleBot;
+    use kodecks_catalog::{decks, CATALOG};
+    use rand::Rng;
+
+    const SEED: u64 = 0;
+
+    fn decks() -> [DeckList; 2] {
+        [decks::starter_deck(&CATALOG), decks::red_deck(&CATALOG)]
+    }
+
+    /// Starts a game against [`SimpleBot`] and takes the actions.
+    fn replay(actions: &[usize]) -> GymEnv {
+        let mut gym = GymEnv::new(CATALOG.clone())
+            .with_opponent(Arc::new(|_| Box::new(SimpleBot)))
+            .with_max_turns(30);
+        gym.reset(SEED, decks());
+        for &action in actions {
+            gym.step(action).unwrap();
+        }
+        gym
+    }
+
+    fn legal_actions(mask: &[bool]) -> Vec<usize> {
+        (0..mask.len()).filter(|&action| mask[action]).collect()
+    }
+
+    #[test]
+    fn test_observation_size() {
+        let gym = GymEnv::new(CATALOG.clone());
+        assert_eq!(gym.observation().len(), OBSERVATION_SIZE);
+
+        let mut gym = replay(&[]);
+        assert_eq!(gym.observation().len(), OBSERVATION_SIZE);
+        let mut rng = SmallRng::seed_from_u64(SEED);
+        for _ in 0..20 {
+            let legal = legal_actions(&gym.action_mask());
+            let step = gym.step(legal[rng.gen_range(0..legal.len())]).unwrap();
+            assert_eq!(step.observation.len(), OBSERVATION_SIZE);
+            assert_eq!(step.mask.len(), ACTION_SPACE_SIZE);
+        }
+    }
+
+    #[test]
+    fn test_masked_actions_are_legal() {
+        let mut rng = SmallRng::seed_from_u64(SEED);
+        let mut history = vec![];
+        for _ in 0..8 {
+            let legal = legal_actions(&replay(&history).action_mask());
+            assert!(!legal.is_empty());
+            for &action in &legal {
+                let result = replay(&history).step(action);
+                assert!(
+                    !matches!(result, Err(GymError::IllegalAction(_))),
+                    "action {action} after {history:?}"
+                );
+            }
+            history.push(legal[rng.gen_range(0..legal.len())]);
+        }
+    }
+
+    #[test]
+    fn test_random_episode_ends() {
+        let mut gym = replay(&[]);
+        let mut rng = SmallRng::seed_from_u64(SEED);
+        let mut reward = 0.0;
+        while !gym.is_done() {
+            let legal = legal_actions(&gym.action_mask());
+            let step = gym.step(legal[rng.gen_range(0..legal.len())]).unwrap();
+            reward = step.reward;
+            if !step.done {
+                assert_eq!(step.reward, 0.0);
+            }
+        }
+        assert!([1.0, -1.0, 0.0].contains(&reward));
+        assert_eq!(gym.step(END_TURN), Err(GymError::GameOver));
+    }
+}
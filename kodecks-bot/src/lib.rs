@@ -4,21 +4,43 @@ use bitflags::bitflags;
 use kodecks::{
     action::{Action, PlayerAvailableActions},
     env::Environment,
-    
+    log::GameLog,
 };
-use rand::rngs::SmallRng;
+use rand::{rngs::SmallRng, SeedableRng};
 use score::ComputedScore;
 use std::sync::Arc;
 
 mod battle;
+mod budget;
 mod cast;
+pub mod deckbuild;
 mod default;
+#[cfg(not(target_family = "wasm"))]
+mod external;
+pub mod gym;
+mod mcts;
+mod preset;
 mod score;
+mod scripted;
 mod select;
+pub mod selfplay;
 mod simple;
+mod trace;
+mod tracker;
+mod view;
 
+pub use budget::{BudgetTracker, ComputeBudget};
 pub use default::DefaultBot;
+#[cfg(not(target_family = "wasm"))]
+pub use external::{ExternalBot, ExternalRequest, ExternalResponse, PROTOCOL_VERSION};
+pub use mcts::{MctsBot, MctsConfig};
+pub use preset::DifficultyPreset;
+pub use score::EvalWeights;
+pub use scripted::ScriptedBot;
 pub use simple::SimpleBot;
+pub use trace::{BotDecisionTrace, CandidateTrace, PlayerOutcome, SimulatedOutcome};
+pub use tracker::CardTracker;
+pub use view::BotView;
 
 #[derive(Clone)]
 pub struct BotContext {
@@ -26,6 +48,10 @@ pub struct BotContext {
     pub flags: BotFlags,
     pub player: u8,
     pub env: Arc<Environment>,
+    /// The number of turns simulated after the current one.
+    pub depth: u16,
+    pub weights: EvalWeights,
+    pub budget: BudgetTracker,
 }
 
 bitflags! {
@@ -36,19 +62,64 @@ bitflags! {
     }
 }
 
+/// A bot is shown only what its player can see, and the redacted logs of the game.
 pub trait Bot {
     fn compute(
         &mut self,
-        env: Arc<Environment>,
+        view: &BotView,
         actions: &PlayerAvailableActions,
     ) -> Vec<(Action, ComputedScore)>;
 
+    /// Computes the candidates within the budget.
+    ///
+    /// Searching bots stop when the budget runs out and return what they have evaluated so far.
+    fn compute_within(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+        _budget: ComputeBudget,
+    ) -> Vec<(Action, ComputedScore)> {
+        self.compute(view, actions)
+    }
+
+    /// Receives the logs of the game, redacted for the player of the bot.
+    fn observe(&mut self, _logs: &[GameLog]) {}
+
+    /// Computes the candidates and traces how the best one is chosen.
+    ///
+    /// The outcomes are simulated in a game with the hidden cards guessed from the view.
+    /// Bots searching their own guesses override this to simulate in the game they searched.
+    fn compute_trace(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+    ) -> BotDecisionTrace {
+        let candidates = self.compute(view, actions);
+        let mut tracker = CardTracker::new();
+        tracker.observe(&view.local);
+        let mut rng = SmallRng::seed_from_u64(view.local.checksum());
+        let env = tracker.sample(view, actions, &mut rng);
+        BotDecisionTrace::new(&env, actions.player, candidates)
+    }
+
     fn compute_best_action(
         &mut self,
-        env: Arc<Environment>,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+    ) -> Option<Action> {
+        self.compute(view, actions)
+            .into_iter()
+            .max_by_key(|(_, score)| *score)
+            .map(|(action, _)| action)
+    }
+
+    fn compute_best_action_within(
+        &mut self,
+        view: &BotView,
         actions: &PlayerAvailableActions,
+        budget: ComputeBudget,
     ) -> Option<Action> {
-        self.compute(env, actions)
+        self.compute_within(view, actions, budget)
             .into_iter()
             .max_by_key(|(_, score)| *score)
             .map(|(action, _)| action)
@@ -0,0 +1,520 @@
+use crate::{
+    score::{get_score, ComputedScore, EvalWeights},
+    Bot, BotDecisionTrace, BotView, BudgetTracker, CardTracker, ComputeBudget, SimpleBot,
+};
+use kodecks::{
+    action::{Action, AvailableAction, PlayerAvailableActions},
+    env::{EndgameState, Environment, LocalEnvironment},
+    field::FieldBattleState,
+    log::GameLog,
+};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::time::Duration;
+use tracing::*;
+
+/// Rollouts give up after this many steps, which only happens in a stalled game.
+const MAX_ROLLOUT_STEPS: usize = 1000;
+
+/// Divides the evaluation before squashing it into a reward.
+const SCORE_SCALE: f64 = 20.0;
+
+#[derive(Debug, Clone, Copy)]
+pub struct MctsConfig {
+    /// The number of iterations for each determinization.
+    pub iterations: usize,
+    /// Stops the search early once the time is up.
+    pub time_budget: Option<Duration>,
+    /// The number of sampled games, each searched in its own tree.
+    pub determinizations: usize,
+    /// The number of turns a rollout plays before the state is evaluated.
+    pub rollout_turns: u16,
+    /// The exploration constant of UCT.
+    pub exploration: f64,
+    /// Used to evaluate the states where rollouts stop.
+    pub weights: EvalWeights,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 200,
+            time_budget: None,
+            determinizations: 4,
+            rollout_turns: 4,
+            exploration: std::f64::consts::SQRT_2,
+            weights: Default::default(),
+        }
+    }
+}
+
+/// A bot that runs Monte Carlo tree search over determinized games.
+///
+/// The cards hidden from the bot are guessed anew for each determinization,
+/// and the visit counts of the trees are merged to choose the action.
+#[derive(Debug, Clone)]
+pub struct MctsBot {
+    rng: SmallRng,
+    config: MctsConfig,
+    tracker: CardTracker,
+}
+
+pub struct MctsBotBuilder {
+    inner: MctsBot,
+}
+
+impl MctsBotBuilder {
+    pub fn with_config(mut self, config: MctsConfig) -> Self {
+        self.inner.config = config;
+        self
+    }
+
+    pub fn with_rng(mut self, rng: SmallRng) -> Self {
+        self.inner.rng = rng;
+        self
+    }
+
+    pub fn build(self) -> MctsBot {
+        self.inner
+    }
+}
+
+impl MctsBot {
+    pub fn builder() -> MctsBotBuilder {
+        MctsBotBuilder {
+            inner: MctsBot {
+                rng: SmallRng::from_entropy(),
+                config: Default::default(),
+                tracker: Default::default(),
+            },
+        }
+    }
+
+    pub fn config(&self) -> &MctsConfig {
+        &self.config
+    }
+}
+
+impl Bot for MctsBot {
+    fn compute(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+    ) -> Vec<(Action, ComputedScore)> {
+        self.compute_within(view, actions, ComputeBudget::UNLIMITED)
+    }
+
+    fn observe(&mut self, logs: &[GameLog]) {
+        self.tracker.observe_logs(logs);
+    }
+
+    /// The outcomes are simulated in the first of the games searched.
+    fn compute_trace(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+    ) -> BotDecisionTrace {
+        let (candidates, env) = self.search(view, actions, ComputeBudget::UNLIMITED);
+        BotDecisionTrace::new(&env, actions.player, candidates)
+    }
+
+    /// Each iteration counts as an evaluation, shared by all determinizations.
+    fn compute_within(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+        budget: ComputeBudget,
+    ) -> Vec<(Action, ComputedScore)> {
+        self.search(view, actions, budget).0
+    }
+}
+
+impl MctsBot {
+    /// Searches the determinizations and returns the candidates with the first sampled game.
+    fn search(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+        budget: ComputeBudget,
+    ) -> (Vec<(Action, ComputedScore)>, Environment) {
+        self.tracker.observe(&view.local);
+        let candidates = candidate_actions(&view.local, actions);
+        if candidates.len() <= 1 {
+            let env = self.tracker.sample(view, actions, &mut self.rng);
+            let candidates = candidates
+                .into_iter()
+                .map(|action| (action, ComputedScore::default()))
+                .collect();
+            return (candidates, env);
+        }
+
+        let config = self.config;
+        let budget = ComputeBudget {
+            time_limit: budget.time_limit.or(config.time_budget),
+            ..budget
+        }
+        .start();
+        let seeds = (0..config.determinizations.max(1))
+            .map(|_| self.rng.gen::<u64>())
+            .collect::<Vec<_>>();
+        let tracker = &self.tracker;
+        let search = |seed: u64| {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let root = tracker.sample(view, actions, &mut rng);
+            let mut tree = Tree::new(actions.player, candidates.clone());
+            tree.search(root.clone(), &config, &budget);
+            (tree.nodes.swap_remove(0).stats, root)
+        };
+
+        #[cfg(feature = "rayon")]
+        let results = seeds.into_par_iter().map(search).collect::<Vec<_>>();
+        #[cfg(not(feature = "rayon"))]
+        let results = seeds.into_iter().map(search).collect::<Vec<_>>();
+
+        let mut stats = vec![Stats::default(); candidates.len()];
+        let mut sampled = None;
+        for (result, root) in results {
+            sampled.get_or_insert(root);
+            for (total, stats) in stats.iter_mut().zip(result) {
+                total.visits += stats.visits;
+                total.reward += stats.reward;
+            }
+        }
+
+        let candidates = candidates
+            .into_iter()
+            .zip(stats)
+            .map(|(action, stats)| {
+                debug!(
+                    "MCTS: {:?} visits: {} mean: {:.3}",
+                    action,
+                    stats.visits,
+                    stats.mean()
+                );
+                (
+                    action,
+                    ComputedScore {
+                        base: 0,
+                        action: stats.visits as i32,
+                    },
+                )
+            })
+            .collect();
+        (candidates, sampled.unwrap())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Stats {
+    visits: u32,
+    /// The sum of rewards from the point of view of the searching player.
+    reward: f64,
+}
+
+impl Stats {
+    fn mean(&self) -> f64 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.reward / self.visits as f64
+        }
+    }
+}
+
+struct Node {
+    player: u8,
+    actions: Vec<Action>,
+    children: Vec<Option<usize>>,
+    stats: Vec<Stats>,
+    visits: u32,
+}
+
+impl Node {
+    fn new(player: u8, actions: Vec<Action>) -> Self {
+        let len = actions.len();
+        Self {
+            player,
+            actions,
+            children: vec![None; len],
+            stats: vec![Stats::default(); len],
+            visits: 0,
+        }
+    }
+
+    fn select(&self, root: u8, exploration: f64) -> usize {
+        if let Some(index) = self.stats.iter().position(|stats| stats.visits == 0) {
+            return index;
+        }
+        let log_visits = (self.visits as f64).ln();
+        let uct = |stats: &Stats| {
+            let mean = stats.mean();
+            let value = if self.player == root {
+                mean
+            } else {
+                1.0 - mean
+            };
+            value + exploration * (log_visits / stats.visits as f64).sqrt()
+        };
+        (0..self.stats.len())
+            .max_by(|&a, &b| uct(&self.stats[a]).total_cmp(&uct(&self.stats[b])))
+            .unwrap_or_default()
+    }
+}
+
+struct Tree {
+    root: u8,
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    fn new(root: u8, actions: Vec<Action>) -> Self {
+        Self {
+            root,
+            nodes: vec![Node::new(root, actions)],
+        }
+    }
+
+    fn search(&mut self, env: Environment, config: &MctsConfig, budget: &BudgetTracker) {
+        for _ in 0..config.iterations {
+            if !budget.consume() {
+                break;
+            }
+            self.iterate(env.clone(), config);
+        }
+    }
+
+    fn iterate(&mut self, mut env: Environment, config: &MctsConfig) {
+        let mut path = vec![];
+        let mut node = 0;
+        loop {
+            let index = self.nodes[node].select(self.root, config.exploration);
+            let expand = self.nodes[node].stats[index].visits == 0;
+            path.push((node, index));
+
+            let current = &self.nodes[node];
+            let report = env.advance(current.player, Some(current.actions[index].clone()));
+            if report.rejected.is_some() || report.endgame.is_ended() {
+                break;
+            }
+            let Some(next) = report.available_actions else {
+                break;
+            };
+            match self.nodes[node].children[index] {
+                Some(child) if !expand => node = child,
+                _ => {
+                    let child = self.nodes.len();
+                    let actions = candidate_actions(&env.local(next.player), &next);
+                    self.nodes.push(Node::new(next.player, actions));
+                    self.nodes[node].children[index] = Some(child);
+                    env = rollout(env, next, config.rollout_turns);
+                    break;
+                }
+            }
+        }
+
+        let reward = evaluate(&env, self.root, &config.weights);
+        for (node, index) in path {
+            let node = &mut self.nodes[node];
+            node.visits += 1;
+            node.stats[index].visits += 1;
+            node.stats[index].reward += reward;
+        }
+    }
+}
+
+/// Plays the game with [`SimpleBot`] for the given number of turns.
+fn rollout(mut env: Environment, actions: PlayerAvailableActions, turns: u16) -> Environment {
+    let last_turn = env.state.turn.saturating_add(turns);
+    let mut next = Some(actions);
+    for _ in 0..MAX_ROLLOUT_STEPS {
+        let Some(actions) = next.take() else {
+            break;
+        };
+        if env.state.turn > last_turn {
+            break;
+        }
+        let action = SimpleBot::best_action(&env, &actions);
+        let report = env.advance(actions.player, action);
+        if report.rejected.is_some() || report.endgame.is_ended() {
+            break;
+        }
+        next = report.available_actions;
+    }
+    env
+}
+
+/// Returns the reward for the player in the range of 0 to 1.
+fn evaluate(env: &Environment, player: u8, weights: &EvalWeights) -> f64 {
+    match env.game_condition() {
+        EndgameState::Finished {
+            winner: Some(winner),
+            ..
+        } => {
+            if winner == player {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        EndgameState::Finished { winner: None, .. } => 0.5,
+        EndgameState::InProgress => {
+            let score = get_score(env, player, weights) as f64 / SCORE_SCALE;
+            1.0 / (1.0 + (-score).exp())
+        }
+    }
+}
+
+/// Expands the available actions into the moves the tree searches.
+///
+/// Attacks and blocks have too many combinations, so only all-out attacks,
+/// single attackers, single blocks and the heuristic block are considered.
+fn candidate_actions(env: &LocalEnvironment, actions: &PlayerAvailableActions) -> Vec<Action> {
+    let mut candidates = vec![];
+    for action in actions.actions.as_ref() {
+        match action {
+            AvailableAction::SelectCard { cards, .. } => {
+                candidates.extend(cards.iter().map(|&card| Action::SelectCard { card }));
+            }
+            AvailableAction::CastCard { cards } => {
+                candidates.extend(cards.iter().map(|&card| Action::CastCard { card }));
+            }
+            AvailableAction::Attack { attackers } => {
+                candidates.push(Action::Attack { attackers: vec![] });
+                if attackers.len() > 1 {
+                    candidates.push(Action::Attack {
+                        attackers: attackers.clone(),
+                    });
+                }
+                candidates.extend(attackers.iter().map(|&attacker| Action::Attack {
+                    attackers: vec![attacker],
+                }));
+            }
+            AvailableAction::Block { blockers } => {
+                candidates.push(Action::Block { pairs: vec![] });
+                let block = PlayerAvailableActions {
+                    actions: [action.clone()].into_iter().collect(),
+                    ..PlayerAvailableActions::new(actions.player)
+                };
+                if let Some(Action::Block { pairs }) = SimpleBot::choose(env, &block) {
+                    if pairs.len() > 1 {
+                        candidates.push(Action::Block { pairs });
+                    }
+                }
+                let opponent = env.next_player(actions.player);
+                let attackers = opponent
+                    .field
+                    .iter()
+                    .filter(|item| item.battle == Some(FieldBattleState::Attacking));
+                for attacker in attackers {
+                    candidates.extend(blockers.iter().map(|&blocker| Action::Block {
+                        pairs: vec![(attacker.card.id, blocker)],
+                    }));
+                }
+            }
+            AvailableAction::EndTurn => candidates.push(Action::EndTurn),
+            AvailableAction::Continue => candidates.push(Action::Continue),
+        }
+    }
+    if candidates.is_empty() {
+        candidates.extend(actions.actions.default_action(env));
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::{field::FieldState, id::ObjectId};
+    use kodecks_catalog::CATALOG;
+
+    fn id(id: u32) -> ObjectId {
+        ObjectId::try_from(id).unwrap()
+    }
+
+    fn lethal_env() -> Environment {
+        Environment::builder(CATALOG.clone())
+            .with_player(0, |player| {
+                player
+                    .with_field(1, "halo", FieldState::Active)
+                    .with_field(2, "halo", FieldState::Active)
+                    .with_deck(3, "wind")
+                    .with_deck(4, "wind")
+            })
+            .with_player(1, |player| {
+                player
+                    .with_life(800)
+                    .with_deck(5, "wind")
+                    .with_deck(6, "wind")
+            })
+            .build()
+    }
+
+    fn lethal_bot() -> MctsBot {
+        MctsBot::builder()
+            .with_config(MctsConfig {
+                iterations: 50,
+                determinizations: 2,
+                // Only the attack this turn wins; the bot would win any later turn anyway.
+                rollout_turns: 0,
+                ..Default::default()
+            })
+            .with_rng(SmallRng::seed_from_u64(0))
+            .build()
+    }
+
+    #[test]
+    fn test_finds_lethal_attack() {
+        let mut env = lethal_env();
+        let actions = env.advance(0, None).available_actions.unwrap();
+
+        let mut bot = lethal_bot();
+        let action = bot.compute_best_action(&BotView::new(&env, 0), &actions);
+        let Some(Action::Attack { attackers }) = action else {
+            panic!("expected an attack, got {:?}", action);
+        };
+        assert_eq!(attackers, vec![id(1), id(2)]);
+
+        let report = env.advance(0, Some(Action::Attack { attackers }));
+        assert_eq!(report.available_actions.unwrap().player, 1);
+        env.advance(1, Some(Action::Block { pairs: vec![] }));
+        assert!(matches!(
+            env.game_condition(),
+            EndgameState::Finished {
+                winner: Some(0),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_trace_is_searched_game() {
+        let mut env = lethal_env();
+        let actions = env.advance(0, None).available_actions.unwrap();
+        let view = BotView::new(&env, 0);
+
+        // Both bots search the same determinizations, so the trace keeps the visit counts.
+        let mut visits = lethal_bot()
+            .compute(&view, &actions)
+            .into_iter()
+            .map(|(_, score)| score.action)
+            .collect::<Vec<_>>();
+        visits.sort_by(|a, b| b.cmp(a));
+        let trace = lethal_bot().compute_trace(&view, &actions);
+        assert_eq!(
+            trace
+                .candidates
+                .iter()
+                .map(|candidate| candidate.action_score)
+                .collect::<Vec<_>>(),
+            visits
+        );
+        assert!(matches!(
+            trace.candidates[0].outcome.endgame,
+            Some(EndgameState::Finished {
+                winner: Some(0),
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,61 @@
+use kodecks::profile::BotDifficulty;
+
+/// The search parameters behind a difficulty level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyPreset {
+    /// The number of turns simulated after the current one when evaluating an action.
+    pub depth: u16,
+    /// The probability of playing a random candidate instead of the best one.
+    pub blunder_rate: f64,
+    /// The maximum random offset added to each evaluated score.
+    pub noise: i32,
+}
+
+impl Default for DifficultyPreset {
+    fn default() -> Self {
+        BotDifficulty::default().into()
+    }
+}
+
+impl From<BotDifficulty> for DifficultyPreset {
+    fn from(difficulty: BotDifficulty) -> Self {
+        match difficulty {
+            BotDifficulty::Easy => Self {
+                depth: 0,
+                blunder_rate: 0.25,
+                noise: 10,
+            },
+            BotDifficulty::Normal => Self {
+                depth: 1,
+                blunder_rate: 0.0,
+                noise: 0,
+            },
+            BotDifficulty::Hard => Self {
+                depth: 2,
+                blunder_rate: 0.0,
+                noise: 0,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normal_keeps_one_turn_search() {
+        assert_eq!(
+            DifficultyPreset::from(BotDifficulty::Normal),
+            DifficultyPreset {
+                depth: 1,
+                blunder_rate: 0.0,
+                noise: 0,
+            }
+        );
+        assert_eq!(
+            DifficultyPreset::default(),
+            DifficultyPreset::from(BotDifficulty::Normal)
+        );
+    }
+}
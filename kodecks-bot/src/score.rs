@@ -1,8 +1,10 @@
 use kodecks::{
-    env::{Environment, EndgameState},
+    env::{EndgameState, Environment},
     score::Score,
 };
 
+pub use kodecks::profile::EvalWeights;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct ComputedScore {
     pub base: i32,
@@ -35,38 +37,44 @@ impl Ord for ComputedScore {
     }
 }
 
-pub fn get_score(env: &Environment, side: u8) -> i32 {
+pub fn get_score(env: &Environment, side: u8, weights: &EvalWeights) -> i32 {
     let state = &env.state;
     let player = state.players().get(side);
     let opponent = state.players().next_player(side);
-    let mut score = 0i32;
-
-    score += player.stats.life as i32 / 100;
-    score -= opponent.stats.life as i32 / 100 * 2;
-
-    score += player.shards.len() as i32;
-    score -= opponent.shards.len() as i32;
-
-    score += player
-        .hand
-        .items()
-        .map(|item| item.card.score())
-        .sum::<i32>()
-        / 2;
-    score += player.field.items().map(|item| item.score()).sum::<i32>();
-    score += player.abilities.score();
-
-    score -= opponent
-        .hand
-        .items()
-        .map(|item| item.card.score())
-        .sum::<i32>()
-        / 2;
-    score -= opponent.field.items().map(|item| item.score()).sum::<i32>();
-    score -= opponent.abilities.score();
-
-    if (player.stats.life as f32) < (state.regulation.initial_life as f32 * 0.2) {
-        score -= 100;
+    // Each term is truncated, so the default weights give the same scores as integer weights.
+    let term = |value: i32, weight: f32| (value as f32 * weight) as i32;
+    let mut score = 0;
+
+    score += term(player.stats.life as i32 / 100, weights.life);
+    score -= term(opponent.stats.life as i32 / 100, weights.opponent_life);
+
+    score += term(player.shards.len() as i32, weights.shards);
+    score -= term(opponent.shards.len() as i32, weights.opponent_shards);
+
+    score += term(
+        player.hand.items().map(|item| item.card.score()).sum(),
+        weights.hand,
+    );
+    score += term(
+        player.field.items().map(|item| item.score()).sum(),
+        weights.field,
+    );
+    score += term(player.abilities.score(), weights.abilities);
+
+    score -= term(
+        opponent.hand.items().map(|item| item.card.score()).sum(),
+        weights.opponent_hand,
+    );
+    score -= term(
+        opponent.field.items().map(|item| item.score()).sum(),
+        weights.opponent_field,
+    );
+    score -= term(opponent.abilities.score(), weights.opponent_abilities);
+
+    if (player.stats.life as f32)
+        < (state.regulation.initial_life as f32 * weights.low_life_threshold)
+    {
+        score -= term(1, weights.low_life_penalty);
     }
 
     score += match env.game_condition() {
@@ -86,3 +94,99 @@ pub fn get_score(env: &Environment, side: u8) -> i32 {
 
     score
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleBot;
+    use kodecks::{player::PlayerConfig, profile::GameProfile};
+    use kodecks_catalog::{decks, CATALOG};
+    use std::sync::Arc;
+
+    /// The score before the weights were introduced.
+    fn integer_score(env: &Environment, side: u8) -> i32 {
+        let state = &env.state;
+        let player = state.players().get(side);
+        let opponent = state.players().next_player(side);
+        let mut score = 0i32;
+
+        score += player.stats.life as i32 / 100;
+        score -= opponent.stats.life as i32 / 100 * 2;
+
+        score += player.shards.len() as i32;
+        score -= opponent.shards.len() as i32;
+
+        score += player
+            .hand
+            .items()
+            .map(|item| item.card.score())
+            .sum::<i32>()
+            / 2;
+        score += player.field.items().map(|item| item.score()).sum::<i32>();
+        score += player.abilities.score();
+
+        score -= opponent
+            .hand
+            .items()
+            .map(|item| item.card.score())
+            .sum::<i32>()
+            / 2;
+        score -= opponent.field.items().map(|item| item.score()).sum::<i32>();
+        score -= opponent.abilities.score();
+
+        if (player.stats.life as f32) < (state.regulation.initial_life as f32 * 0.2) {
+            score -= 100;
+        }
+
+        score += match env.game_condition() {
+            EndgameState::Finished {
+                winner: Some(player),
+                ..
+            } => {
+                if player == side {
+                    1000
+                } else {
+                    -1000
+                }
+            }
+            EndgameState::Finished { winner: None, .. } => -500,
+            _ => 0,
+        };
+
+        score
+    }
+
+    #[test]
+    fn test_default_weights_keep_integer_scores() {
+        let profile = GameProfile {
+            players: vec![
+                PlayerConfig {
+                    deck: decks::red_deck(&CATALOG),
+                },
+                PlayerConfig {
+                    deck: decks::blue_deck(&CATALOG),
+                },
+            ],
+            rng_seed: Some(0),
+            ..Default::default()
+        };
+        let mut env = Arc::new(Environment::new(profile, CATALOG.clone()));
+        let weights = EvalWeights::default();
+        let mut player = 0;
+        let mut action = None;
+        for _ in 0..1000 {
+            let report = Arc::make_mut(&mut env).process(player, action.take());
+            for side in 0..2 {
+                assert_eq!(get_score(&env, side, &weights), integer_score(&env, side));
+            }
+            if report.endgame.is_ended() {
+                break;
+            }
+            if let Some(actions) = report.available_actions {
+                player = actions.player;
+                action = SimpleBot::best_action(&env, &actions);
+            }
+        }
+        assert!(env.game_condition().is_ended());
+    }
+}
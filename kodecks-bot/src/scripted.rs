@@ -0,0 +1,224 @@
+use crate::{score::ComputedScore, Bot, BotView};
+use kodecks::{
+    action::{Action, PlayerAvailableActions},
+    env::LocalEnvironment,
+    field::FieldBattleState,
+};
+use std::collections::VecDeque;
+
+/// A bot that follows a script.
+///
+/// Each action in the script is taken at most once, as soon as it becomes available.
+/// When no scripted action is available, the bot takes the default action.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedBot {
+    script: VecDeque<Action>,
+}
+
+impl ScriptedBot {
+    pub fn new<I>(script: I) -> Self
+    where
+        I: IntoIterator<Item = Action>,
+    {
+        Self {
+            script: script.into_iter().collect(),
+        }
+    }
+}
+
+impl Bot for ScriptedBot {
+    fn compute(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+    ) -> Vec<(Action, ComputedScore)> {
+        let scripted = self
+            .script
+            .iter()
+            .position(|action| is_available(&view.local, actions, action))
+            .and_then(|index| self.script.remove(index));
+        scripted
+            .or_else(|| actions.actions.default_action(&view.local))
+            .map(|action| (action, ComputedScore::default()))
+            .into_iter()
+            .collect()
+    }
+}
+
+fn is_available(env: &LocalEnvironment, actions: &PlayerAvailableActions, action: &Action) -> bool {
+    if !actions.actions.validate(action) {
+        return false;
+    }
+    match action {
+        Action::Block { pairs } => {
+            let opponent = env.next_player(actions.player);
+            pairs.iter().all(|(attacker, _)| {
+                opponent.field.iter().any(|item| {
+                    item.card.id == *attacker && item.battle == Some(FieldBattleState::Attacking)
+                })
+            })
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::{
+        action::{AvailableAction, AvailableActionList},
+        env::{EndgameState, Environment},
+        id::ObjectId,
+        scenario::{PuzzleScenario, Scenario, TutorialScenario},
+    };
+    use kodecks_catalog::{puzzles::find_puzzle, tutorials::find_tutorial, CATALOG};
+    use std::{iter, sync::Arc};
+
+    fn id(id: u32) -> ObjectId {
+        ObjectId::try_from(id).unwrap()
+    }
+
+    #[test]
+    fn test_all_out_attack_is_solvable() {
+        let puzzle = find_puzzle("all-out-attack").unwrap();
+        let mut env = Arc::new(Environment::from_puzzle(puzzle, CATALOG.clone()));
+        let scenario = PuzzleScenario::new(puzzle.clone());
+        let mut bots = [
+            ScriptedBot::new([
+                Action::CastCard { card: id(3) },
+                Action::CastCard { card: id(4) },
+                Action::Attack {
+                    attackers: vec![id(1), id(2), id(3), id(4)],
+                },
+            ]),
+            ScriptedBot::new(puzzle.script.clone()),
+        ];
+
+        let mut player = env.state.players.player_in_turn().id;
+        let mut available_actions = None;
+        while !env.game_condition().is_ended() {
+            let action = available_actions.as_ref().and_then(|actions| {
+                bots[player as usize].compute_best_action(&BotView::new(&env, player), actions)
+            });
+            let env = Arc::make_mut(&mut env);
+            let report = env.process(player, action);
+            for (player, state) in scenario.endgame(env) {
+                env.state
+                    .players
+                    .get_mut(player)
+                    .endgame
+                    .get_or_insert(state);
+            }
+            available_actions = report.available_actions;
+            if let Some(actions) = &available_actions {
+                player = actions.player;
+            }
+        }
+
+        assert_eq!(env.state.turn, 1);
+        assert!(matches!(
+            env.game_condition(),
+            EndgameState::Finished {
+                winner: Some(PuzzleScenario::SOLVER),
+                ..
+            }
+        ));
+    }
+
+    /// Returns concrete actions covering every entry of the list.
+    ///
+    /// Attacks and blocks are taken with all the available creatures at once,
+    /// and also one by one.
+    fn expand(env: &Environment, actions: &AvailableActionList) -> Vec<Action> {
+        let attacking = env
+            .state
+            .players
+            .iter()
+            .flat_map(|player| player.field.attacking_cards())
+            .map(|card| card.id())
+            .collect::<Vec<_>>();
+        actions
+            .iter()
+            .flat_map(|action| match action {
+                AvailableAction::SelectCard { cards, .. } => cards
+                    .iter()
+                    .map(|&card| Action::SelectCard { card })
+                    .collect(),
+                AvailableAction::Attack { attackers } => iter::once(attackers.clone())
+                    .chain(attackers.iter().map(|&attacker| vec![attacker]))
+                    .map(|attackers| Action::Attack { attackers })
+                    .collect(),
+                AvailableAction::Block { blockers } => {
+                    let pairs = attacking.iter().copied().zip(blockers.iter().copied());
+                    iter::once(pairs.clone().collect())
+                        .chain(pairs.map(|pair| vec![pair]))
+                        .map(|pairs| Action::Block { pairs })
+                        .collect()
+                }
+                AvailableAction::CastCard { cards } => cards
+                    .iter()
+                    .map(|&card| Action::CastCard { card })
+                    .collect(),
+                AvailableAction::EndTurn => vec![Action::EndTurn],
+                AvailableAction::Continue => vec![Action::Continue],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tutorial_rejects_off_script_actions() {
+        let tutorial = find_tutorial("basics").unwrap();
+        let mut env = Arc::new(Environment::new(tutorial.profile(), CATALOG.clone()));
+        let mut scenario = TutorialScenario::new(tutorial.clone());
+        let mut opponent = ScriptedBot::new(tutorial.script.clone());
+
+        let mut steps = 0;
+        let mut rejected = 0;
+        let mut player = env.state.players.player_in_turn().id;
+        let mut available_actions: Option<PlayerAvailableActions> = None;
+        while steps < tutorial.steps.len() {
+            assert!(!env.game_condition().is_ended());
+            let action = match &available_actions {
+                Some(actions) if actions.player == TutorialScenario::LEARNER => {
+                    let allowed = actions
+                        .message_dialog
+                        .as_ref()
+                        .map(|dialog| dialog.allowed_action.clone());
+                    if allowed.is_some() {
+                        steps += 1;
+                    }
+                    if let Some(Some(allowed)) = allowed {
+                        let allowed = iter::once(allowed).collect::<AvailableActionList>();
+                        let unrestricted = env.last_available_actions().unwrap();
+                        for action in expand(&env, &unrestricted.actions) {
+                            let on_script = allowed.validate(&action);
+                            assert_eq!(
+                                actions.validate(TutorialScenario::LEARNER, &action),
+                                on_script,
+                                "step {steps}: {action:?}"
+                            );
+                            if !on_script {
+                                rejected += 1;
+                            }
+                        }
+                        expand(&env, &allowed).into_iter().next()
+                    } else {
+                        actions.actions.default_action(&env.local(player))
+                    }
+                }
+                Some(actions) => opponent.compute_best_action(&BotView::new(&env, player), actions),
+                None => None,
+            };
+            let env = Arc::make_mut(&mut env);
+            let report = env.process(player, action.clone());
+            if let Some(action) = &action {
+                scenario.on_action(player, action);
+            }
+            available_actions = scenario.override_actions(env, report.available_actions);
+            if let Some(actions) = &available_actions {
+                player = actions.player;
+            }
+        }
+        assert!(rejected > 0);
+    }
+}
@@ -1,7 +1,7 @@
 use super::BotContext;
 use crate::{
     score::{get_score, ComputedScore},
-    Bot, SimpleBot,
+    SimpleBot,
 };
 use kodecks::{action::Action, id::ObjectId, phase::Phase};
 use std::sync::Arc;
@@ -21,6 +21,7 @@ pub fn find_select_combination(
     let scored_combinations = cards.into_iter();
 
     scored_combinations
+        .filter(|_| ctx.budget.consume())
         .map(|card| {
             (
                 card,
@@ -32,7 +33,7 @@ pub fn find_select_combination(
 }
 
 fn evaluate_select(mut ctx: BotContext, action: Option<Action>) -> ComputedScore {
-    let initial_score = get_score(&ctx.env, ctx.player);
+    let initial_score = get_score(&ctx.env, ctx.player, &ctx.weights);
 
     let env = Arc::make_mut(&mut ctx.env);
     let mut next_action = action;
@@ -40,25 +41,25 @@ fn evaluate_select(mut ctx: BotContext, action: Option<Action>) -> ComputedScore
 
     if next_action.is_none() {
         if let Some(actions) = env.last_available_actions() {
-            next_action = actions.actions.default_action(env);
+            next_action = actions.actions.default_action(&env.local(actions.player));
             player = actions.player;
         }
     }
 
     let initial_turn = env.state.turn;
-    while !(matches!(env.state.phase, Phase::End) && env.state.turn > initial_turn) {
+    while !(matches!(env.state.phase, Phase::End) && env.state.turn >= initial_turn + ctx.depth) {
         let report = env.process(player, next_action.take());
         if report.endgame.is_ended() {
             break;
         }
         if let Some(available_actions) = &report.available_actions {
             player = available_actions.player;
-            next_action = SimpleBot.compute_best_action(Arc::new(env.clone()), available_actions);
+            next_action = SimpleBot::best_action(env, available_actions);
         }
     }
 
     ComputedScore {
         base: initial_score,
-        action: get_score(env, ctx.player),
+        action: get_score(env, ctx.player, &ctx.weights),
     }
 }
@@ -0,0 +1,46 @@
+use crate::{Bot, BotView};
+use kodecks::env::{EndgameState, Environment};
+use std::sync::Arc;
+
+/// The outcome of a game between bots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameResult {
+    /// `None` for a draw, including games stopped at the turn limit.
+    pub winner: Option<u8>,
+    pub turns: u16,
+}
+
+/// Plays a game in which every player is controlled by the bot of the same index.
+pub fn play_game(env: Environment, bots: &mut [&mut dyn Bot], max_turns: u16) -> GameResult {
+    let mut env = Arc::new(env);
+    let mut player = 0;
+    let mut action = None;
+    loop {
+        let report = Arc::make_mut(&mut env).process(player, action.take());
+        for (id, bot) in bots.iter_mut().enumerate() {
+            let logs = report
+                .logs
+                .iter()
+                .map(|log| log.clone().redacted(id as u8))
+                .collect::<Vec<_>>();
+            bot.observe(&logs);
+        }
+        if let EndgameState::Finished { winner, .. } = report.endgame {
+            return GameResult {
+                winner,
+                turns: env.state.turn,
+            };
+        }
+        if env.state.turn > max_turns {
+            return GameResult {
+                winner: None,
+                turns: env.state.turn,
+            };
+        }
+        if let Some(actions) = report.available_actions {
+            player = actions.player;
+            let view = BotView::new(&env, player);
+            action = bots[player as usize].compute_best_action(&view, &actions);
+        }
+    }
+}
@@ -1,20 +1,41 @@
-use crate::{score::ComputedScore, Bot};
+use crate::{score::ComputedScore, Bot, BotView};
 use kodecks::{
     action::{Action, AvailableAction, PlayerAvailableActions},
-    env::Environment,
+    card::CardSnapshot,
+    env::{Environment, LocalEnvironment},
+    field::{FieldBattleState, FieldState},
     score::Score,
 };
-use std::sync::Arc;
 use tracing::debug;
 
 pub struct SimpleBot;
 
-impl Bot for SimpleBot {
-    fn compute(
-        &mut self,
-        env: Arc<Environment>,
+impl SimpleBot {
+    /// Chooses the action from what the player can see.
+    pub(crate) fn choose(
+        env: &LocalEnvironment,
+        actions: &PlayerAvailableActions,
+    ) -> Option<Action> {
+        Self::candidates(env, actions)
+            .into_iter()
+            .max_by_key(|(_, score)| *score)
+            .map(|(action, _)| action)
+    }
+
+    /// Chooses the action of a player in a simulated game.
+    pub(crate) fn best_action(
+        env: &Environment,
+        actions: &PlayerAvailableActions,
+    ) -> Option<Action> {
+        Self::choose(&env.local(actions.player), actions)
+    }
+
+    fn candidates(
+        env: &LocalEnvironment,
         actions: &PlayerAvailableActions,
     ) -> Vec<(Action, ComputedScore)> {
+        let power =
+            |card: &CardSnapshot| card.power().map(|power| power.value()).unwrap_or_default();
         for action in actions.actions.as_ref() {
             if let AvailableAction::SelectCard {
                 cards,
@@ -23,19 +44,15 @@ impl Bot for SimpleBot {
             {
                 let best_candidate = cards
                     .iter()
-                    .filter_map(|id| env.state.find_card(*id).ok())
-                    .map(|card| {
+                    .filter_map(|id| Some((env.find_card(*id).ok()?, env.find_zone(*id).ok()?)))
+                    .map(|(card, zone)| {
                         (
-                            card.id(),
+                            card.id,
                             ComputedScore {
                                 base: 0,
                                 action: card.score()
                                     * score_factor
-                                    * if card.zone().player == actions.player {
-                                        1
-                                    } else {
-                                        -1
-                                    },
+                                    * if zone.player == actions.player { 1 } else { -1 },
                             },
                         )
                     })
@@ -46,13 +63,14 @@ impl Bot for SimpleBot {
             }
 
             if let AvailableAction::Attack { attackers } = action {
-                let player = env.state.players().get(actions.player);
-                let opponent = env.state.players().next_player(actions.player);
+                let player = env.players.get(actions.player);
+                let opponent = env.next_player(actions.player);
 
                 let blockers = opponent
                     .field
-                    .active_cards()
-                    .filter_map(|card| card.computed().power)
+                    .iter()
+                    .filter(|item| item.state == FieldState::Active)
+                    .filter_map(|item| item.card.power())
                     .map(|power| power.value())
                     .collect::<Vec<_>>();
                 let blocker_power_sum = blockers.iter().sum::<u32>();
@@ -65,16 +83,9 @@ impl Bot for SimpleBot {
                 }
                 let attackers = attackers
                     .iter()
-                    .filter_map(|id| env.state.find_card(*id).ok())
-                    .filter(|card| {
-                        let power = card
-                            .computed()
-                            .power
-                            .map(|power| power.value())
-                            .unwrap_or_default();
-                        power > 0 && power > max_blocker_power
-                    })
-                    .map(|card| card.id())
+                    .filter_map(|id| env.find_card(*id).ok())
+                    .filter(|card| power(card) > 0 && power(card) > max_blocker_power)
+                    .map(|card| card.id)
                     .collect::<Vec<_>>();
                 if !attackers.is_empty() {
                     return vec![(Action::Attack { attackers }, ComputedScore::default())];
@@ -82,42 +93,25 @@ impl Bot for SimpleBot {
             }
 
             if let AvailableAction::Block { blockers } = action {
-                let player = env.state.players().get(actions.player);
-                let opponent = env.state.players().next_player(actions.player);
+                let player = env.players.get(actions.player);
+                let opponent = env.next_player(actions.player);
                 let mut blockers = blockers
                     .iter()
-                    .filter_map(|id| env.state.find_card(*id).ok())
+                    .filter_map(|id| env.find_card(*id).ok())
+                    .collect::<Vec<_>>();
+                blockers.sort_by_key(|card| power(card) as i32);
+                let mut attackers = opponent
+                    .field
+                    .iter()
+                    .filter(|item| item.battle == Some(FieldBattleState::Attacking))
+                    .map(|item| &item.card)
                     .collect::<Vec<_>>();
-                blockers.sort_by_key(|card| {
-                    card.computed()
-                        .power
-                        .map(|power| power.value())
-                        .unwrap_or_default() as i32
-                });
-                let mut attackers = opponent.field.attacking_cards().collect::<Vec<_>>();
-                attackers.sort_by_key(|card| {
-                    card.computed()
-                        .power
-                        .map(|power| power.value())
-                        .unwrap_or_default() as i32
-                });
+                attackers.sort_by_key(|card| power(card) as i32);
                 let mut pairs = vec![];
                 while !attackers.is_empty() && !blockers.is_empty() {
-                    let attackers_power_sum = attackers
-                        .iter()
-                        .map(|card| {
-                            card.computed()
-                                .power
-                                .map(|power| power.value())
-                                .unwrap_or_default()
-                        })
-                        .sum::<u32>();
+                    let attackers_power_sum = attackers.iter().map(|card| power(card)).sum::<u32>();
                     let attacker = attackers.pop().unwrap();
-                    let attacker_power = attacker
-                        .computed()
-                        .power
-                        .map(|power| power.value())
-                        .unwrap_or_default();
+                    let attacker_power = power(attacker);
 
                     debug!(
                         "attacker_power_sum: {} {}",
@@ -126,17 +120,12 @@ impl Bot for SimpleBot {
 
                     if attacker_power > 0 {
                         let blocker = blockers.iter().position(|blocker| {
-                            blocker
-                                .computed()
-                                .power
-                                .map(|power| power.value())
-                                .unwrap_or_default()
-                                > attacker_power
+                            power(blocker) > attacker_power
                                 || attackers_power_sum >= player.stats.life
                         });
                         if let Some(index) = blocker {
                             let blocker = blockers.remove(index);
-                            pairs.push((attacker.id(), blocker.id()));
+                            pairs.push((attacker.id, blocker.id));
                         }
                     }
                 }
@@ -146,10 +135,10 @@ impl Bot for SimpleBot {
             if let AvailableAction::CastCard { cards } = action {
                 let best_candidate = cards
                     .iter()
-                    .filter_map(|id| env.state.find_card(*id).ok())
+                    .filter_map(|id| env.find_card(*id).ok())
                     .map(|card| {
                         (
-                            card.id(),
+                            card.id,
                             ComputedScore {
                                 base: 0,
                                 action: card.score(),
@@ -165,9 +154,19 @@ impl Bot for SimpleBot {
 
         actions
             .actions
-            .default_action(&env)
+            .default_action(env)
             .map(|action| (action, ComputedScore::default()))
             .into_iter()
             .collect()
     }
 }
+
+impl Bot for SimpleBot {
+    fn compute(
+        &mut self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+    ) -> Vec<(Action, ComputedScore)> {
+        Self::candidates(&view.local, actions)
+    }
+}
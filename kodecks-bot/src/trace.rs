@@ -0,0 +1,221 @@
+use crate::{score::ComputedScore, SimpleBot};
+use bincode::{Decode, Encode};
+use kodecks::{
+    action::Action,
+    env::{EndgameState, Environment},
+    id::ObjectId,
+    phase::Phase,
+    zone::CardZone,
+};
+use serde::{Deserialize, Serialize};
+
+/// Outcome simulations give up after this many steps, which only happens in a stalled game.
+const MAX_SIMULATION_STEPS: usize = 1000;
+
+/// Only the candidates with the highest scores are simulated, since bots may consider hundreds.
+const MAX_TRACED_CANDIDATES: usize = 16;
+
+/// The candidates a bot considered for a decision and the one it chose.
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct BotDecisionTrace {
+    pub player: u8,
+    pub turn: u16,
+    pub candidates: Vec<CandidateTrace>,
+    /// The candidate with the highest score, taken by the bot.
+    pub chosen: Option<Action>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct CandidateTrace {
+    pub action: Action,
+    /// The names of the cards the action involves.
+    pub cards: Vec<String>,
+    pub base_score: i32,
+    pub action_score: i32,
+    pub outcome: SimulatedOutcome,
+}
+
+impl CandidateTrace {
+    pub fn score(&self) -> i32 {
+        self.action_score - self.base_score
+    }
+}
+
+/// The state of the game after the action is played out to the end of the turn.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Encode, Decode)]
+pub struct SimulatedOutcome {
+    pub players: Vec<PlayerOutcome>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endgame: Option<EndgameState>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct PlayerOutcome {
+    pub id: u8,
+    pub life: u32,
+    pub hand: usize,
+    pub field: usize,
+}
+
+impl BotDecisionTrace {
+    /// Traces the candidates computed by a bot, simulating the best of them in the environment.
+    ///
+    /// The environment should be the one the bot searched, so that the outcomes
+    /// do not depend on cards hidden from it.
+    /// At most [`MAX_TRACED_CANDIDATES`] candidates are kept, from the highest score.
+    pub fn new(
+        env: &Environment,
+        player: u8,
+        mut candidates: Vec<(Action, ComputedScore)>,
+    ) -> Self {
+        let chosen = candidates
+            .iter()
+            .max_by_key(|(_, score)| *score)
+            .map(|(action, _)| action.clone());
+        candidates.sort_by(|(_, a), (_, b)| b.cmp(a));
+        candidates.truncate(MAX_TRACED_CANDIDATES);
+        let candidates = candidates
+            .into_iter()
+            .map(|(action, score)| CandidateTrace {
+                cards: action_cards(&action)
+                    .into_iter()
+                    .filter_map(|id| env.state.find_card(id).ok())
+                    .map(|card| card.archetype().name.clone())
+                    .collect(),
+                base_score: score.base,
+                action_score: score.action,
+                outcome: simulate(env, player, action.clone()),
+                action,
+            })
+            .collect();
+        Self {
+            player,
+            turn: env.state.turn,
+            candidates,
+            chosen,
+        }
+    }
+}
+
+fn action_cards(action: &Action) -> Vec<ObjectId> {
+    match action {
+        Action::CastCard { card } | Action::SelectCard { card } => vec![*card],
+        Action::Attack { attackers } => attackers.clone(),
+        Action::Block { pairs } => pairs
+            .iter()
+            .flat_map(|(attacker, blocker)| [*attacker, *blocker])
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Plays the action, then lets [`SimpleBot`] play both sides until the turn ends.
+fn simulate(env: &Environment, player: u8, action: Action) -> SimulatedOutcome {
+    let mut env = env.clone();
+    let initial_turn = env.state.turn;
+    let mut current_player = player;
+    let mut next_action = Some(action);
+    for _ in 0..MAX_SIMULATION_STEPS {
+        let report = env.process(current_player, next_action.take());
+        if report.endgame.is_ended()
+            || (matches!(env.state.phase, Phase::End) && env.state.turn >= initial_turn)
+        {
+            break;
+        }
+        if let Some(actions) = &report.available_actions {
+            current_player = actions.player;
+            next_action = SimpleBot::best_action(&env, actions);
+        }
+    }
+
+    let endgame = env.game_condition();
+    SimulatedOutcome {
+        players: env
+            .state
+            .players
+            .iter()
+            .map(|player| PlayerOutcome {
+                id: player.id,
+                life: player.stats.life,
+                hand: player.hand.len(),
+                field: player.field.len(),
+            })
+            .collect(),
+        endgame: endgame.is_ended().then_some(endgame),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bot, BotView, DefaultBot, MctsBot, MctsConfig};
+    use kodecks::{action::PlayerAvailableActions, field::FieldState, profile::BotDifficulty};
+    use kodecks_catalog::CATALOG;
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    fn decision() -> (Environment, PlayerAvailableActions) {
+        let mut env = Environment::builder(CATALOG.clone())
+            .with_player(0, |player| {
+                player
+                    .with_field(1, "halo", FieldState::Active)
+                    .with_field(2, "ruby", FieldState::Active)
+                    .with_hand(3, "wind")
+                    .with_hand(4, "wind")
+                    .with_deck(5, "wind")
+            })
+            .with_player(1, |player| {
+                player
+                    .with_field(6, "ruby", FieldState::Active)
+                    .with_deck(7, "wind")
+            })
+            .build();
+        let actions = env.advance(0, None).available_actions.unwrap();
+        (env, actions)
+    }
+
+    #[test]
+    fn test_chosen_is_best_action() {
+        let (env, actions) = decision();
+        let view = BotView::new(&env, 0);
+        for seed in 0..8 {
+            for difficulty in [BotDifficulty::Easy, BotDifficulty::Normal] {
+                let bot = DefaultBot::builder()
+                    .with_difficulty(difficulty)
+                    .with_rng(SmallRng::seed_from_u64(seed))
+                    .build();
+                let trace = bot.clone().compute_trace(&view, &actions);
+                let best = bot.clone().compute_best_action(&view, &actions);
+                assert_eq!(format!("{:?}", trace.chosen), format!("{:?}", best));
+            }
+
+            let bot = MctsBot::builder()
+                .with_config(MctsConfig {
+                    iterations: 10,
+                    determinizations: 1,
+                    ..Default::default()
+                })
+                .with_rng(SmallRng::seed_from_u64(seed))
+                .build();
+            let trace = bot.clone().compute_trace(&view, &actions);
+            let best = bot.clone().compute_best_action(&view, &actions);
+            assert_eq!(format!("{:?}", trace.chosen), format!("{:?}", best));
+        }
+    }
+
+    #[test]
+    fn test_traced_candidates_are_capped() {
+        let (env, _) = decision();
+        let candidates = (0..100)
+            .map(|score| {
+                let score = ComputedScore {
+                    base: 0,
+                    action: score,
+                };
+                (Action::EndTurn, score)
+            })
+            .collect();
+        let trace = BotDecisionTrace::new(&env, 0, candidates);
+        assert_eq!(trace.candidates.len(), MAX_TRACED_CANDIDATES);
+        assert_eq!(trace.candidates[0].score(), 99);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::BotView;
+use kodecks::{
+    action::PlayerAvailableActions,
+    archetype::ArchetypeId,
+    card::CardSnapshot,
+    catalog::Catalog,
+    color::Color,
+    env::{Environment, HiddenCards, LocalEnvironment},
+    id::ObjectId,
+    log::GameLog,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::collections::{BTreeMap, HashMap};
+
+/// Keeps track of the cards a bot has seen, so that it never reads hidden information.
+///
+/// The tracker learns only from the state visible to the bot and from the logs redacted for it.
+/// The cards that have not been seen yet are estimated from the number of cards
+/// in each deck, favoring the colors the player has shown.
+#[derive(Debug, Clone, Default)]
+pub struct CardTracker {
+    /// The owners and archetypes of the cards seen so far, ordered to keep the samples reproducible.
+    seen: BTreeMap<ObjectId, (u8, ArchetypeId)>,
+}
+
+impl CardTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the cards visible in the state.
+    pub fn observe(&mut self, env: &LocalEnvironment) {
+        for player in env.players.iter() {
+            let cards = player
+                .hand
+                .iter()
+                .map(|item| &item.card)
+                .chain(&player.graveyard)
+                .chain(player.field.iter().map(|item| &item.card));
+            for card in cards {
+                self.record(card);
+            }
+        }
+    }
+
+    /// Records the cards appearing in the logs.
+    pub fn observe_logs(&mut self, logs: &[GameLog]) {
+        for log in logs {
+            match log {
+                GameLog::AttackDeclared { attacker } => self.record(attacker),
+                GameLog::CreatureAttackedCreature { attacker, blocker } => {
+                    self.record(attacker);
+                    self.record(blocker);
+                }
+                GameLog::CreatureAttackedPlayer { attacker, .. } => self.record(attacker),
+                GameLog::ShardsEarned { source, .. } | GameLog::ShardsSpent { source, .. } => {
+                    self.record(source)
+                }
+                GameLog::CardMoved { card, .. } | GameLog::ShieldBroken { card } => {
+                    self.record(card)
+                }
+                GameLog::EffectActivated { source, .. } => self.record(source),
+                GameLog::CardTargeted { source, target } => {
+                    self.record(source);
+                    self.record(target);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the archetypes of the cards of the player seen so far.
+    pub fn known_cards(&self, player: u8) -> impl Iterator<Item = ArchetypeId> + '_ {
+        self.seen
+            .values()
+            .filter(move |(owner, _)| *owner == player)
+            .map(|(_, archetype)| *archetype)
+    }
+
+    /// Guesses the cards the player keeps hidden from the viewer of the state.
+    ///
+    /// Hand cards seen before keep their identity, the other seen cards not visible now
+    /// are put in the deck, and the rest are sampled from the catalog.
+    pub fn guess_hidden(
+        &self,
+        env: &LocalEnvironment,
+        player: u8,
+        catalog: &Catalog,
+        max_same_cards: u8,
+        rng: &mut impl Rng,
+    ) -> HiddenCards {
+        let state = env.players.get(player);
+        let visible = env
+            .players
+            .iter()
+            .flat_map(|player| player.cards())
+            .filter(|card| !card.archetype_id.is_empty())
+            .map(|card| card.id)
+            .collect::<Vec<_>>();
+
+        let mut hand = Vec::new();
+        let mut unknown_hand = Vec::new();
+        for item in &state.hand {
+            let card = &item.card;
+            if !card.archetype_id.is_empty() {
+                continue;
+            }
+            match self.seen.get(&card.id) {
+                Some((_, archetype)) => hand.push((card.id, *archetype)),
+                None => unknown_hand.push(card.id),
+            }
+        }
+
+        let mut deck = self
+            .seen
+            .iter()
+            .filter(|(&id, &(owner, _))| {
+                owner == player && !visible.contains(&id) && !hand.iter().any(|(h, _)| *h == id)
+            })
+            .map(|(_, (_, archetype))| *archetype)
+            .collect::<Vec<_>>();
+        deck.truncate(state.deck);
+
+        let unknown = unknown_hand.len() + state.deck - deck.len();
+        let mut guessed = self.sample_unknown(player, unknown, catalog, max_same_cards, rng);
+        for id in unknown_hand {
+            if let Some(archetype) = guessed.pop() {
+                hand.push((id, archetype));
+            }
+        }
+        deck.extend(guessed);
+        deck.shuffle(rng);
+        HiddenCards { hand, deck }
+    }
+
+    /// Returns a game built from the view, in which every card hidden from the viewer is guessed.
+    ///
+    /// The view is all that is read, so the game tells the viewer nothing they could not know.
+    /// The order of the viewer's own deck is hidden too, so it is guessed like the others.
+    pub fn sample(
+        &self,
+        view: &BotView,
+        actions: &PlayerAvailableActions,
+        rng: &mut impl Rng,
+    ) -> Environment {
+        let local = &view.local;
+        let max_same_cards = view.regulation.max_same_cards;
+        let hidden = local
+            .players
+            .iter()
+            .map(|player| {
+                let cards = self.guess_hidden(local, player.id, &view.catalog, max_same_cards, rng);
+                (player.id, cards)
+            })
+            .collect::<Vec<_>>();
+        Environment::from_local(
+            local,
+            view.catalog.clone(),
+            view.regulation.clone(),
+            &hidden,
+            Some(actions.clone()),
+            rng,
+        )
+    }
+
+    fn record(&mut self, card: &CardSnapshot) {
+        if !card.is_token && !card.archetype_id.is_empty() {
+            self.seen.insert(card.id, (card.owner, card.archetype_id));
+        }
+    }
+
+    /// Samples cards the player may have, never exceeding the copies allowed in a deck.
+    fn sample_unknown(
+        &self,
+        player: u8,
+        count: usize,
+        catalog: &Catalog,
+        max_same_cards: u8,
+        rng: &mut impl Rng,
+    ) -> Vec<ArchetypeId> {
+        let mut copies = HashMap::new();
+        let mut colors = Color::empty();
+        for archetype in self.known_cards(player) {
+            *copies.entry(archetype).or_insert(0) += 1;
+            colors |= catalog[archetype].attribute.color;
+        }
+
+        let mut pool = catalog
+            .iter()
+            .filter(|card| !card.attribute.is_token)
+            .filter(|card| colors.is_empty() || colors.contains(card.attribute.color))
+            .flat_map(|card| {
+                let used = copies.get(&card.id).copied().unwrap_or(0);
+                std::iter::repeat_n(card.id, max_same_cards.saturating_sub(used) as usize)
+            })
+            .collect::<Vec<_>>();
+        let all = catalog
+            .iter()
+            .filter(|card| !card.attribute.is_token)
+            .map(|card| card.id)
+            .collect::<Vec<_>>();
+        while pool.len() < count && !all.is_empty() {
+            pool.extend(&all);
+        }
+        pool.partial_shuffle(rng, count).0.to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::{field::FieldState, zone::CardZone};
+    use kodecks_catalog::CATALOG;
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    /// Samples the game in which the opponent keeps the given cards hidden.
+    fn sample_hidden_cards(hand: &str, deck: [&str; 2]) -> Vec<(u64, Vec<ArchetypeId>)> {
+        let mut env = Environment::builder(CATALOG.clone())
+            .with_player(0, |player| {
+                player
+                    .with_field(1, "halo", FieldState::Active)
+                    .with_hand(2, "ruby")
+                    .with_deck(3, "wind")
+            })
+            .with_player(1, |player| {
+                player
+                    .with_field(4, "ruby", FieldState::Active)
+                    .with_hand(5, hand)
+                    .with_deck(6, deck[0])
+                    .with_deck(7, deck[1])
+            })
+            .build();
+        let actions = env.advance(0, None).available_actions.unwrap();
+        let view = BotView::new(&env, 0);
+        let mut tracker = CardTracker::new();
+        tracker.observe(&view.local);
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        (0..8)
+            .map(|_| {
+                let sample = tracker.sample(&view, &actions, &mut rng);
+                let deck = sample
+                    .state
+                    .players()
+                    .get(1)
+                    .deck
+                    .iter()
+                    .map(|card| card.archetype().id)
+                    .collect();
+                (sample.local(1).checksum(), deck)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_samples_do_not_depend_on_hidden_cards() {
+        assert_eq!(
+            sample_hidden_cards("wind", ["airb", "lase"]),
+            sample_hidden_cards("quag", ["ceno", "scra"])
+        );
+    }
+}
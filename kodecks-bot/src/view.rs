@@ -0,0 +1,24 @@
+use kodecks::{
+    catalog::Catalog,
+    env::{Environment, LocalEnvironment},
+    regulation::Regulation,
+};
+use std::sync::Arc;
+
+/// What a bot is shown of the game: the state visible to its player and the rules of the game.
+#[derive(Debug, Clone)]
+pub struct BotView {
+    pub local: LocalEnvironment,
+    pub catalog: Arc<Catalog>,
+    pub regulation: Regulation,
+}
+
+impl BotView {
+    pub fn new(env: &Environment, player: u8) -> Self {
+        Self {
+            local: env.local(player),
+            catalog: env.catalog().clone(),
+            regulation: env.state.regulation.clone(),
+        }
+    }
+}
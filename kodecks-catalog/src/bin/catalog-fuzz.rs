@@ -0,0 +1,48 @@
+use bpaf::Bpaf;
+use kodecks_catalog::fuzz::{fuzz_game, FuzzConfig};
+use std::path::PathBuf;
+
+fn main() {
+    let opts = options().run();
+    let config = FuzzConfig {
+        max_turns: opts.max_turns,
+        ..Default::default()
+    };
+
+    let mut failures = 0;
+    for seed in opts.seed..opts.seed + opts.games {
+        if let Err(failure) = fuzz_game(seed, &config) {
+            failures += 1;
+            println!("{failure}");
+            match failure.save(&opts.out) {
+                Ok(path) => println!("replay saved to {}", path.display()),
+                Err(err) => eprintln!("failed to save replay: {err}"),
+            }
+        }
+    }
+
+    println!("{} games played, {} failed", opts.games, failures);
+    if failures > 0 {
+        std::process::exit(1);
+    }
+}
+
+#[derive(Debug, Clone, Bpaf)]
+#[bpaf(options)]
+pub struct Options {
+    #[bpaf(long, fallback(10000))]
+    /// Number of games to play
+    games: u64,
+
+    #[bpaf(long, fallback(0))]
+    /// Seed of the first game
+    seed: u64,
+
+    #[bpaf(long, fallback(200))]
+    /// Turn limit of a game
+    max_turns: u16,
+
+    #[bpaf(long, fallback(PathBuf::from("fuzz-replays")))]
+    /// Directory to save replays of failing games
+    out: PathBuf,
+}
@@ -1,6 +1,6 @@
 use bpaf::Bpaf;
 use kodecks::{card::safe_name, color::Color};
-use kodecks_catalog::CATALOG;
+use kodecks_catalog::{CATALOG, VANILLA_CREATURES};
 use std::io::Write;
 use std::{
     collections::{BTreeMap, HashSet},
@@ -14,6 +14,9 @@ fn main() {
         add_card(&name, opts.card_id);
     } else {
         show_stat();
+        if !check_tests() {
+            std::process::exit(1);
+        }
     }
 }
 
@@ -143,6 +146,36 @@ fn show_stat() {
     }
 }
 
+fn check_tests() -> bool {
+    let cards_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/cards");
+    let mut checked = HashSet::new();
+
+    let mut missing = CATALOG
+        .iter()
+        .filter(|card| checked.insert(card.id))
+        .filter(|card| !VANILLA_CREATURES.contains(&card.id.as_str()))
+        .map(|card| cards_dir.join(format!("{}.rs", card.safe_name.replace('-', "_"))))
+        .filter(|path| !has_test(&fs::read_to_string(path).unwrap_or_default()))
+        .collect::<Vec<_>>();
+    missing.sort();
+
+    if !missing.is_empty() {
+        println!("\nCards without tests");
+        println!("-------------------");
+        for path in &missing {
+            println!("{}", path.file_name().unwrap().to_string_lossy());
+        }
+    }
+    missing.is_empty()
+}
+
+/// Returns true if the card module has a test module with at least one test.
+fn has_test(source: &str) -> bool {
+    source
+        .split_once("#[cfg(test)]")
+        .is_some_and(|(_, tests)| tests.contains("#[test]"))
+}
+
 #[derive(Debug, Clone, Bpaf)]
 #[bpaf(options)]
 pub struct Options {
@@ -67,3 +67,38 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, field::FieldState, log::GameLog, player::PlayerZone};
+
+    #[test]
+    fn test_return_creature_to_hand() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| {
+                    player.with_hand(1, "airb").with_shards(Color::BLUE, 3)
+                })
+                .with_player(1, |player| {
+                    player.with_field(51, "host", FieldState::Active)
+                }),
+        );
+
+        let report = env.cast(0, 1);
+        assert!(selectable_cards(&report).contains(&id(51)));
+
+        let report = env.select(0, 51);
+        assert_log!(
+            report,
+            GameLog::CardMoved {
+                card,
+                to: PlayerZone { player: 1, zone: Zone::Hand },
+                ..
+            } if card.id == id(51)
+        );
+        assert_eq!(env.zone_of(51), Some(Zone::Hand));
+        assert_eq!(env.zone_of(1), Some(Zone::Field));
+    }
+}
@@ -14,3 +14,28 @@ card_def!(
 );
 
 impl Effect for CardDef {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, field::FieldState, log::GameLog};
+
+    #[test]
+    fn test_shield_prevents_destruction() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| player.with_field(1, "badl", FieldState::Active))
+                .with_player(1, |player| {
+                    player.with_field(51, "amet", FieldState::Active)
+                }),
+        );
+        assert_eq!(env.shields_of(51), 1);
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[(1, 51)]);
+        assert_log!(report, GameLog::ShieldBroken { card } if card.id == id(51));
+        assert_eq!(env.zone_of(51), Some(Zone::Field));
+        assert_eq!(env.shields_of(51), 0);
+    }
+}
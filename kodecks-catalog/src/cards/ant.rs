@@ -13,3 +13,49 @@ card_def!(
 );
 
 impl Effect for CardDef {}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::*;
+    use kodecks::{
+        action::Action, assert_log, assert_no_log, field::FieldState, log::GameLog, zone::CardZone,
+    };
+
+    #[test]
+    fn test_token_destroyed() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| {
+                    player
+                        .with_field(1, "badl", FieldState::Active)
+                        .with_field(2, "wind", FieldState::Active)
+                        .with_deck(3, "wind")
+                })
+                .with_player(1, |player| {
+                    player
+                        .with_field(51, "vora", FieldState::Active)
+                        .with_deck(52, "wind")
+                }),
+        );
+        env.attack(0, &[1]);
+        env.block(1, &[(1, 51)]);
+
+        let ant = env.state.players.get(1).field.iter().next().unwrap().id();
+        env.act(
+            1,
+            Action::Attack {
+                attackers: vec![ant],
+            },
+        );
+
+        let report = env.act(
+            0,
+            Action::Block {
+                pairs: vec![(ant, id(2))],
+            },
+        );
+        assert_log!(report, GameLog::CardTokenDestroyed { card } if card.id == ant);
+        assert_no_log!(report, GameLog::ShardsEarned { player: 1, .. });
+        assert!(env.state.find_card(ant).is_err());
+    }
+}
@@ -24,7 +24,7 @@ impl Effect for CardDef {
                 let power = ctx
                     .source()
                     .computed()
-                    .power
+                    .power()
                     .map_or(0, |power| power.value());
                 let commands = if power > 0 {
                     vec![ActionCommand::InflictDamage {
@@ -45,3 +45,35 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::*;
+    use kodecks::{assert_log, field::FieldState, log::GameLog};
+
+    #[test]
+    fn test_damage_to_controller_when_attacking() {
+        let mut env = start(
+            builder().with_player(0, |player| player.with_field(1, "bamb", FieldState::Active)),
+        );
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[]);
+        assert_log!(
+            report,
+            GameLog::DamageTaken {
+                player: 0,
+                amount: 300
+            }
+        );
+        assert_log!(
+            report,
+            GameLog::DamageTaken {
+                player: 1,
+                amount: 300
+            }
+        );
+        assert_eq!(env.life_of(0), 1700);
+        assert_eq!(env.life_of(1), 1700);
+    }
+}
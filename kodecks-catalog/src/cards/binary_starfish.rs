@@ -41,3 +41,27 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, log::GameLog};
+
+    #[test]
+    fn test_clone_when_casted_from_hand() {
+        let mut env = start(builder().with_player(0, |player| {
+            player.with_hand(1, "bina").with_shards(Color::BLUE, 3)
+        }));
+
+        let report = env.cast(0, 1);
+        assert_log!(
+            report,
+            GameLog::CardTokenGenerated { card } if card.archetype_id == ArchetypeId::new("bina")
+        );
+
+        let field = &env.state.players.get(0).field;
+        assert_eq!(field.len(), 2);
+        assert!(field.iter().all(|card| card.archetype().id == ArchetypeId::new("bina")));
+    }
+}
@@ -14,3 +14,30 @@ card_def!(
 );
 
 impl Effect for CardDef {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, field::FieldState, log::GameLog};
+
+    #[test]
+    fn test_toxic_destroys_stronger_attacker() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| player.with_field(1, "badl", FieldState::Active))
+                .with_player(1, |player| {
+                    player.with_field(51, "copp", FieldState::Active)
+                }),
+        );
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[(1, 51)]);
+        assert_log!(
+            report,
+            GameLog::CardMoved { card, reason: MoveReason::Destroyed, .. } if card.id == id(1)
+        );
+        assert_eq!(env.zone_of(1), Some(Zone::Graveyard));
+        assert_eq!(env.zone_of(51), Some(Zone::Graveyard));
+    }
+}
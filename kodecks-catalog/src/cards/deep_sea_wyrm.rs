@@ -43,3 +43,54 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{field::FieldState, player::PlayerZone};
+
+    #[test]
+    fn test_shuffle_weaker_creatures_into_decks() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| {
+                    player
+                        .with_hand(1, "deep")
+                        .with_field(2, "ruby", FieldState::Active)
+                        .with_shards(Color::BLUE, 6)
+                })
+                .with_player(1, |player| {
+                    player
+                        .with_field(51, "host", FieldState::Active)
+                        .with_field(52, "quic", FieldState::Active)
+                }),
+        );
+
+        env.cast(0, 1);
+        assert_eq!(
+            env.state.find_zone(id(2)).ok(),
+            Some(PlayerZone::new(0, Zone::Deck))
+        );
+        assert_eq!(
+            env.state.find_zone(id(51)).ok(),
+            Some(PlayerZone::new(1, Zone::Deck))
+        );
+        assert_eq!(env.zone_of(52), Some(Zone::Field));
+        assert_eq!(env.zone_of(1), Some(Zone::Field));
+    }
+
+    #[test]
+    fn test_stealth() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| {
+                    player.with_hand(1, "airb").with_shards(Color::BLUE, 3)
+                })
+                .with_player(1, |player| player.with_field(51, "deep", FieldState::Active)),
+        );
+
+        let report = env.cast(0, 1);
+        assert_eq!(selectable_cards(&report), vec![id(1)]);
+    }
+}
@@ -44,3 +44,42 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, assert_no_log, field::FieldState, log::GameLog};
+
+    #[test]
+    fn test_generate_shard_on_battle_damage() {
+        let mut env = start(
+            builder().with_player(0, |player| player.with_field(1, "diam", FieldState::Active)),
+        );
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[]);
+        assert_log!(
+            report,
+            GameLog::ShardsEarned { player: 0, color, amount: 1, .. } if *color == Color::RED
+        );
+        assert_eq!(env.shards_of(0, Color::RED), 1);
+    }
+
+    #[test]
+    fn test_no_shard_when_blocked() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| player.with_field(1, "diam", FieldState::Active))
+                .with_player(1, |player| {
+                    player.with_field(51, "host", FieldState::Active)
+                }),
+        );
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[(1, 51)]);
+        assert_no_log!(report, GameLog::ShardsEarned { player: 0, .. });
+        assert_log!(report, GameLog::ShieldBroken { card } if card.id == id(1));
+        assert_eq!(env.zone_of(1), Some(Zone::Field));
+    }
+}
@@ -42,3 +42,31 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::field::FieldState;
+
+    #[test]
+    fn test_exhaust_all_creatures() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| {
+                    player
+                        .with_hand(1, "flas")
+                        .with_field(2, "ruby", FieldState::Active)
+                        .with_shards(Color::BLUE, 3)
+                })
+                .with_player(1, |player| {
+                    player.with_field(51, "host", FieldState::Active)
+                }),
+        );
+
+        env.cast(0, 1);
+        for card in [1, 2, 51] {
+            assert_eq!(env.field_state_of(card), Some(FieldState::Exhausted));
+        }
+    }
+}
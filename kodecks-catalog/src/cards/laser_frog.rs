@@ -65,3 +65,42 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, field::FieldState, log::GameLog};
+
+    #[test]
+    fn test_break_shield() {
+        let mut env = start(builder().with_player(0, |player| {
+            player
+                .with_hand(1, "lase")
+                .with_field(2, "amet", FieldState::Active)
+                .with_field(3, "ruby", FieldState::Active)
+                .with_shards(Color::RED, 2)
+        }));
+
+        let report = env.cast(0, 1);
+        assert_eq!(selectable_cards(&report), vec![id(2)]);
+
+        let report = env.select(0, 2);
+        assert_log!(report, GameLog::ShieldBroken { card } if card.id == id(2));
+        assert_eq!(env.shields_of(2), 0);
+        assert_eq!(env.zone_of(2), Some(Zone::Field));
+    }
+
+    #[test]
+    fn test_no_target_without_shields() {
+        let mut env = start(builder().with_player(0, |player| {
+            player
+                .with_hand(1, "lase")
+                .with_field(2, "ruby", FieldState::Active)
+                .with_shards(Color::RED, 2)
+        }));
+
+        let report = env.cast(0, 1);
+        assert!(selectable_cards(&report).is_empty());
+    }
+}
@@ -67,3 +67,40 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, assert_no_log, field::FieldState, log::GameLog};
+
+    #[test]
+    fn test_destroy_own_creature() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| {
+                    player
+                        .with_hand(1, "mire")
+                        .with_field(2, "ruby", FieldState::Active)
+                        .with_shards(Color::GREEN, 3)
+                })
+                .with_player(1, |player| {
+                    player.with_field(51, "host", FieldState::Active)
+                }),
+        );
+
+        let report = env.cast(0, 1);
+        let cards = selectable_cards(&report);
+        assert!(cards.contains(&id(2)));
+        assert!(!cards.contains(&id(51)));
+
+        let report = env.select(0, 2);
+        assert_log!(
+            report,
+            GameLog::CardMoved { card, reason: MoveReason::Destroyed, .. } if card.id == id(2)
+        );
+        // Devour prevents the destroyed creature from generating a shard.
+        assert_no_log!(report, GameLog::ShardsEarned { .. });
+        assert_eq!(env.zone_of(2), Some(Zone::Graveyard));
+    }
+}
@@ -56,6 +56,12 @@ mod wind_up_spider;
 mod wiretap_vine;
 mod zigzag_ammonite;
 
+/// Creatures without abilities or effects, which share a single test.
+pub const VANILLA_CREATURES: &[&str] = &[
+    "auto", "awkw", "badl", "ceno", "demi", "ever", "halo", "heli", "host", "icef", "mars", "mini",
+    "moon", "quag", "quar", "ruby", "sola", "subs", "turb", "wast", "wind", "wire", "zigz",
+];
+
 pub static CARDS: &CardList = &[
     ruby_digger::ARCHETYPE,
     cenote_otter::ARCHETYPE,
@@ -14,3 +14,27 @@ card_def!(
 );
 
 impl Effect for CardDef {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_no_log, field::FieldState, log::GameLog};
+
+    #[test]
+    fn test_piercing() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| player.with_field(1, "orep", FieldState::Active))
+                .with_player(1, |player| {
+                    player.with_field(51, "vigi", FieldState::Active)
+                }),
+        );
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[(1, 51)]);
+        assert_no_log!(report, GameLog::ShieldBroken { .. });
+        assert_eq!(env.zone_of(1), Some(Zone::Graveyard));
+        assert_eq!(env.zone_of(51), Some(Zone::Graveyard));
+    }
+}
@@ -15,3 +15,27 @@ card_def!(
 );
 
 impl Effect for CardDef {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, field::FieldState, log::GameLog};
+
+    #[test]
+    fn test_toxic_with_shield() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| player.with_field(1, "badl", FieldState::Active))
+                .with_player(1, |player| {
+                    player.with_field(51, "pois", FieldState::Active)
+                }),
+        );
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[(1, 51)]);
+        assert_log!(report, GameLog::ShieldBroken { card } if card.id == id(51));
+        assert_eq!(env.zone_of(1), Some(Zone::Graveyard));
+        assert_eq!(env.zone_of(51), Some(Zone::Field));
+    }
+}
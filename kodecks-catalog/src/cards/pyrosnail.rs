@@ -37,3 +37,34 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, assert_no_log, field::FieldState, log::GameLog};
+
+    #[test]
+    fn test_damage_when_destroyed() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| player.with_field(1, "wind", FieldState::Active))
+                .with_player(1, |player| {
+                    player.with_field(51, "pyro", FieldState::Active)
+                }),
+        );
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[(1, 51)]);
+        assert_log!(
+            report,
+            GameLog::DamageTaken {
+                player: 0,
+                amount: 100
+            }
+        );
+        assert_no_log!(report, GameLog::ShardsEarned { player: 1, .. });
+        assert_eq!(env.zone_of(51), Some(Zone::Graveyard));
+        assert_eq!(env.life_of(0), 1900);
+    }
+}
@@ -41,3 +41,35 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, assert_no_log, log::GameLog};
+
+    #[test]
+    fn test_generate_shard_without_shards() {
+        let mut env = start(builder().with_player(0, |player| {
+            player.with_hand(1, "scra").with_shards(Color::GREEN, 2)
+        }));
+
+        let report = env.cast(0, 1);
+        assert_log!(
+            report,
+            GameLog::ShardsEarned { player: 0, color, amount: 1, .. } if *color == Color::GREEN
+        );
+        assert_eq!(env.shards_of(0, Color::GREEN), 1);
+    }
+
+    #[test]
+    fn test_no_shard_with_remaining_shards() {
+        let mut env = start(builder().with_player(0, |player| {
+            player.with_hand(1, "scra").with_shards(Color::GREEN, 3)
+        }));
+
+        let report = env.cast(0, 1);
+        assert_no_log!(report, GameLog::ShardsEarned { .. });
+        assert_eq!(env.shards_of(0, Color::GREEN), 1);
+    }
+}
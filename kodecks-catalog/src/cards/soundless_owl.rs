@@ -14,3 +14,26 @@ card_def!(
 );
 
 impl Effect for CardDef {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::field::FieldState;
+
+    #[test]
+    fn test_stealth() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| {
+                    player.with_hand(1, "airb").with_shards(Color::BLUE, 3)
+                })
+                .with_player(1, |player| {
+                    player.with_field(51, "soun", FieldState::Active)
+                }),
+        );
+
+        let report = env.cast(0, 1);
+        assert_eq!(selectable_cards(&report), vec![id(1)]);
+    }
+}
@@ -39,6 +39,10 @@ impl Effect for CardDef {
 }
 
 impl ContinuousEffect for CardDef {
+    fn layer(&self) -> ContinuousLayer {
+        ContinuousLayer::PowerModify
+    }
+
     fn apply_card(
         &mut self,
         _state: &GameState,
@@ -47,10 +51,85 @@ impl ContinuousEffect for CardDef {
         computed: &mut ComputedAttribute,
     ) -> Result<()> {
         if target.id() == source.id() {
-            if let Some(power) = &mut computed.power {
-                power.add(100);
-            }
+            computed.add_power(source, 100);
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::*;
+    use kodecks::{
+        archetype::ArchetypeId,
+        computed::{ComputedModifier, ModifierKind},
+        field::FieldState,
+    };
+
+    #[test]
+    fn test_power_up_when_opponent_casts() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| player.with_hand(1, "wind").with_deck(2, "wind"))
+                .with_player(1, |player| {
+                    player
+                        .with_hand(51, "moon")
+                        .with_field(52, "vigi", FieldState::Active)
+                        .with_deck(53, "moon")
+                }),
+        );
+
+        env.cast(0, 1);
+        assert_eq!(env.power_of(52), 200);
+
+        env.end_turn(0);
+        assert_eq!(env.power_of(52), 100);
+
+        env.cast(1, 51);
+        assert_eq!(env.power_of(52), 100);
+    }
+
+    #[test]
+    fn test_modifiers_visible_to_owner() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| player.with_hand(1, "wind").with_deck(2, "wind"))
+                .with_player(1, |player| {
+                    player
+                        .with_field(52, "vigi", FieldState::Active)
+                        .with_deck(53, "moon")
+                }),
+        );
+
+        env.cast(0, 1);
+        let owner = env.local(1);
+        let computed = owner.find_card(id(52)).unwrap().computed.as_ref().unwrap();
+        let effect = computed
+            .modifiers
+            .first()
+            .and_then(|modifier| modifier.effect);
+        assert!(effect.is_some());
+        assert_eq!(
+            computed.modifiers,
+            vec![ComputedModifier {
+                effect,
+                source: id(52),
+                archetype_id: ArchetypeId::new("vigi"),
+                kind: ModifierKind::Power {
+                    before: 100,
+                    after: 200
+                },
+            }]
+        );
+
+        let opponent = env.local(0);
+        let computed = opponent
+            .find_card(id(52))
+            .unwrap()
+            .computed
+            .as_ref()
+            .unwrap();
+        assert_eq!(computed.current_power(), 200);
+        assert!(computed.modifiers.is_empty());
+    }
+}
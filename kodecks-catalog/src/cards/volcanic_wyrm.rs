@@ -37,3 +37,57 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, field::FieldState, log::GameLog};
+
+    #[test]
+    fn test_damage_when_attacking() {
+        let mut env = start(
+            builder().with_player(0, |player| player.with_field(1, "volc", FieldState::Active)),
+        );
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[]);
+        assert_log!(
+            report,
+            GameLog::DamageTaken {
+                player: 1,
+                amount: 200
+            }
+        );
+        assert_log!(
+            report,
+            GameLog::DamageTaken {
+                player: 1,
+                amount: 500
+            }
+        );
+        assert_eq!(env.life_of(1), 1300);
+    }
+
+    #[test]
+    fn test_damage_when_blocking() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| player.with_field(1, "ruby", FieldState::Active))
+                .with_player(1, |player| {
+                    player.with_field(51, "volc", FieldState::Active)
+                }),
+        );
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[(1, 51)]);
+        assert_log!(
+            report,
+            GameLog::DamageTaken {
+                player: 0,
+                amount: 200
+            }
+        );
+        assert_eq!(env.zone_of(1), Some(Zone::Graveyard));
+    }
+}
@@ -38,3 +38,52 @@ impl Effect for CardDef {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use kodecks::{assert_log, assert_no_log, field::FieldState, log::GameLog};
+
+    #[test]
+    fn test_generate_ant_when_destroyed() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| player.with_field(1, "vora", FieldState::Active))
+                .with_player(1, |player| {
+                    player.with_field(51, "badl", FieldState::Active)
+                }),
+        );
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[(1, 51)]);
+        assert_log!(
+            report,
+            GameLog::CardTokenGenerated { card } if card.archetype_id == ArchetypeId::new("ant")
+        );
+        assert_eq!(env.zone_of(1), Some(Zone::Graveyard));
+
+        let field = &env.state.players.get(0).field;
+        assert_eq!(field.len(), 1);
+        assert!(field.iter().all(|card| card.archetype().id == ArchetypeId::new("ant")));
+    }
+
+    #[test]
+    fn test_devour() {
+        let mut env = start(
+            builder()
+                .with_player(0, |player| player.with_field(1, "ruby", FieldState::Active))
+                .with_player(1, |player| {
+                    player.with_field(51, "vora", FieldState::Active)
+                }),
+        );
+        env.attack(0, &[1]);
+
+        let report = env.block(1, &[(1, 51)]);
+        assert_log!(
+            report,
+            GameLog::CardMoved { card, reason: MoveReason::Destroyed, .. } if card.id == id(1)
+        );
+        assert_no_log!(report, GameLog::ShardsEarned { .. });
+    }
+}
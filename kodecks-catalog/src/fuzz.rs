@@ -0,0 +1,338 @@
+//! Plays seeded games with random legal actions and checks engine invariants after each step.
+//!
+//! A failing game is returned with a [`Replay`] which reproduces it step by step.
+
+use crate::{decks, CATALOG};
+use kodecks::{
+    action::{Action, AvailableAction, PlayerAvailableActions},
+    card::CardEntry,
+    deck::{DeckItem, DeckList},
+    env::{Environment, Report},
+    id::ObjectId,
+    log::GameLog,
+    player::PlayerConfig,
+    profile::GameProfile,
+    replay::Replay,
+    zone::CardZone,
+};
+use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+use std::{
+    collections::HashSet,
+    fmt, fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+const DECK_SIZE: usize = 20;
+
+#[derive(Debug, Clone, Copy)]
+pub struct FuzzConfig {
+    /// A game still in progress after this turn is considered stuck.
+    pub max_turns: u16,
+    /// The maximum number of `process` calls in a game.
+    pub max_steps: usize,
+    /// The maximum number of consecutive steps with a non-empty stack.
+    pub max_stack_steps: usize,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            max_turns: 200,
+            max_steps: 100_000,
+            max_stack_steps: 1_000,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FuzzFailure {
+    pub seed: u64,
+    pub step: usize,
+    pub reason: String,
+    pub replay: Replay,
+}
+
+impl FuzzFailure {
+    /// Writes the replay to `fuzz-<seed>.json` in the directory.
+    pub fn save(&self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!("fuzz-{}.json", self.seed));
+        fs::write(&path, serde_json::to_string_pretty(&self.replay)?)?;
+        Ok(path)
+    }
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "seed {} failed at step {}: {}",
+            self.seed, self.step, self.reason
+        )
+    }
+}
+
+/// Plays a game with random decks and actions derived from the seed.
+pub fn fuzz_game(seed: u64, config: &FuzzConfig) -> Result<(), Box<FuzzFailure>> {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let profile = GameProfile {
+        players: (0..2)
+            .map(|_| PlayerConfig {
+                deck: random_deck(&mut rng),
+            })
+            .collect(),
+        rng_seed: Some(seed),
+        ..Default::default()
+    };
+
+    let mut replay = Replay::new(profile.clone());
+    let mut env = Environment::new(profile, CATALOG.clone());
+    let mut invariants = Invariants::new(&env);
+
+    let mut player = 0;
+    let mut action = None;
+    for step in 0..config.max_steps {
+        replay.push(player, action.clone());
+        let fail = |reason: String| {
+            Box::new(FuzzFailure {
+                seed,
+                step,
+                reason,
+                replay: replay.clone(),
+            })
+        };
+
+        let report = panic::catch_unwind(AssertUnwindSafe(|| env.process(player, action.take())))
+            .map_err(|err| fail(panic_message(err)))?;
+        invariants.check(&env, &report, config).map_err(&fail)?;
+
+        if report.endgame.is_ended() {
+            return Ok(());
+        }
+        if let Some(actions) = &report.available_actions {
+            player = actions.player;
+            action = Some(random_action(&env, actions, &mut rng));
+        }
+    }
+
+    Err(Box::new(FuzzFailure {
+        seed,
+        step: config.max_steps,
+        reason: format!("game did not end within {} steps", config.max_steps),
+        replay,
+    }))
+}
+
+struct Invariants {
+    cards: usize,
+    life: Vec<u32>,
+    stack_steps: usize,
+}
+
+impl Invariants {
+    fn new(env: &Environment) -> Self {
+        Self {
+            cards: card_ids(env).len(),
+            life: env
+                .state
+                .players
+                .iter()
+                .map(|player| player.stats.life)
+                .collect(),
+            stack_steps: 0,
+        }
+    }
+
+    fn check(
+        &mut self,
+        env: &Environment,
+        report: &Report,
+        config: &FuzzConfig,
+    ) -> Result<(), String> {
+        if let Some(rejected) = &report.rejected {
+            return Err(format!("legal action rejected: {:?}", rejected));
+        }
+
+        let ids = card_ids(env);
+        let unique = ids.iter().collect::<HashSet<_>>();
+        if unique.len() != ids.len() {
+            return Err("a card is in multiple zones".to_string());
+        }
+        let cards = ids
+            .iter()
+            .filter(|id| !env.state.find_card(**id).unwrap().is_token())
+            .count();
+        if cards != self.cards {
+            return Err(format!("expected {} cards, found {}", self.cards, cards));
+        }
+
+        for log in &report.logs {
+            if let GameLog::LifeChanged { player, life } = log {
+                self.life[*player as usize] = *life;
+            }
+        }
+        for player in env.state.players.iter() {
+            let expected = self.life[player.id as usize];
+            if player.stats.life != expected {
+                return Err(format!(
+                    "life of player {} is {} but the logs say {}",
+                    player.id, player.stats.life, expected
+                ));
+            }
+        }
+
+        let turn_changed = report
+            .logs
+            .iter()
+            .any(|log| matches!(log, GameLog::TurnChanged { .. }));
+        if env.is_stack_empty() {
+            self.stack_steps = 0;
+        } else if turn_changed {
+            return Err("the turn changed with a non-empty stack".to_string());
+        } else {
+            self.stack_steps += 1;
+            if self.stack_steps > config.max_stack_steps {
+                return Err(format!(
+                    "the stack did not drain within {} steps",
+                    config.max_stack_steps
+                ));
+            }
+        }
+
+        if env.state.turn > config.max_turns {
+            return Err(format!(
+                "game did not end within {} turns",
+                config.max_turns
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn card_ids(env: &Environment) -> Vec<ObjectId> {
+    env.state
+        .players
+        .iter()
+        .flat_map(|player| {
+            player
+                .deck
+                .iter()
+                .chain(player.hand.iter())
+                .chain(player.field.iter())
+                .chain(player.graveyard.iter())
+                .map(|card| card.id())
+        })
+        .collect()
+}
+
+fn random_deck(rng: &mut SmallRng) -> DeckList {
+    match rng.gen_range(0..4) {
+        0 => decks::starter_deck(&CATALOG),
+        1 => decks::blue_deck(&CATALOG),
+        2 => decks::red_deck(&CATALOG),
+        _ => {
+            let mut archetypes = CATALOG
+                .iter()
+                .filter(|card| !card.attribute.is_token)
+                .map(|card| card.id)
+                .collect::<Vec<_>>();
+            archetypes.sort();
+            archetypes.dedup();
+            DeckList {
+                id: "fuzz".to_string(),
+                name: "Fuzz".to_string(),
+                cards: (0..DECK_SIZE)
+                    .map(|_| DeckItem {
+                        card: CardEntry {
+                            archetype_id: *archetypes.choose(rng).unwrap(),
+                            style: 0,
+                        },
+                        base_id: None,
+                    })
+                    .collect(),
+            }
+        }
+    }
+}
+
+fn random_action(
+    env: &Environment,
+    actions: &PlayerAvailableActions,
+    rng: &mut SmallRng,
+) -> Action {
+    let Some(action) = actions.actions.as_ref().choose(rng) else {
+        return Action::Concede;
+    };
+    match action {
+        AvailableAction::SelectCard { cards, .. } => Action::SelectCard {
+            card: *cards.choose(rng).unwrap(),
+        },
+        AvailableAction::CastCard { cards } => Action::CastCard {
+            card: *cards.choose(rng).unwrap(),
+        },
+        AvailableAction::Attack { attackers } => {
+            let amount = rng.gen_range(1..=attackers.len());
+            Action::Attack {
+                attackers: attackers.choose_multiple(rng, amount).copied().collect(),
+            }
+        }
+        AvailableAction::Block { blockers } => {
+            let mut blockers = blockers.clone();
+            blockers.shuffle(rng);
+            let opponent = env.state.players.next_player(actions.player);
+            let pairs = opponent
+                .field
+                .attacking_cards()
+                .map(|card| card.id())
+                .zip(blockers)
+                .filter(|_| rng.gen_bool(0.5))
+                .collect();
+            Action::Block { pairs }
+        }
+        AvailableAction::EndTurn => Action::EndTurn,
+        AvailableAction::Continue => Action::Continue,
+    }
+}
+
+fn panic_message(err: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(message) = err.downcast_ref::<&str>() {
+        format!("panicked: {message}")
+    } else if let Some(message) = err.downcast_ref::<String>() {
+        format!("panicked: {message}")
+    } else {
+        "panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzz() {
+        let config = FuzzConfig::default();
+        for seed in 0..200 {
+            if let Err(failure) = fuzz_game(seed, &config) {
+                let path = failure.save(&std::env::temp_dir());
+                panic!("{failure}\nreplay: {path:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_replay() {
+        let config = FuzzConfig {
+            max_steps: 50,
+            ..Default::default()
+        };
+        let failure = fuzz_game(0, &config).unwrap_err();
+        assert_eq!(failure.replay.steps.len(), 50);
+
+        let json = serde_json::to_string(&failure.replay).unwrap();
+        let replay: Replay = serde_json::from_str(&json).unwrap();
+        let env = failure.replay.play(CATALOG.clone());
+        assert!(env.state.turn > 1);
+        assert_eq!(env.checksum(0), replay.play(CATALOG.clone()).checksum(0));
+    }
+}
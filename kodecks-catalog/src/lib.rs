@@ -5,6 +5,14 @@ use std::sync::{Arc, LazyLock};
 
 mod cards;
 pub mod decks;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 mod macros;
+pub mod puzzles;
+#[cfg(test)]
+mod test_util;
+pub mod tutorials;
+
+pub use cards::VANILLA_CREATURES;
 
 pub static CATALOG: LazyLock<Arc<Catalog>> = LazyLock::new(|| Arc::new(Catalog::new(cards::CARDS)));
@@ -0,0 +1,43 @@
+use kodecks::scenario::Puzzle;
+use std::sync::LazyLock;
+
+const PUZZLES: &[&str] = &[include_str!("../puzzles/all_out_attack.json")];
+
+pub static PUZZLE_LIST: LazyLock<Vec<Puzzle>> = LazyLock::new(|| {
+    PUZZLES
+        .iter()
+        .map(|json| serde_json::from_str(json).unwrap())
+        .collect()
+});
+
+pub fn find_puzzle(id: &str) -> Option<&'static Puzzle> {
+    PUZZLE_LIST.iter().find(|puzzle| puzzle.id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CATALOG;
+
+    #[test]
+    fn test_puzzle_list() {
+        for puzzle in PUZZLE_LIST.iter() {
+            let cards = puzzle.players.iter().flat_map(|player| {
+                player
+                    .hand
+                    .iter()
+                    .chain(player.field.iter().map(|item| &item.card))
+                    .chain(&player.graveyard)
+                    .chain(&player.deck)
+            });
+            for item in cards {
+                assert!(
+                    CATALOG.contains(item.card.archetype_id),
+                    "unknown card {} in puzzle {}",
+                    item.card.archetype_id,
+                    puzzle.id
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Helpers for the card behavior tests.
+//!
+//! Every card module in [`crate::cards`] is expected to have at least one test,
+//! which is checked by `catalog-util`. Creatures without abilities or effects
+//! are listed in [`VANILLA_CREATURES`] and tested together instead.
+
+use crate::{CATALOG, VANILLA_CREATURES};
+use kodecks::{
+    action::{Action, AvailableAction},
+    color::Color,
+    env::{Environment, EnvironmentBuilder, Report},
+    field::FieldState,
+    id::ObjectId,
+    zone::Zone,
+};
+
+pub fn id(id: u32) -> ObjectId {
+    ObjectId::try_from(id).unwrap()
+}
+
+/// Returns an environment builder starting at the main phase of the first player.
+pub fn builder() -> EnvironmentBuilder {
+    Environment::builder(CATALOG.clone())
+}
+
+/// Builds the environment and advances it to the first decision.
+pub fn start(builder: EnvironmentBuilder) -> Environment {
+    let mut env = builder.build();
+    let report = env.advance(0, None);
+    assert!(
+        report.available_actions.is_some(),
+        "no actions available\nlogs: {:#?}",
+        report.logs
+    );
+    env
+}
+
+/// Returns the cards offered by a [`AvailableAction::SelectCard`] in the report.
+pub fn selectable_cards(report: &Report) -> Vec<ObjectId> {
+    report
+        .available_actions
+        .iter()
+        .flat_map(|actions| actions.actions.as_ref())
+        .filter_map(|action| match action {
+            AvailableAction::SelectCard { cards, .. } => Some(cards.clone()),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// Shortcuts for driving a test game. Every action must be accepted.
+pub trait TestEnvironment {
+    fn act(&mut self, player: u8, action: Action) -> Report;
+
+    fn cast(&mut self, player: u8, card: u32) -> Report {
+        self.act(player, Action::CastCard { card: id(card) })
+    }
+
+    fn select(&mut self, player: u8, card: u32) -> Report {
+        self.act(player, Action::SelectCard { card: id(card) })
+    }
+
+    fn attack(&mut self, player: u8, attackers: &[u32]) -> Report {
+        let attackers = attackers.iter().copied().map(id).collect();
+        self.act(player, Action::Attack { attackers })
+    }
+
+    fn block(&mut self, player: u8, pairs: &[(u32, u32)]) -> Report {
+        let pairs = pairs
+            .iter()
+            .map(|&(attacker, blocker)| (id(attacker), id(blocker)))
+            .collect();
+        self.act(player, Action::Block { pairs })
+    }
+
+    fn end_turn(&mut self, player: u8) -> Report {
+        self.act(player, Action::EndTurn)
+    }
+
+    fn zone_of(&self, card: u32) -> Option<Zone>;
+    fn power_of(&self, card: u32) -> u32;
+    fn shields_of(&self, card: u32) -> u8;
+    fn field_state_of(&self, card: u32) -> Option<FieldState>;
+    fn life_of(&self, player: u8) -> u32;
+    fn shards_of(&self, player: u8, color: Color) -> u8;
+}
+
+impl TestEnvironment for Environment {
+    fn act(&mut self, player: u8, action: Action) -> Report {
+        let report = self.advance(player, Some(action.clone()));
+        assert!(
+            report.rejected.is_none(),
+            "action rejected: {action:?}\n{:#?}",
+            report.rejected
+        );
+        report
+    }
+
+    fn zone_of(&self, card: u32) -> Option<Zone> {
+        self.state.find_zone(id(card)).ok().map(|zone| zone.zone)
+    }
+
+    fn power_of(&self, card: u32) -> u32 {
+        self.state
+            .find_card(id(card))
+            .unwrap()
+            .computed()
+            .current_power()
+    }
+
+    fn shields_of(&self, card: u32) -> u8 {
+        self.state
+            .find_card(id(card))
+            .unwrap()
+            .computed()
+            .current_shields()
+    }
+
+    fn field_state_of(&self, card: u32) -> Option<FieldState> {
+        self.state
+            .players
+            .iter()
+            .find_map(|player| player.field.get_item(id(card)).ok())
+            .map(|item| item.state)
+    }
+
+    fn life_of(&self, player: u8) -> u32 {
+        self.state.players.get(player).stats.life
+    }
+
+    fn shards_of(&self, player: u8, color: Color) -> u8 {
+        self.state.players.get(player).shards.get(color)
+    }
+}
+
+mod tests {
+    use super::*;
+    use kodecks::{assert_log, assert_no_log, log::GameLog};
+
+    #[test]
+    fn test_vanilla_creatures() {
+        for &archetype_id in VANILLA_CREATURES {
+            let archetype = &CATALOG[archetype_id];
+            let attribute = &archetype.attribute;
+            assert!(
+                attribute.abilities.is_empty()
+                    && attribute.anon_abilities.is_empty()
+                    && attribute.shields.is_none(),
+                "{archetype_id} is not vanilla"
+            );
+            let power = attribute.power.unwrap();
+
+            let mut env = start(builder().with_player(0, |player| {
+                player
+                    .with_hand(1, archetype_id)
+                    .with_shards(attribute.color, attribute.cost)
+            }));
+
+            let report = env.cast(0, 1);
+            if attribute.cost > 0 {
+                assert_log!(
+                    report,
+                    GameLog::ShardsSpent { player: 0, amount, .. } if *amount == attribute.cost
+                );
+            } else {
+                assert_no_log!(report, GameLog::ShardsSpent { .. });
+            }
+            assert_eq!(env.zone_of(1), Some(Zone::Field), "{archetype_id}");
+            assert_eq!(env.power_of(1), power, "{archetype_id}");
+
+            env.attack(0, &[1]);
+            let report = env.block(1, &[]);
+            assert_log!(
+                report,
+                GameLog::DamageTaken { player: 1, amount } if *amount == power
+            );
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use kodecks::scenario::Tutorial;
+use std::sync::LazyLock;
+
+const TUTORIALS: &[&str] = &[include_str!("../tutorials/basics.json")];
+
+pub static TUTORIAL_LIST: LazyLock<Vec<Tutorial>> = LazyLock::new(|| {
+    TUTORIALS
+        .iter()
+        .map(|json| serde_json::from_str(json).unwrap())
+        .collect()
+});
+
+pub fn find_tutorial(id: &str) -> Option<&'static Tutorial> {
+    TUTORIAL_LIST.iter().find(|tutorial| tutorial.id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CATALOG;
+
+    #[test]
+    fn test_tutorial_list() {
+        for tutorial in TUTORIAL_LIST.iter() {
+            for item in tutorial.decks.iter().flatten() {
+                assert!(
+                    CATALOG.contains(item.card.archetype_id),
+                    "unknown card {} in tutorial {}",
+                    item.card.archetype_id,
+                    tutorial.id
+                );
+            }
+        }
+    }
+}
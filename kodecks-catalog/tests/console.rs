@@ -0,0 +1,117 @@
+use kodecks::{
+    action::Action,
+    archetype::ArchetypeId,
+    assert_log,
+    color::Color,
+    command::ActionCommand,
+    console,
+    env::Environment,
+    error::ConsoleError,
+    field::FieldState,
+    id::ObjectId,
+    log::GameLog,
+    profile::{DebugConfig, DebugFlags},
+    zone::{CardZone, Zone},
+};
+use kodecks_catalog::CATALOG;
+
+fn id(id: u32) -> ObjectId {
+    ObjectId::try_from(id).unwrap()
+}
+
+#[test]
+fn test_console_commands() {
+    let mut env = Environment::builder(CATALOG.clone())
+        .with_debug(DebugConfig {
+            flags: DebugFlags::DEBUG_COMMAND,
+            ..Default::default()
+        })
+        .with_player(0, |player| {
+            player
+                .with_field(1, "ruby", FieldState::Active)
+                .with_deck(2, "wind")
+        })
+        .build();
+    env.advance(0, None);
+    let life = env.state.players.get(1).stats.life;
+
+    let commands = console::parse(
+        "damage 1 500; destroy Ruby Digger; token p1 ant; shards me blue 3; draw p0",
+        &env.local(0),
+        &CATALOG,
+    )
+    .unwrap();
+    let report = env.advance(0, Some(Action::DebugCommand { commands }));
+    assert!(report.rejected.is_none());
+
+    assert_eq!(env.state.players.get(1).stats.life, life - 500);
+    assert_eq!(env.state.find_zone(id(1)).unwrap().zone, Zone::Graveyard);
+    assert_eq!(env.state.find_zone(id(2)).unwrap().zone, Zone::Hand);
+    assert_eq!(env.state.players.get(0).shards.get(Color::BLUE), 3);
+    assert_log!(report, GameLog::CardTokenGenerated { card } if card.controller == 1);
+}
+
+#[test]
+fn test_explicit_token_ids_are_kept() {
+    let mut env = Environment::builder(CATALOG.clone())
+        .with_debug(DebugConfig {
+            flags: DebugFlags::DEBUG_COMMAND,
+            ..Default::default()
+        })
+        .build();
+    env.advance(0, None);
+
+    let mut commands = console::parse("token p0 ant", &env.local(0), &CATALOG).unwrap();
+    commands.push(ActionCommand::GenerateCardToken {
+        token: id(500),
+        archetype: ArchetypeId::new("ant"),
+        player: 0,
+    });
+    let report = env.advance(0, Some(Action::DebugCommand { commands }));
+    assert!(report.rejected.is_none());
+
+    let placeholder = id(console::TOKEN_ID_PLACEHOLDER);
+    assert!(env.state.find_card(placeholder).is_err());
+    assert_eq!(env.state.find_zone(id(500)).unwrap().zone, Zone::Field);
+    assert_eq!(env.state.players.get(0).field.len(), 2);
+}
+
+#[test]
+fn test_console_errors() {
+    let mut env = Environment::builder(CATALOG.clone()).build();
+    env.advance(0, None);
+    let env = env.local(0);
+    let parse = |input| console::parse(input, &env, &CATALOG).unwrap_err();
+
+    assert!(matches!(
+        parse("fly 1"),
+        ConsoleError::UnknownCommand { .. }
+    ));
+    assert!(matches!(
+        parse("damage p7 100"),
+        ConsoleError::UnknownPlayer { .. }
+    ));
+    assert!(matches!(
+        parse("damage 1 lots"),
+        ConsoleError::InvalidNumber { .. }
+    ));
+    assert!(matches!(
+        parse("draw p0 100"),
+        ConsoleError::InvalidNumber { .. }
+    ));
+    assert!(matches!(
+        parse("destroy Ruby Digger"),
+        ConsoleError::UnknownCard { .. }
+    ));
+    assert!(matches!(
+        parse("token p0 dragon"),
+        ConsoleError::UnknownArchetype { .. }
+    ));
+    assert!(matches!(
+        parse("shards p0"),
+        ConsoleError::MissingArgument {
+            argument: "color",
+            ..
+        }
+    ));
+}
@@ -0,0 +1,100 @@
+use kodecks::{
+    action::Action,
+    archetype::ArchetypeId,
+    env::{Environment, HiddenCards},
+    id::ObjectId,
+    zone::{CardZone, Zone},
+};
+use kodecks_catalog::CATALOG;
+use rand::{rngs::SmallRng, SeedableRng};
+
+fn id(id: u32) -> ObjectId {
+    ObjectId::try_from(id).unwrap()
+}
+
+#[test]
+fn test_determinize() {
+    let mut env = Environment::builder(CATALOG.clone())
+        .with_player(0, |player| player.with_hand(1, "ruby"))
+        .with_player(1, |player| {
+            player
+                .with_hand(2, "wind")
+                .with_deck(3, "airb")
+                .with_deck(4, "lase")
+                .with_deck(5, "scra")
+        })
+        .build();
+    env.advance(0, None);
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut resampled = false;
+    for _ in 0..16 {
+        let sample = env.determinize(0, &mut rng);
+        assert_eq!(sample.state.find_zone(id(1)).unwrap().zone, Zone::Hand);
+        let opponent = sample.state.players().get(1);
+        assert_eq!(opponent.hand.len(), 1);
+        assert_eq!(opponent.deck.len(), 3);
+        resampled |= sample.state.find_zone(id(2)).unwrap().zone == Zone::Deck;
+    }
+    assert!(resampled);
+}
+
+#[test]
+fn test_from_local() {
+    let mut env = Environment::builder(CATALOG.clone())
+        .with_player(0, |player| player.with_hand(1, "ruby").with_deck(2, "quar"))
+        .with_player(1, |player| {
+            player
+                .with_hand(3, "wind")
+                .with_deck(4, "airb")
+                .with_deck(5, "lase")
+        })
+        .build();
+    let actions = env.advance(0, None).available_actions;
+
+    let local = env.local(0);
+    let hidden = [
+        (
+            0,
+            HiddenCards {
+                hand: vec![],
+                deck: vec![ArchetypeId::new("quar")],
+            },
+        ),
+        (
+            1,
+            HiddenCards {
+                hand: vec![(id(3), ArchetypeId::new("ceno"))],
+                deck: vec![ArchetypeId::new("quag"), ArchetypeId::new("ceno")],
+            },
+        ),
+    ];
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut sample = Environment::from_local(
+        &local,
+        CATALOG.clone(),
+        env.state.regulation.clone(),
+        &hidden,
+        actions,
+        &mut rng,
+    );
+    assert_eq!(sample.local(0).checksum(), local.checksum());
+
+    let card = sample.state.find_card(id(3)).unwrap();
+    assert_eq!(card.archetype().id, ArchetypeId::new("ceno"));
+    assert_eq!(card.zone().zone, Zone::Hand);
+    let deck = sample
+        .state
+        .players()
+        .get(1)
+        .deck
+        .iter()
+        .map(|card| card.archetype().id.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(deck, ["quag", "ceno"]);
+    let card = sample.state.find_card(id(1)).unwrap();
+    assert_eq!(card.archetype().id, ArchetypeId::new("ruby"));
+
+    let report = sample.advance(0, Some(Action::EndTurn));
+    assert!(report.rejected.is_none());
+}
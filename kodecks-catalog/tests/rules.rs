@@ -0,0 +1,188 @@
+use kodecks::{
+    action::Action, assert_log, assert_no_log, color::Color, env::Environment,
+    error::UnavailableReason, field::FieldState, id::ObjectId, log::GameLog, phase::Phase,
+    zone::Zone,
+};
+use kodecks_catalog::CATALOG;
+
+fn id(id: u32) -> ObjectId {
+    ObjectId::try_from(id).unwrap()
+}
+
+#[test]
+fn test_unblocked_attack() {
+    let mut env = Environment::builder(CATALOG.clone())
+        .with_player(0, |player| player.with_field(1, "ruby", FieldState::Active))
+        .with_player(1, |player| player.with_life(500))
+        .build();
+    env.advance(0, None);
+    env.advance(
+        0,
+        Some(Action::Attack {
+            attackers: vec![id(1)],
+        }),
+    );
+
+    let report = env.advance(1, Some(Action::Block { pairs: vec![] }));
+    assert_log!(
+        report,
+        GameLog::DamageTaken {
+            player: 1,
+            amount: 300
+        }
+    );
+    assert_eq!(env.state.players.get(1).stats.life, 200);
+}
+
+#[test]
+fn test_battle_with_equal_power() {
+    let mut env = Environment::builder(CATALOG.clone())
+        .with_player(0, |player| player.with_field(1, "wind", FieldState::Active))
+        .with_player(1, |player| player.with_field(2, "quar", FieldState::Active))
+        .build();
+    env.advance(0, None);
+    env.advance(
+        0,
+        Some(Action::Attack {
+            attackers: vec![id(1)],
+        }),
+    );
+
+    let report = env.advance(
+        1,
+        Some(Action::Block {
+            pairs: vec![(id(1), id(2))],
+        }),
+    );
+    assert_log!(
+        report,
+        GameLog::ShardsEarned { player: 0, color, amount: 1, .. } if *color == Color::RED
+    );
+    assert_log!(
+        report,
+        GameLog::ShardsEarned { player: 1, color, amount: 1, .. } if *color == Color::RED
+    );
+    assert_no_log!(report, GameLog::DamageTaken { .. });
+}
+
+#[test]
+fn test_rejected_action() {
+    let mut env = Environment::builder(CATALOG.clone())
+        .with_player(0, |player| {
+            player
+                .with_hand(1, "ruby")
+                .with_deck(2, "ruby")
+                .with_shards(Color::RED, 10)
+        })
+        .with_player(1, |player| player.with_hand(51, "ruby"))
+        .build();
+    env.advance(0, None);
+
+    let report = env.advance(0, Some(Action::CastCard { card: id(2) }));
+    let rejected = report.rejected.unwrap();
+    assert!(matches!(rejected.action, Action::CastCard { card } if card == id(2)));
+    assert_eq!(
+        rejected.reason,
+        UnavailableReason::CardNotInHand { card: id(2) }
+    );
+    assert!(report.available_actions.is_some());
+
+    let report = env.advance(1, Some(Action::CastCard { card: id(51) }));
+    assert_eq!(
+        report.rejected.unwrap().reason,
+        UnavailableReason::NotInAction
+    );
+
+    let report = env.advance(0, Some(Action::CastCard { card: id(1) }));
+    assert!(report.rejected.is_none());
+    assert_eq!(env.state.find_zone(id(1)).unwrap().zone, Zone::Field);
+}
+
+#[test]
+fn test_rejected_cast() {
+    let mut env = Environment::builder(CATALOG.clone())
+        .with_player(0, |player| {
+            player
+                .with_hand(1, "wind")
+                .with_hand(2, "wind")
+                .with_hand(3, "ruby")
+                .with_deck(4, "ruby")
+        })
+        .with_player(1, |player| player.with_deck(51, "ruby"))
+        .build();
+    env.advance(0, None);
+
+    let report = env.advance(0, Some(Action::CastCard { card: id(3) }));
+    assert_eq!(
+        report.rejected.unwrap().reason,
+        UnavailableReason::InsufficientShards {
+            color: Color::RED,
+            required: 2,
+            available: 0
+        }
+    );
+
+    let mut env = Environment::builder(CATALOG.clone())
+        .with_player(0, |player| {
+            player
+                .with_hand(1, "wind")
+                .with_hand(2, "wind")
+                .with_hand(3, "ruby")
+                .with_deck(4, "ruby")
+                .with_shards(Color::RED, 10)
+        })
+        .with_player(1, |player| player.with_deck(51, "ruby"))
+        .build();
+    env.advance(0, None);
+
+    let report = env.advance(0, Some(Action::CastCard { card: id(1) }));
+    assert!(report.rejected.is_none());
+
+    let report = env.advance(0, Some(Action::CastCard { card: id(2) }));
+    assert_eq!(
+        report.rejected.unwrap().reason,
+        UnavailableReason::CreatureAlreadyFreeCasted
+    );
+}
+
+#[test]
+fn test_rejected_attack() {
+    let mut env = Environment::builder(CATALOG.clone())
+        .with_player(0, |player| {
+            player
+                .with_hand(1, "ruby")
+                .with_field(2, "ruby", FieldState::Active)
+                .with_field(3, "ruby", FieldState::Exhausted)
+                .with_deck(4, "ruby")
+        })
+        .with_player(1, |player| player.with_hand(51, "ruby"))
+        .build();
+    env.advance(0, None);
+
+    let report = env.advance(
+        0,
+        Some(Action::Attack {
+            attackers: vec![id(2), id(3)],
+        }),
+    );
+    assert_eq!(
+        report.rejected.unwrap().reason,
+        UnavailableReason::Exhausted { card: id(3) }
+    );
+
+    let report = env.advance(
+        0,
+        Some(Action::Attack {
+            attackers: vec![id(2)],
+        }),
+    );
+    assert!(report.rejected.is_none());
+
+    let report = env.advance(1, Some(Action::CastCard { card: id(51) }));
+    assert_eq!(
+        report.rejected.unwrap().reason,
+        UnavailableReason::WrongPhase {
+            phase: Phase::Block
+        }
+    );
+}
@@ -0,0 +1,70 @@
+use kodecks::{
+    action::Action,
+    color::Color,
+    env::Environment,
+    error::UnavailableReason,
+    history::History,
+    id::ObjectId,
+    profile::{DebugConfig, DebugFlags},
+    zone::Zone,
+};
+use kodecks_catalog::CATALOG;
+use std::sync::Arc;
+
+fn id(id: u32) -> ObjectId {
+    ObjectId::try_from(id).unwrap()
+}
+
+#[test]
+fn test_undo() {
+    let mut env = Arc::new(
+        Environment::builder(CATALOG.clone())
+            .with_debug(DebugConfig {
+                flags: DebugFlags::UNDO,
+                ..Default::default()
+            })
+            .with_player(0, |player| {
+                player.with_hand(1, "ruby").with_shards(Color::RED, 10)
+            })
+            .build(),
+    );
+    Arc::make_mut(&mut env).advance(0, None);
+
+    let mut history = History::default();
+    assert_eq!(
+        history.undo(&env, 0).err(),
+        Some(UnavailableReason::NothingToUndo)
+    );
+
+    let snapshot = env.clone();
+    Arc::make_mut(&mut env).advance(0, Some(Action::CastCard { card: id(1) }));
+    history.push(snapshot);
+    assert_eq!(env.state.find_zone(id(1)).unwrap().zone, Zone::Field);
+
+    let env = history.undo(&env, 0).ok().unwrap();
+    assert_eq!(env.state.find_zone(id(1)).unwrap().zone, Zone::Hand);
+    assert!(history.is_empty());
+    assert_eq!(
+        env.unavailable_reason(0, &Action::Undo),
+        Some(UnavailableReason::NothingToUndo)
+    );
+}
+
+#[test]
+fn test_undo_disabled() {
+    let mut env = Environment::builder(CATALOG.clone()).build();
+    env.advance(0, None);
+    let env = Arc::new(env);
+
+    let mut history = History::default();
+    history.push(env.clone());
+    assert!(history.is_empty());
+    assert_eq!(
+        history.undo(&env, 0).err(),
+        Some(UnavailableReason::UndoDisabled)
+    );
+    assert_eq!(
+        env.unavailable_reason(0, &Action::Undo),
+        Some(UnavailableReason::UndoDisabled)
+    );
+}
@@ -0,0 +1,249 @@
+use crate::message::GameEventKind;
+use kodecks::{
+    env::{LocalEnvironment, LocalGameState, LocalGameStateDelta},
+    error::DeltaError,
+};
+use std::collections::VecDeque;
+
+/// The maximum number of unacknowledged states before a full snapshot is sent again.
+const MAX_PENDING_STATES: usize = 8;
+
+/// The number of states a client keeps to apply deltas against.
+const MAX_HISTORY: usize = 16;
+
+/// Encodes state updates for a player as deltas relative to the last acknowledged state.
+#[derive(Debug, Clone, Default)]
+pub struct StateEncoder {
+    acknowledged: Option<LocalEnvironment>,
+    pending: VecDeque<(u64, LocalEnvironment)>,
+}
+
+impl StateEncoder {
+    pub fn encode(&mut self, state: LocalGameState) -> GameEventKind {
+        let checksum = state.env.checksum();
+        if self.pending.len() >= MAX_PENDING_STATES {
+            self.resync();
+        }
+        self.pending.push_back((checksum, state.env.clone()));
+
+        if let Some(base) = &self.acknowledged {
+            GameEventKind::StateDelta {
+                delta: Box::new(LocalGameStateDelta {
+                    env: state.env.diff(base),
+                    logs: state.logs,
+                    available_actions: state.available_actions,
+                }),
+                checksum,
+            }
+        } else {
+            GameEventKind::StateUpdated {
+                state: Box::new(state),
+                checksum,
+            }
+        }
+    }
+
+    pub fn acknowledge(&mut self, timestamp: u32, checksum: u64) {
+        if let Some(pos) = self
+            .pending
+            .iter()
+            .rposition(|(sum, env)| *sum == checksum && env.timestamp == timestamp)
+        {
+            self.acknowledged = self.pending.drain(..=pos).next_back().map(|(_, env)| env);
+        }
+    }
+
+    /// Forgets the acknowledged state so that the next update is a full snapshot.
+    pub fn resync(&mut self) {
+        self.acknowledged = None;
+        self.pending.clear();
+    }
+}
+
+/// Reconstructs full states from the updates produced by [`StateEncoder`].
+#[derive(Debug, Clone, Default)]
+pub struct StateDecoder {
+    history: VecDeque<(u64, LocalEnvironment)>,
+}
+
+impl StateDecoder {
+    pub fn push(&mut self, env: &LocalEnvironment) {
+        if self.history.len() >= MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back((env.checksum(), env.clone()));
+    }
+
+    pub fn apply(
+        &mut self,
+        delta: LocalGameStateDelta,
+        checksum: u64,
+    ) -> Result<LocalGameState, DeltaError> {
+        let (_, base) = self
+            .history
+            .iter()
+            .rev()
+            .find(|(sum, env)| *sum == delta.env.base_checksum && env.timestamp == delta.env.base)
+            .ok_or(DeltaError::BaseMismatch {
+                expected: delta.env.base,
+                actual: self
+                    .history
+                    .back()
+                    .map(|(_, env)| env.timestamp)
+                    .unwrap_or_default(),
+            })?;
+
+        let mut env = base.clone();
+        env.apply_delta(delta.env)?;
+        if env.checksum() != checksum {
+            return Err(DeltaError::ChecksumMismatch);
+        }
+        self.push(&env);
+
+        Ok(LocalGameState {
+            env,
+            logs: delta.logs,
+            available_actions: delta.available_actions,
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::env::Environment;
+    use kodecks_catalog::CATALOG;
+
+    /// Returns a state of the player 0 with the given timestamp and life.
+    fn state(timestamp: u32) -> LocalGameState {
+        let env = Environment::builder(CATALOG.clone())
+            .with_player(0, |player| player.with_hand(1, "wind"))
+            .with_player(1, |player| player)
+            .build();
+        let mut env = env.local(0);
+        env.timestamp = timestamp;
+        env.players.get_mut(0).stats.life = timestamp;
+        LocalGameState {
+            env,
+            logs: vec![],
+            available_actions: None,
+        }
+    }
+
+    /// Sends the state through the encoder and returns what the decoder makes of it.
+    fn send(
+        encoder: &mut StateEncoder,
+        decoder: &mut StateDecoder,
+        state: LocalGameState,
+    ) -> Result<LocalGameState, DeltaError> {
+        match encoder.encode(state) {
+            GameEventKind::StateUpdated { state, .. } => {
+                decoder.push(&state.env);
+                Ok(*state)
+            }
+            GameEventKind::StateDelta { delta, checksum } => decoder.apply(*delta, checksum),
+            event => panic!("unexpected event: {:?}", event),
+        }
+    }
+
+    #[test]
+    fn test_acknowledge() {
+        let mut encoder = StateEncoder::default();
+        let mut decoder = StateDecoder::default();
+
+        let first = send(&mut encoder, &mut decoder, state(1)).unwrap();
+        assert!(matches!(
+            encoder.encode(state(2)),
+            GameEventKind::StateUpdated { .. }
+        ));
+
+        // An unknown checksum does not change the base.
+        encoder.acknowledge(1, first.env.checksum() + 1);
+        assert!(matches!(
+            encoder.encode(state(3)),
+            GameEventKind::StateUpdated { .. }
+        ));
+
+        encoder.acknowledge(1, first.env.checksum());
+        let fourth = send(&mut encoder, &mut decoder, state(4)).unwrap();
+        assert_eq!(fourth.env.checksum(), state(4).env.checksum());
+
+        // Deltas are made against the latest acknowledged state.
+        encoder.acknowledge(4, fourth.env.checksum());
+        match encoder.encode(state(5)) {
+            GameEventKind::StateDelta { delta, .. } => assert_eq!(delta.env.base, 4),
+            event => panic!("unexpected event: {:?}", event),
+        }
+    }
+
+    #[test]
+    fn test_resync_unknown_base() {
+        let mut encoder = StateEncoder::default();
+        let mut decoder = StateDecoder::default();
+
+        let first = send(&mut encoder, &mut decoder, state(1)).unwrap();
+        encoder.acknowledge(1, first.env.checksum());
+
+        decoder.clear();
+        assert_eq!(
+            send(&mut encoder, &mut decoder, state(2)).unwrap_err(),
+            DeltaError::BaseMismatch {
+                expected: 1,
+                actual: 0
+            }
+        );
+
+        encoder.resync();
+        let third = send(&mut encoder, &mut decoder, state(3)).unwrap();
+        encoder.acknowledge(3, third.env.checksum());
+        let fourth = send(&mut encoder, &mut decoder, state(4)).unwrap();
+        assert_eq!(fourth.env.checksum(), state(4).env.checksum());
+    }
+
+    #[test]
+    fn test_pending_eviction() {
+        let mut encoder = StateEncoder::default();
+        let mut decoder = StateDecoder::default();
+
+        let first = send(&mut encoder, &mut decoder, state(1)).unwrap();
+        encoder.acknowledge(1, first.env.checksum());
+
+        // Too many unacknowledged states make the encoder start over with a snapshot.
+        for timestamp in 2..=MAX_PENDING_STATES as u32 + 1 {
+            assert!(matches!(
+                encoder.encode(state(timestamp)),
+                GameEventKind::StateDelta { .. }
+            ));
+        }
+        assert!(matches!(
+            encoder.encode(state(MAX_PENDING_STATES as u32 + 2)),
+            GameEventKind::StateUpdated { .. }
+        ));
+    }
+
+    #[test]
+    fn test_history_eviction() {
+        let mut decoder = StateDecoder::default();
+        for timestamp in 1..=MAX_HISTORY as u32 + 1 {
+            decoder.push(&state(timestamp).env);
+        }
+
+        // The oldest state is no longer available as a base.
+        let delta = |base: u32| LocalGameStateDelta {
+            env: state(100).env.diff(&state(base).env),
+            logs: vec![],
+            available_actions: None,
+        };
+        let checksum = state(100).env.checksum();
+        assert!(matches!(
+            decoder.apply(delta(1), checksum),
+            Err(DeltaError::BaseMismatch { expected: 1, .. })
+        ));
+        let state = decoder.apply(delta(2), checksum).unwrap();
+        assert_eq!(state.env.timestamp, 100);
+    }
+}
@@ -1,41 +1,156 @@
-use crate::message::{GameCommand, GameCommandKind, GameEvent, GameEventKind, Output};
+use crate::{
+    delta::StateEncoder,
+    message::{GameCommand, GameCommandKind, GameEvent, GameEventKind, Output},
+};
 use futures::{
     channel::mpsc::{Receiver, Sender},
     SinkExt, StreamExt,
 };
 use kodecks::{
     action::{Action, PlayerAvailableActions},
-    env::{Environment, LocalGameState},
-    profile::GameProfile,
+    env::{ActionRejected, Environment, LocalGameState, Report},
+    error::UnavailableReason,
+    history::History,
+    profile::{BotConfig, BotKind, DebugFlags, GameProfile},
+    scenario::{Puzzle, PuzzleScenario, Scenario, Tutorial, TutorialScenario},
 };
-use kodecks_bot::{Bot, DefaultBot};
+#[cfg(not(target_family = "wasm"))]
+use kodecks_bot::ExternalBot;
+use kodecks_bot::{Bot, BotView, ComputeBudget, DefaultBot, MctsBot, MctsConfig, ScriptedBot};
 use kodecks_catalog::CATALOG;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
+
+/// Bots give up searching after this time unless the profile sets a limit.
+const DEFAULT_BOT_TIME_LIMIT: Duration = Duration::from_secs(3);
 
 pub async fn start_game(
     log_id: String,
     profile: GameProfile,
-    mut receiver: Receiver<GameCommand>,
-    mut sender: Sender<Output>,
+    receiver: Receiver<GameCommand>,
+    sender: Sender<Output>,
 ) {
     let bots = profile.bots.clone();
-    let mut players = profile
+    let players = profile
         .players
         .iter()
         .enumerate()
-        .map(|(id, _)| PlayerData {
-            id: id as u8,
-            bot: if bots.iter().any(|bot| bot.player == id as u8) {
-                Some(DefaultBot::builder().build())
+        .map(|(id, _)| {
+            let bot = bots.iter().find(|bot| bot.player == id as u8).map(new_bot);
+            PlayerData::new(id as u8, bot)
+        })
+        .collect();
+
+    let env = Environment::new(profile, CATALOG.clone());
+    run_game(log_id, env, players, None, receiver, sender).await;
+}
+
+fn new_bot(config: &BotConfig) -> Box<dyn Bot + Send> {
+    let time_limit = config
+        .time_limit_ms
+        .map(|ms| Duration::from_millis(ms as u64))
+        .unwrap_or(DEFAULT_BOT_TIME_LIMIT);
+    // Processes cannot be spawned on the web, where the builtin bot plays instead.
+    #[cfg(not(target_family = "wasm"))]
+    if let BotKind::External { command, args } = &config.kind {
+        match ExternalBot::spawn(command, args) {
+            Ok(bot) => return Box::new(bot.with_timeout(time_limit)),
+            Err(err) => tracing::error!("Failed to start external bot {command}: {err}"),
+        }
+    }
+    let weights = config.weights.unwrap_or_default();
+    if let BotKind::Mcts = config.kind {
+        return Box::new(
+            MctsBot::builder()
+                .with_config(MctsConfig {
+                    time_budget: Some(time_limit),
+                    weights,
+                    ..Default::default()
+                })
+                .build(),
+        );
+    }
+    Box::new(
+        DefaultBot::builder()
+            .with_difficulty(config.difficulty)
+            .with_personality(config.personality)
+            .with_weights(weights)
+            .with_budget(ComputeBudget::with_time_limit(time_limit))
+            .build(),
+    )
+}
+
+pub async fn start_puzzle(
+    log_id: String,
+    puzzle: Puzzle,
+    receiver: Receiver<GameCommand>,
+    sender: Sender<Output>,
+) {
+    let players = (0..puzzle.players.len() as u8)
+        .map(|id| {
+            let bot: Option<Box<dyn Bot + Send>> = if id == PuzzleScenario::SOLVER {
+                None
             } else {
+                Some(Box::new(ScriptedBot::new(puzzle.script.clone())))
+            };
+            PlayerData::new(id, bot)
+        })
+        .collect();
+
+    let env = Environment::from_puzzle(&puzzle, CATALOG.clone());
+    let scenario = PuzzleScenario::new(puzzle);
+    run_game(
+        log_id,
+        env,
+        players,
+        Some(Box::new(scenario)),
+        receiver,
+        sender,
+    )
+    .await;
+}
+
+pub async fn start_tutorial(
+    log_id: String,
+    tutorial: Tutorial,
+    receiver: Receiver<GameCommand>,
+    sender: Sender<Output>,
+) {
+    let players = (0..tutorial.decks.len() as u8)
+        .map(|id| {
+            let bot: Option<Box<dyn Bot + Send>> = if id == TutorialScenario::LEARNER {
                 None
-            },
-            next_action: None,
+            } else {
+                Some(Box::new(ScriptedBot::new(tutorial.script.clone())))
+            };
+            PlayerData::new(id, bot)
         })
-        .collect::<Vec<_>>();
+        .collect();
+
+    let env = Environment::new(tutorial.profile(), CATALOG.clone());
+    let scenario = TutorialScenario::new(tutorial);
+    run_game(
+        log_id,
+        env,
+        players,
+        Some(Box::new(scenario)),
+        receiver,
+        sender,
+    )
+    .await;
+}
 
-    let mut env = Arc::new(Environment::new(profile, CATALOG.clone()));
+async fn run_game(
+    log_id: String,
+    env: Environment,
+    mut players: Vec<PlayerData>,
+    mut scenario: Option<Box<dyn Scenario>>,
+    mut receiver: Receiver<GameCommand>,
+    mut sender: Sender<Output>,
+) {
+    let mut env = Arc::new(env);
+    let mut history = History::default();
     let mut available_actions: Option<PlayerAvailableActions> = None;
+    let mut player_actions: Option<PlayerAvailableActions> = None;
     let mut player_in_action = env.state.players.player_in_turn().id;
 
     for player in &players {
@@ -57,16 +172,30 @@ pub async fn start_game(
         if let Some(available_actions) = &available_actions {
             if players[available_actions.player as usize].bot.is_none() {
                 if let Some(command) = receiver.next().await {
-                    match command.kind {
-                        GameCommandKind::NextAction { action } => {
-                            players[command.player as usize].next_action = Some(action);
-                        }
-                    }
+                    handle_command(
+                        &env,
+                        player_actions.as_ref(),
+                        &mut players,
+                        &mut sender,
+                        command,
+                    )
+                    .await;
                 }
             }
         }
 
         while !env.game_condition().is_ended() {
+            while let Ok(Some(command)) = receiver.try_next() {
+                handle_command(
+                    &env,
+                    player_actions.as_ref(),
+                    &mut players,
+                    &mut sender,
+                    command,
+                )
+                .await;
+            }
+
             let conceded = players
                 .iter()
                 .find(|data| matches!(data.next_action, Some(Action::Concede)))
@@ -75,12 +204,44 @@ pub async fn start_game(
                 (player, Some(Action::Concede))
             } else if let Some(available_actions) = &available_actions {
                 if let Some(bot) = players[player_in_action as usize].bot.as_mut() {
-                    let env = env.clone();
-                    (
-                        player_in_action,
-                        bot.compute_best_action(env, available_actions),
-                    )
+                    let view = BotView::new(&env, player_in_action);
+                    let action = if env.state.debug.flags.contains(DebugFlags::BOT_TRACE) {
+                        let trace = bot.compute_trace(&view, available_actions);
+                        let action = trace.chosen.clone();
+                        for player in players.iter().filter(|player| player.bot.is_none()) {
+                            let event = GameEvent {
+                                game_id: 0,
+                                player: player.id,
+                                event: GameEventKind::BotDecision {
+                                    trace: Box::new(trace.clone()),
+                                },
+                            };
+                            sender.send(Output::GameEvent(event)).await.unwrap();
+                        }
+                        action
+                    } else {
+                        bot.compute_best_action(&view, available_actions)
+                    };
+                    (player_in_action, action)
                 } else if let Some(action) = players[player_in_action as usize].next_action.take() {
+                    let restricted = scenario.is_some()
+                        && !matches!(action, Action::Concede)
+                        && player_actions.as_ref().is_some_and(|actions| {
+                            actions.player == player_in_action
+                                && !actions.validate(player_in_action, &action)
+                        });
+                    if restricted {
+                        let event = GameEvent {
+                            game_id: 0,
+                            player: player_in_action,
+                            event: GameEventKind::ActionRejected {
+                                action,
+                                reason: UnavailableReason::RestrictedByScenario,
+                            },
+                        };
+                        sender.send(Output::GameEvent(event)).await.unwrap();
+                        continue;
+                    }
                     (player_in_action, Some(action))
                 } else {
                     break;
@@ -89,14 +250,80 @@ pub async fn start_game(
                 (player_in_action, None)
             };
 
-            let report = Arc::make_mut(&mut env).process(player, next_action);
+            let report = if let Some(Action::Undo) = next_action {
+                match history.undo(&env, player) {
+                    Ok(snapshot) => {
+                        env = snapshot;
+                        for player in &mut players {
+                            player.encoder.resync();
+                        }
+                        env.report()
+                    }
+                    Err(reason) => Report {
+                        rejected: Some(ActionRejected {
+                            action: Action::Undo,
+                            reason,
+                        }),
+                        ..env.report()
+                    },
+                }
+            } else {
+                let snapshot = (next_action.is_some() && players[player as usize].bot.is_none())
+                    .then(|| env.clone());
+                let report = Arc::make_mut(&mut env).process(player, next_action.clone());
+                if let (Some(snapshot), None) = (snapshot, &report.rejected) {
+                    history.push(snapshot);
+                }
+                report
+            };
             available_actions.clone_from(&report.available_actions);
+            for data in &mut players {
+                if let Some(bot) = data.bot.as_mut() {
+                    let logs = report
+                        .logs
+                        .iter()
+                        .map(|log| log.clone().redacted(data.id))
+                        .collect::<Vec<_>>();
+                    bot.observe(&logs);
+                }
+            }
+
+            player_actions.clone_from(&report.available_actions);
+            if let Some(scenario) = &mut scenario {
+                if let (Some(action), None) = (&next_action, &report.rejected) {
+                    scenario.on_action(player, action);
+                }
+                let endgame = scenario.endgame(&env);
+                let env = Arc::make_mut(&mut env);
+                for (player, state) in endgame {
+                    env.state
+                        .players
+                        .get_mut(player)
+                        .endgame
+                        .get_or_insert(state);
+                }
+                player_actions = scenario.override_actions(env, player_actions.take());
+            }
+
+            if let Some(rejected) = report.rejected.clone() {
+                if players[player as usize].bot.is_none() {
+                    let event = GameEvent {
+                        game_id: 0,
+                        player,
+                        event: GameEventKind::ActionRejected {
+                            action: rejected.action,
+                            reason: rejected.reason,
+                        },
+                    };
+                    sender.send(Output::GameEvent(event)).await.unwrap();
+                }
+            }
 
             if let Some(available_actions) = &report.available_actions {
                 player_in_action = available_actions.player;
             }
 
-            for player in &players {
+            for player in &mut players {
                 if player.bot.is_none() {
                     let state = LocalGameState {
                         env: env.local(player.id),
@@ -105,17 +332,14 @@ pub async fn start_game(
                             .iter()
                             .map(|log| log.clone().redacted(player.id))
                             .collect(),
-                        available_actions: report
-                            .available_actions
+                        available_actions: player_actions
                             .clone()
                             .filter(|actions| actions.player == player.id),
                     };
                     let event = GameEvent {
                         game_id: 0,
                         player: player.id,
-                        event: GameEventKind::StateUpdated {
-                            state: Box::new(state),
-                        },
+                        event: player.encoder.encode(state),
                     };
 
                     sender.send(Output::GameEvent(event)).await.unwrap();
@@ -125,9 +349,57 @@ pub async fn start_game(
     }
 }
 
-#[derive(Debug)]
+async fn handle_command(
+    env: &Environment,
+    available_actions: Option<&PlayerAvailableActions>,
+    players: &mut [PlayerData],
+    sender: &mut Sender<Output>,
+    command: GameCommand,
+) {
+    let player = &mut players[command.player as usize];
+    match command.kind {
+        GameCommandKind::NextAction { action } => {
+            player.next_action = Some(action);
+        }
+        GameCommandKind::Acknowledge {
+            timestamp,
+            checksum,
+        } => {
+            player.encoder.acknowledge(timestamp, checksum);
+        }
+        GameCommandKind::Resync => {
+            player.encoder.resync();
+            let state = LocalGameState {
+                env: env.local(player.id),
+                logs: vec![],
+                available_actions: available_actions
+                    .cloned()
+                    .filter(|actions| actions.player == player.id),
+            };
+            let event = GameEvent {
+                game_id: 0,
+                player: player.id,
+                event: player.encoder.encode(state),
+            };
+            sender.send(Output::GameEvent(event)).await.unwrap();
+        }
+    }
+}
+
 struct PlayerData {
     id: u8,
-    bot: Option<DefaultBot>,
+    bot: Option<Box<dyn Bot + Send>>,
     next_action: Option<Action>,
+    encoder: StateEncoder,
+}
+
+impl PlayerData {
+    fn new(id: u8, bot: Option<Box<dyn Bot + Send>>) -> Self {
+        Self {
+            id,
+            bot,
+            next_action: None,
+            encoder: StateEncoder::default(),
+        }
+    }
 }
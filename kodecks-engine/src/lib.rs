@@ -1,7 +1,9 @@
+pub mod delta;
 pub mod game;
 pub mod login;
 pub mod message;
 pub mod room;
+pub mod spectate;
 pub mod user;
 pub mod version;
 pub mod well_known;
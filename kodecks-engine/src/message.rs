@@ -1,8 +1,18 @@
-use crate::{room::RoomConfig, user::UserId};
+use crate::{
+    room::RoomConfig,
+    spectate::{GameSummary, SpectateMode},
+    user::UserId,
+};
 use bincode::{Decode, Encode};
 use kodecks::{
-    action::Action, env::LocalGameState, error::Error, player::PlayerConfig, profile::GameProfile,
+    action::Action,
+    env::{LocalGameState, LocalGameStateDelta},
+    error::{Error, UnavailableReason},
+    player::PlayerConfig,
+    profile::GameProfile,
+    scenario::{Puzzle, Tutorial},
 };
+use kodecks_bot::BotDecisionTrace;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Encode, Decode)]
@@ -18,10 +28,23 @@ pub enum Command {
         log_id: String,
         profile: GameProfile,
     },
+    StartPuzzle {
+        log_id: String,
+        puzzle: Box<Puzzle>,
+    },
+    StartTutorial {
+        log_id: String,
+        tutorial: Box<Tutorial>,
+    },
     CreateRoom {
         config: RoomConfig,
         host_player: PlayerConfig,
     },
+    ListGames,
+    Spectate {
+        game_id: u32,
+        mode: SpectateMode,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
@@ -47,12 +70,15 @@ pub struct GameCommand {
 #[derive(Debug, Clone, Encode, Decode)]
 pub enum GameCommandKind {
     NextAction { action: Action },
+    Acknowledge { timestamp: u32, checksum: u64 },
+    Resync,
 }
 
 #[derive(Debug, Clone, Encode, Decode)]
 pub enum Output {
     GameEvent(GameEvent),
     RoomEvent(RoomEvent),
+    GameList(Vec<GameSummary>),
     Error(Error),
 }
 
@@ -79,7 +105,31 @@ pub struct GameEvent {
 
 #[derive(Debug, Clone, Encode, Decode)]
 pub enum GameEventKind {
-    Created { log_id: String },
-    StateUpdated { state: Box<LocalGameState> },
-    PlayerThinking { thinking: u8, timeout: Option<u32> },
+    Created {
+        log_id: String,
+    },
+    Spectating {
+        log_id: String,
+        mode: SpectateMode,
+    },
+    StateUpdated {
+        state: Box<LocalGameState>,
+        checksum: u64,
+    },
+    StateDelta {
+        delta: Box<LocalGameStateDelta>,
+        checksum: u64,
+    },
+    PlayerThinking {
+        thinking: u8,
+        timeout: Option<u32>,
+    },
+    ActionRejected {
+        action: Action,
+        reason: UnavailableReason,
+    },
+    /// Sent to the other players when the profile enables bot traces.
+    BotDecision {
+        trace: Box<BotDecisionTrace>,
+    },
 }
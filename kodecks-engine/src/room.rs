@@ -15,4 +15,6 @@ pub struct RoomConfig {
 pub enum RoomType {
     #[default]
     RandomMatch,
+    /// A friendly match in which players may undo their actions.
+    Casual,
 }
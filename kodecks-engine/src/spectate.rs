@@ -0,0 +1,34 @@
+use crate::user::UserId;
+use bincode::{Decode, Encode};
+use kodecks::player::Viewer;
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Debug, Clone, Default, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SpectateMode {
+    /// Hands and decks are hidden from the spectator.
+    #[default]
+    Observer,
+    /// Every card is visible, but updates are delayed so that the information
+    /// cannot be relayed to the players.
+    ///
+    /// Servers only allow this mode for the users configured as casters.
+    Omniscient,
+}
+
+impl From<SpectateMode> for Viewer {
+    fn from(mode: SpectateMode) -> Self {
+        match mode {
+            SpectateMode::Observer => Viewer::Observer,
+            SpectateMode::Omniscient => Viewer::Omniscient,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct GameSummary {
+    pub game_id: u32,
+    pub players: Vec<UserId>,
+}
@@ -1,7 +1,7 @@
 #![cfg(target_family = "wasm")]
 
 use crate::{
-    game::start_game,
+    game::{start_game, start_puzzle, start_tutorial},
     message::{Command, Input, Output},
     Connection,
 };
@@ -101,6 +101,21 @@ pub async fn EngineReactor(mut scope: ReactorScope<Vec<u8>, Vec<u8>>) {
                             sender = Some(command_sender);
                             spawn_local(start_game(log_id, profile, receiver, event_send.clone()));
                         }
+                        Input::Command(Command::StartPuzzle { log_id, puzzle }) => {
+                            let (command_sender, receiver) = mpsc::channel(256);
+                            sender = Some(command_sender);
+                            spawn_local(start_puzzle(log_id, *puzzle, receiver, event_send.clone()));
+                        }
+                        Input::Command(Command::StartTutorial { log_id, tutorial }) => {
+                            let (command_sender, receiver) = mpsc::channel(256);
+                            sender = Some(command_sender);
+                            spawn_local(start_tutorial(
+                                log_id,
+                                *tutorial,
+                                receiver,
+                                event_send.clone(),
+                            ));
+                        }
                         Input::GameCommand(session_command) => {
                             if let Some(sender) = &mut sender {
                                 sender.send(session_command).await.unwrap();
@@ -1,5 +1,5 @@
 use crate::{
-    game::{GameList, PlayerData},
+    game::{GameList, PlayerData, SpectatorData},
     room::RoomList,
     session::Session,
     token::Token,
@@ -14,12 +14,14 @@ use kodecks::error::Error;
 use kodecks_catalog::CATALOG;
 use kodecks_engine::{
     message::{Command, Input, Output, RoomCommand, RoomCommandKind, RoomEvent, RoomEventKind},
+    spectate::SpectateMode,
     user::UserId,
 };
 use semver::{BuildMetadata, Version, VersionReq};
 use serde::Serialize;
 use std::{
     cmp::Ordering,
+    collections::HashSet,
     sync::{Arc, Mutex},
 };
 
@@ -30,6 +32,7 @@ pub struct AppState {
     tokens: DashMap<Token, UserId>,
     rooms: Mutex<RoomList>,
     games: Mutex<GameList>,
+    casters: HashSet<UserId>,
 }
 
 impl AppState {
@@ -39,6 +42,23 @@ impl AppState {
             tokens: DashMap::new(),
             rooms: Mutex::new(RoomList::default()),
             games: Mutex::new(GameList::default()),
+            casters: HashSet::new(),
+        }
+    }
+
+    /// Allows the users to spectate games in [`SpectateMode::Omniscient`].
+    pub fn with_casters<I>(mut self, casters: I) -> Self
+    where
+        I: IntoIterator<Item = UserId>,
+    {
+        self.casters.extend(casters);
+        self
+    }
+
+    pub fn can_spectate(&self, user_id: &UserId, mode: SpectateMode) -> bool {
+        match mode {
+            SpectateMode::Observer => true,
+            SpectateMode::Omniscient => self.casters.contains(user_id),
         }
     }
 
@@ -151,7 +171,9 @@ impl AppState {
                 rooms
                     .random_match_rooms()
                     .filter(|room| {
-                        room.owner != *user_id && room.config.regulation == config.regulation
+                        room.owner != *user_id
+                            && room.config.regulation == config.regulation
+                            && room.config.room_type == config.room_type
                     })
                     .for_each(|room| {
                         self.send(
@@ -171,6 +193,7 @@ impl AppState {
                 if let Some(room) = rooms.get(&room_id) {
                     let owner = room.owner.clone();
                     let regulation = room.config.regulation.clone();
+                    let room_type = room.config.room_type;
                     let mut players = vec![];
                     if let Some(room) = rooms.remove_by_owner(&owner) {
                         if let Some(sender) = self
@@ -189,7 +212,29 @@ impl AppState {
                         }
                     }
                     if players.len() == 2 {
-                        self.games.lock().unwrap().create(regulation, players);
+                        self.games
+                            .lock()
+                            .unwrap()
+                            .create(regulation, room_type, players);
+                    }
+                }
+            }
+            Input::Command(Command::ListGames) => {
+                let games = self.games.lock().unwrap().summaries();
+                self.send(user_id, Output::GameList(games));
+            }
+            Input::Command(Command::Spectate { game_id, mode }) => {
+                if !self.can_spectate(user_id, mode) {
+                    self.send(user_id, Output::Error(Error::PermissionDenied));
+                    return;
+                }
+                if let Some(sender) = self
+                    .session_from_id(user_id)
+                    .and_then(|session| session.event_sender().clone())
+                {
+                    let spectator = SpectatorData::new(user_id.clone(), mode, sender);
+                    if let Err(err) = self.games.lock().unwrap().spectate(game_id, spectator) {
+                        self.send(user_id, Output::Error(err));
                     }
                 }
             }
@@ -211,3 +256,20 @@ pub struct Status {
 pub async fn status(State(state): State<Arc<AppState>>) -> (StatusCode, Json<Status>) {
     (StatusCode::OK, Json(state.status()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_omniscient_spectators() {
+        let caster = UserId::from("caster".to_string());
+        let user = UserId::from("user".to_string());
+        let state = AppState::new().with_casters([caster.clone()]);
+
+        assert!(state.can_spectate(&user, SpectateMode::Observer));
+        assert!(!state.can_spectate(&user, SpectateMode::Omniscient));
+        assert!(state.can_spectate(&caster, SpectateMode::Observer));
+        assert!(state.can_spectate(&caster, SpectateMode::Omniscient));
+    }
+}
@@ -2,14 +2,19 @@ use futures_util::future;
 use kodecks::{
     action::{Action, PlayerAvailableActions},
     env::{Environment, LocalGameState},
+    error::{Error, UnavailableReason},
+    history::History,
     log::GameLog,
-    player::PlayerConfig,
-    profile::GameProfile,
+    player::{PlayerConfig, Viewer},
+    profile::{DebugConfig, DebugFlags, GameProfile},
     regulation::Regulation,
 };
 use kodecks_catalog::CATALOG;
 use kodecks_engine::{
+    delta::StateEncoder,
     message::{GameCommand, GameCommandKind, GameEvent, GameEventKind, Output},
+    room::RoomType,
+    spectate::{GameSummary, SpectateMode},
     user::UserId,
 };
 use std::{
@@ -26,6 +31,7 @@ use tracing::warn;
 
 const CHANNEL_TIMEOUT: Duration = Duration::from_secs(1);
 const PLAYER_THINKING_INTERVAL: Duration = Duration::from_secs(5);
+const OMNISCIENT_SPECTATOR_DELAY: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Default)]
 pub struct GameList {
@@ -35,7 +41,12 @@ pub struct GameList {
 }
 
 impl GameList {
-    pub fn create(&mut self, regulation: Regulation, players: Vec<PlayerData>) -> u32 {
+    pub fn create(
+        &mut self,
+        regulation: Regulation,
+        room_type: RoomType,
+        players: Vec<PlayerData>,
+    ) -> u32 {
         let id = self.counter;
         self.counter += 1;
 
@@ -43,7 +54,7 @@ impl GameList {
             self.players.insert(player.user_id.clone(), id);
         }
 
-        let game = Game::new(id, regulation, players);
+        let game = Game::new(id, regulation, room_type, players);
         self.games.insert(id, game);
         id
     }
@@ -54,6 +65,39 @@ impl GameList {
         }
     }
 
+    pub fn summaries(&self) -> Vec<GameSummary> {
+        let mut games = self
+            .games
+            .iter()
+            .filter(|(_, game)| !game.sender.is_closed())
+            .map(|(id, game)| GameSummary {
+                game_id: *id,
+                players: game
+                    .players
+                    .iter()
+                    .map(|player| player.user_id.clone())
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        games.sort_by_key(|game| game.game_id);
+        games
+    }
+
+    pub fn spectate(&self, game_id: u32, spectator: SpectatorData) -> Result<(), Error> {
+        if self.players.get(&spectator.user_id) == Some(&game_id) {
+            return Err(Error::SpectatingOwnGame);
+        }
+        self.games
+            .get(&game_id)
+            .filter(|game| !game.spectator_sender.is_closed())
+            .ok_or(Error::GameNotFound)
+            .and_then(|game| {
+                game.spectator_sender
+                    .try_send(spectator)
+                    .map_err(|_| Error::GameNotFound)
+            })
+    }
+
     pub fn cleanup(&mut self) {
         self.games.retain(|_, game| !game.sender.is_closed());
         self.players.retain(|_, id| self.games.contains_key(id));
@@ -90,6 +134,7 @@ pub struct PlayerData {
     sender: Sender<Output>,
     next_actions: VecDeque<Action>,
     consecutive_timeouts: u8,
+    encoder: StateEncoder,
 }
 
 impl PlayerData {
@@ -100,21 +145,87 @@ impl PlayerData {
             sender,
             next_actions: VecDeque::new(),
             consecutive_timeouts: 0,
+            encoder: StateEncoder::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SpectatorData {
+    user_id: UserId,
+    mode: SpectateMode,
+    sender: Sender<Output>,
+}
+
+impl SpectatorData {
+    pub fn new(user_id: UserId, mode: SpectateMode, sender: Sender<Output>) -> Self {
+        Self {
+            user_id,
+            mode,
+            sender,
         }
     }
 }
 
+struct Spectator {
+    user_id: UserId,
+    viewer: Viewer,
+    sender: Sender<(Instant, Output)>,
+}
+
+impl Spectator {
+    fn new(data: SpectatorData) -> Self {
+        let delay = match data.mode {
+            SpectateMode::Observer => Duration::ZERO,
+            SpectateMode::Omniscient => OMNISCIENT_SPECTATOR_DELAY,
+        };
+        let user_id = data.user_id.clone();
+        let (sender, mut receiver) = mpsc::channel::<(Instant, Output)>(1024);
+        tokio::spawn(async move {
+            while let Some((timestamp, output)) = receiver.recv().await {
+                time::sleep_until(timestamp + delay).await;
+                if data.sender.send(output).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            user_id,
+            viewer: data.mode.into(),
+            sender,
+        }
+    }
+
+    fn send(&self, output: Output) -> bool {
+        self.sender.try_send((Instant::now(), output)).is_ok()
+    }
+}
+
 #[derive(Debug)]
 pub struct Game {
     sender: Sender<GameCommand>,
+    spectator_sender: Sender<SpectatorData>,
     players: Vec<PlayerData>,
 }
 
 impl Game {
-    pub fn new(game_id: u32, regulation: Regulation, players: Vec<PlayerData>) -> Self {
+    pub fn new(
+        game_id: u32,
+        regulation: Regulation,
+        room_type: RoomType,
+        players: Vec<PlayerData>,
+    ) -> Self {
         let player_configs = players.iter().map(|player| player.config.clone()).collect();
+        let debug = match room_type {
+            RoomType::RandomMatch => None,
+            RoomType::Casual => Some(DebugConfig {
+                flags: DebugFlags::UNDO,
+                ..Default::default()
+            }),
+        };
         let profile = GameProfile {
             regulation,
+            debug,
             players: player_configs,
             ..Default::default()
         };
@@ -125,16 +236,22 @@ impl Game {
             nanoid::nanoid!()
         );
 
-        let (sender, receiver) = mpsc::channel(1);
+        let (sender, receiver) = mpsc::channel(16);
+        let (spectator_sender, spectator_receiver) = mpsc::channel(16);
         tokio::spawn(Self::start_game(
             game_id,
             log_id,
             profile,
             players.clone(),
             receiver,
+            spectator_receiver,
         ));
 
-        Self { sender, players }
+        Self {
+            sender,
+            spectator_sender,
+            players,
+        }
     }
 
     pub fn handle_command(&self, user_id: &UserId, command: GameCommand) {
@@ -151,10 +268,13 @@ impl Game {
         profile: GameProfile,
         mut players: Vec<PlayerData>,
         mut receiver: Receiver<GameCommand>,
+        mut spectator_receiver: Receiver<SpectatorData>,
     ) {
         let regulation = profile.regulation.clone();
+        let mut spectators: Vec<Spectator> = vec![];
 
         let mut env = Arc::new(Environment::new(profile, CATALOG.clone()));
+        let mut history = History::default();
         let mut available_actions: Option<PlayerAvailableActions> = None;
         let mut player_in_action = env.state.players.player_in_turn().id;
 
@@ -194,20 +314,53 @@ impl Game {
                     command = receiver.recv() => {
                         if let Some(command) = command {
                             let player = &mut players[command.player as usize];
-                            let GameCommandKind::NextAction { action } = command.kind;
-                            player.next_actions.push_back(action);
-                            player.consecutive_timeouts = 0;
+                            match command.kind {
+                                GameCommandKind::NextAction { action } => {
+                                    player.next_actions.push_back(action);
+                                    player.consecutive_timeouts = 0;
+                                }
+                                GameCommandKind::Acknowledge { timestamp, checksum } => {
+                                    player.encoder.acknowledge(timestamp, checksum);
+                                }
+                                GameCommandKind::Resync => {
+                                    player.encoder.resync();
+                                    let state = LocalGameState {
+                                        env: env.local(command.player),
+                                        logs: vec![],
+                                        available_actions: env
+                                            .last_available_actions()
+                                            .cloned()
+                                            .filter(|actions| actions.player == command.player),
+                                    };
+                                    let event = GameEvent {
+                                        game_id,
+                                        player: command.player,
+                                        event: player.encoder.encode(state),
+                                    };
+                                    let result = player
+                                        .sender
+                                        .send_timeout(Output::GameEvent(event), CHANNEL_TIMEOUT)
+                                        .await;
+                                    if let Err(err) = result {
+                                        warn!("failed to send event: {}", err);
+                                        player.next_actions.push_back(Action::Concede);
+                                    }
+                                }
+                            }
                         } else {
                             return;
                         }
                     }
+                    Some(spectator) = spectator_receiver.recv() => {
+                        Self::join_spectator(&mut spectators, game_id, &log_id, &env, spectator);
+                    }
                     _ = action_timeout => {
                         let player = &mut players[player_in_action as usize];
                         player.consecutive_timeouts += 1;
                         let action = if player.consecutive_timeouts >= regulation.max_consecutive_timeouts {
                             Action::Concede
                         } else {
-                            available_actions.actions.default_action(&env).unwrap_or(Action::Concede)
+                            available_actions.actions.default_action(&env.local(player_in_action)).unwrap_or(Action::Concede)
                         };
                         player.next_actions.push_back(action);
                     }
@@ -243,16 +396,35 @@ impl Game {
                     } else if matches!(players[1].next_actions.front(), Some(Action::Concede)) {
                         (1, Some(Action::Concede))
                     } else if let Some(available_actions) = &available_actions {
-                        let next_actions =
-                            &mut players[available_actions.player as usize].next_actions;
-                        while let Some(action) = next_actions.front() {
-                            if available_actions.actions.validate(action) {
+                        let player = available_actions.player;
+                        while let Some(action) = players[player as usize].next_actions.front() {
+                            if available_actions.actions.validate(action)
+                                || matches!(action, Action::Undo)
+                            {
                                 break;
-                            } else {
-                                next_actions.pop_front();
+                            }
+                            let action = players[player as usize].next_actions.pop_front().unwrap();
+                            let reason = env
+                                .unavailable_reason(player, &action)
+                                .unwrap_or(UnavailableReason::InvalidAction);
+                            let event = GameEvent {
+                                game_id,
+                                player,
+                                event: GameEventKind::ActionRejected { action, reason },
+                            };
+                            let result = players[player as usize]
+                                .sender
+                                .send_timeout(Output::GameEvent(event), CHANNEL_TIMEOUT)
+                                .await;
+                            if let Err(err) = result {
+                                warn!("failed to send event: {}", err);
+                                players[player as usize].next_actions.clear();
+                                players[player as usize]
+                                    .next_actions
+                                    .push_back(Action::Concede);
                             }
                         }
-                        if let Some(action) = next_actions.pop_front() {
+                        if let Some(action) = players[player as usize].next_actions.pop_front() {
                             (player_in_action, Some(action))
                         } else {
                             break;
@@ -261,9 +433,68 @@ impl Game {
                         (player_in_action, None)
                     };
 
-                let report = Arc::make_mut(&mut env).process(player, next_action);
+                let report = if let Some(Action::Undo) = next_action {
+                    match history.undo(&env, player) {
+                        Ok(snapshot) => {
+                            env = snapshot;
+                            for player in &mut players {
+                                player.encoder.resync();
+                            }
+                            env.report()
+                        }
+                        Err(reason) => {
+                            let event = GameEvent {
+                                game_id,
+                                player,
+                                event: GameEventKind::ActionRejected {
+                                    action: Action::Undo,
+                                    reason,
+                                },
+                            };
+                            let result = players[player as usize]
+                                .sender
+                                .send_timeout(Output::GameEvent(event), CHANNEL_TIMEOUT)
+                                .await;
+                            if let Err(err) = result {
+                                warn!("failed to send event: {}", err);
+                                players[player as usize]
+                                    .next_actions
+                                    .push_back(Action::Concede);
+                            }
+                            continue;
+                        }
+                    }
+                } else {
+                    let snapshot = next_action.is_some().then(|| env.clone());
+                    let report = Arc::make_mut(&mut env).process(player, next_action);
+                    if let (Some(snapshot), None) = (snapshot, &report.rejected) {
+                        history.push(snapshot);
+                    }
+                    report
+                };
                 available_actions.clone_from(&report.available_actions);
 
+                if let Some(rejected) = report.rejected.clone() {
+                    let event = GameEvent {
+                        game_id,
+                        player,
+                        event: GameEventKind::ActionRejected {
+                            action: rejected.action,
+                            reason: rejected.reason,
+                        },
+                    };
+                    let result = players[player as usize]
+                        .sender
+                        .send_timeout(Output::GameEvent(event), CHANNEL_TIMEOUT)
+                        .await;
+                    if let Err(err) = result {
+                        warn!("failed to send event: {}", err);
+                        players[player as usize]
+                            .next_actions
+                            .push_back(Action::Concede);
+                    }
+                }
+
                 if let Some(available_actions) = &report.available_actions {
                     player_in_action = available_actions.player;
                     next_action_deadline = Instant::now() + regulation.action_timeout;
@@ -293,9 +524,7 @@ impl Game {
                     let event = GameEvent {
                         game_id,
                         player: player.id,
-                        event: GameEventKind::StateUpdated {
-                            state: Box::new(state),
-                        },
+                        event: players[player.id as usize].encoder.encode(state),
                     };
                     let result = players[player.id as usize]
                         .sender
@@ -308,7 +537,239 @@ impl Game {
                             .push_back(Action::Concede);
                     }
                 }
+
+                while let Ok(spectator) = spectator_receiver.try_recv() {
+                    Self::join_spectator(&mut spectators, game_id, &log_id, &env, spectator);
+                }
+                spectators.retain(|spectator| {
+                    let state = LocalGameState {
+                        env: env.local(spectator.viewer),
+                        logs: report
+                            .logs
+                            .iter()
+                            .map(|log| log.clone().redacted(spectator.viewer))
+                            .collect(),
+                        available_actions: None,
+                    };
+                    spectator.send(Output::GameEvent(GameEvent {
+                        game_id,
+                        player: state.env.player,
+                        event: GameEventKind::StateUpdated {
+                            checksum: state.env.checksum(),
+                            state: Box::new(state),
+                        },
+                    }))
+                });
+            }
+        }
+    }
+
+    /// Adds the spectator and sends the current state.
+    ///
+    /// A user who spectates the game again replaces the previous spectator,
+    /// which lets a client request the full state after a checksum mismatch.
+    fn join_spectator(
+        spectators: &mut Vec<Spectator>,
+        game_id: u32,
+        log_id: &str,
+        env: &Environment,
+        data: SpectatorData,
+    ) {
+        spectators.retain(|spectator| spectator.user_id != data.user_id);
+        let mode = data.mode;
+        let spectator = Spectator::new(data);
+        let state = LocalGameState {
+            env: env.local(spectator.viewer),
+            logs: vec![],
+            available_actions: None,
+        };
+        let player = state.env.player;
+        let joined = spectator.send(Output::GameEvent(GameEvent {
+            game_id,
+            player,
+            event: GameEventKind::Spectating {
+                log_id: log_id.to_string(),
+                mode,
+            },
+        })) && spectator.send(Output::GameEvent(GameEvent {
+            game_id,
+            player,
+            event: GameEventKind::StateUpdated {
+                checksum: state.env.checksum(),
+                state: Box::new(state),
+            },
+        }));
+        if joined {
+            spectators.push(spectator);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::id::ObjectId;
+    use kodecks_catalog::decks::starter_deck;
+
+    #[tokio::test]
+    async fn test_reject_invalid_action() {
+        let config = PlayerConfig {
+            deck: starter_deck(&CATALOG),
+        };
+        let (sender0, mut receiver0) = mpsc::channel(64);
+        let (sender1, mut receiver1) = mpsc::channel(64);
+        let players = vec![
+            PlayerData::new(UserId::from("alice".to_string()), config.clone(), sender0),
+            PlayerData::new(UserId::from("bob".to_string()), config, sender1),
+        ];
+        let mut games = GameList::default();
+        let game_id = games.create(Regulation::STANDARD, RoomType::RandomMatch, players);
+
+        // Only the player in action has the action taken; the other one is kept queued.
+        let id = ObjectId::try_from(9999).unwrap();
+        let action = Action::CastCard { card: id };
+        for (player, user_id) in [(0, "alice"), (1, "bob")] {
+            games.handle_command(
+                &UserId::from(user_id.to_string()),
+                GameCommand {
+                    game_id,
+                    player,
+                    kind: GameCommandKind::NextAction {
+                        action: action.clone(),
+                    },
+                },
+            );
+        }
+
+        let rejected = time::timeout(Duration::from_secs(10), async {
+            loop {
+                let output = select! {
+                    Some(output) = receiver0.recv() => output,
+                    Some(output) = receiver1.recv() => output,
+                };
+                if let Output::GameEvent(GameEvent {
+                    event: GameEventKind::ActionRejected { action, reason },
+                    ..
+                }) = output
+                {
+                    return (action, reason);
+                }
             }
+        })
+        .await
+        .expect("the action should be rejected");
+        assert!(matches!(rejected.0, Action::CastCard { card } if card == id));
+    }
+
+    #[tokio::test]
+    async fn test_undo() {
+        for (room_type, expected) in [
+            (RoomType::RandomMatch, UnavailableReason::UndoDisabled),
+            (RoomType::Casual, UnavailableReason::NothingToUndo),
+        ] {
+            let config = PlayerConfig {
+                deck: starter_deck(&CATALOG),
+            };
+            let (sender0, mut receiver0) = mpsc::channel(64);
+            let (sender1, mut receiver1) = mpsc::channel(64);
+            let players = vec![
+                PlayerData::new(UserId::from("alice".to_string()), config.clone(), sender0),
+                PlayerData::new(UserId::from("bob".to_string()), config, sender1),
+            ];
+            let mut games = GameList::default();
+            let game_id = games.create(Regulation::STANDARD, room_type, players);
+
+            for (player, user_id) in [(0, "alice"), (1, "bob")] {
+                games.handle_command(
+                    &UserId::from(user_id.to_string()),
+                    GameCommand {
+                        game_id,
+                        player,
+                        kind: GameCommandKind::NextAction {
+                            action: Action::Undo,
+                        },
+                    },
+                );
+            }
+
+            let reason = time::timeout(Duration::from_secs(10), async {
+                loop {
+                    let output = select! {
+                        Some(output) = receiver0.recv() => output,
+                        Some(output) = receiver1.recv() => output,
+                    };
+                    if let Output::GameEvent(GameEvent {
+                        event:
+                            GameEventKind::ActionRejected {
+                                action: Action::Undo,
+                                reason,
+                            },
+                        ..
+                    }) = output
+                    {
+                        return reason;
+                    }
+                }
+            })
+            .await
+            .expect("the undo should be rejected");
+            assert_eq!(reason, expected, "{room_type:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spectate() {
+        let config = PlayerConfig {
+            deck: starter_deck(&CATALOG),
+        };
+        let (sender0, _receiver0) = mpsc::channel(64);
+        let (sender1, _receiver1) = mpsc::channel(64);
+        let players = vec![
+            PlayerData::new(UserId::from("alice".to_string()), config.clone(), sender0),
+            PlayerData::new(UserId::from("bob".to_string()), config, sender1),
+        ];
+        let mut games = GameList::default();
+        let game_id = games.create(Regulation::STANDARD, RoomType::RandomMatch, players);
+
+        let (sender, _receiver) = mpsc::channel(64);
+        let player = SpectatorData::new(
+            UserId::from("alice".to_string()),
+            SpectateMode::Observer,
+            sender,
+        );
+        assert_eq!(
+            games.spectate(game_id, player),
+            Err(Error::SpectatingOwnGame)
+        );
+
+        // Spectating again replaces the previous spectator and resends the state.
+        let user_id = UserId::from("carol".to_string());
+        let (first, mut first_receiver) = mpsc::channel(64);
+        let (second, mut second_receiver) = mpsc::channel(64);
+        for sender in [first, second] {
+            let spectator = SpectatorData::new(user_id.clone(), SpectateMode::Observer, sender);
+            games.spectate(game_id, spectator).unwrap();
         }
+
+        time::timeout(Duration::from_secs(10), async {
+            while first_receiver.recv().await.is_some() {}
+        })
+        .await
+        .expect("the first spectator should be closed");
+
+        let output = time::timeout(Duration::from_secs(10), async {
+            loop {
+                if let Some(Output::GameEvent(GameEvent {
+                    event: GameEventKind::StateUpdated { state, checksum },
+                    ..
+                })) = second_receiver.recv().await
+                {
+                    return (state, checksum);
+                }
+            }
+        })
+        .await
+        .expect("the state should be sent again");
+        assert_eq!(output.0.env.checksum(), output.1);
     }
 }
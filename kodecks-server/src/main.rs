@@ -9,6 +9,7 @@ use http::{
     Method, StatusCode,
 };
 use kodecks::error::Error;
+use kodecks_engine::user::UserId;
 use std::{net::SocketAddr, sync::Arc};
 use tokio::try_join;
 use tower_governor::{governor::GovernorConfigBuilder, GovernorLayer};
@@ -61,7 +62,15 @@ async fn main() -> anyhow::Result<()> {
             .unwrap(),
     );
 
-    let state = Arc::new(app::AppState::new());
+    // Comma-separated ids of the users allowed to spectate with every card visible.
+    let casters = std::env::var("CASTERS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| UserId::from(id.to_string()))
+        .collect::<Vec<_>>();
+    let state = Arc::new(app::AppState::new().with_casters(casters));
 
     let authorized = Router::new()
         .route("/logout", get(login::logout))
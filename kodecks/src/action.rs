@@ -1,6 +1,6 @@
 use crate::{
     command::ActionCommand,
-    env::Environment,
+    env::LocalEnvironment,
     id::ObjectId,
     message::{Message, MessageDialog},
 };
@@ -120,6 +120,7 @@ impl AvailableActionList {
             }
             Action::EndTurn => self.0.iter().any(|action| matches!(action, AvailableAction::EndTurn)),
             Action::Continue => self.0.iter().any(|action| matches!(action, AvailableAction::Continue)),
+            Action::Undo => false,
             _ => true,
         }
     }
@@ -188,15 +189,18 @@ impl AvailableActionList {
             .any(|action| matches!(action, AvailableAction::Continue))
     }
 
-    pub fn default_action(&self, env: &Environment) -> Option<Action> {
+    /// Returns the action taken when the player does not choose one.
+    ///
+    /// Only the state visible to the player is read.
+    pub fn default_action(&self, env: &LocalEnvironment) -> Option<Action> {
         for action in self.iter() {
             match action {
                 AvailableAction::SelectCard { cards, .. } => {
                     let oldest_card = cards
                         .iter()
-                        .filter_map(|card| env.state.find_card(*card).ok())
-                        .min_by_key(|card| card.timestamp())
-                        .map(|card| card.id());
+                        .filter_map(|card| env.find_card(*card).ok())
+                        .min_by_key(|card| card.timestamp)
+                        .map(|card| card.id);
                     if let Some(card) = oldest_card {
                         return Some(Action::SelectCard { card });
                     }
@@ -252,4 +256,5 @@ pub enum Action {
     Concede,
     Continue,
     DebugCommand { commands: Vec<ActionCommand> },
+    Undo,
 }
@@ -8,7 +8,7 @@ use crate::{
     event::EventFilter,
     id::{CardId, ObjectId, ObjectIdCounter, TimedObjectId},
     linear::Linear,
-    player::{PlayerMask, PlayerZone},
+    player::{PlayerMask, PlayerZone, Viewer},
     score::Score,
     zone::Zone,
 };
@@ -18,6 +18,7 @@ use num::Zero;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+#[derive(Clone)]
 pub struct Card {
     id: ObjectId,
     owner: u8,
@@ -25,10 +26,10 @@ pub struct Card {
     controller: u8,
     archetype: Arc<CardArchetype>,
     style: u8,
-    computed: ComputedAttribute,
+    computed: Arc<ComputedAttribute>,
     flags: ComputedFlags,
     event_filter: EventFilter,
-    effect: Box<dyn Effect>,
+    effect: Arc<dyn Effect>,
     revealed: PlayerMask,
     timestamp: u32,
     is_token: bool,
@@ -49,7 +50,7 @@ impl Card {
         owner: u8,
     ) -> Self {
         let effect = (archetype.effect)();
-        let computed = (&*archetype).into();
+        let computed = Arc::new((&*archetype).into());
         Self {
             id: counter.allocate(item.base_id),
             owner,
@@ -60,16 +61,48 @@ impl Card {
             computed,
             flags: ComputedFlags::empty(),
             event_filter: effect.event_filter(),
-            effect,
+            effect: effect.into(),
             revealed: PlayerMask::default(),
             timestamp: 0,
             is_token: false,
         }
     }
 
+    /// Restores a card from its snapshot, with its effect in the initial state.
+    ///
+    /// The computed attributes of the snapshot are kept until they are computed again.
+    pub(crate) fn from_snapshot(
+        snapshot: &CardSnapshot,
+        archetype: Arc<CardArchetype>,
+        zone: PlayerZone,
+    ) -> Self {
+        let effect = (archetype.effect)();
+        let computed = snapshot
+            .computed
+            .clone()
+            .unwrap_or_else(|| (&*archetype).into());
+        let mut card = Self {
+            id: snapshot.id,
+            owner: snapshot.owner,
+            zone,
+            controller: snapshot.controller,
+            archetype,
+            style: snapshot.style,
+            computed: Arc::new(computed.clone()),
+            flags: ComputedFlags::empty(),
+            event_filter: effect.event_filter(),
+            effect: effect.into(),
+            revealed: snapshot.revealed,
+            timestamp: snapshot.timestamp,
+            is_token: snapshot.is_token,
+        };
+        card.set_computed(computed);
+        card
+    }
+
     pub fn new_token(id: ObjectId, archetype: Arc<CardArchetype>, owner: u8) -> Self {
         let effect = (archetype.effect)();
-        let computed = (&*archetype).into();
+        let computed = Arc::new((&*archetype).into());
         Self {
             id,
             owner,
@@ -81,7 +114,7 @@ impl Card {
             flags: ComputedFlags::empty(),
             event_filter: effect.event_filter(),
             revealed: PlayerMask::default(),
-            effect,
+            effect: effect.into(),
             timestamp: 0,
             is_token: true,
         }
@@ -125,11 +158,13 @@ impl Card {
     }
 
     pub fn set_computed(&mut self, computed: ComputedAttribute) {
-        self.computed = computed;
+        if *self.computed != computed {
+            self.computed = Arc::new(computed);
+        }
 
         let mut flags = ComputedFlags::empty();
         let stealth = self.zone.zone == Zone::Field
-            && self.computed.abilities.contains(&KeywordAbility::Stealth);
+            && self.computed.abilities().contains(&KeywordAbility::Stealth);
         flags.set(ComputedFlags::TARGETABLE, !stealth);
         self.flags = flags;
     }
@@ -146,9 +181,10 @@ impl Card {
         self.event_filter
     }
 
-    pub fn effect(&self) -> Box<dyn Effect> {
-        #[allow(clippy::deref_addrof)]
-        dyn_clone::clone_box(&**&self.effect)
+    /// Returns the effect shared between clones of the card.
+    /// Use [`Card::set_effect`] to store a modified copy.
+    pub fn effect(&self) -> &(dyn Effect + 'static) {
+        &*self.effect
     }
 
     pub fn revealed(&self) -> PlayerMask {
@@ -156,7 +192,7 @@ impl Card {
     }
 
     pub fn set_effect(&mut self, effect: Box<dyn Effect>) {
-        self.effect = effect;
+        self.effect = effect.into();
     }
 
     pub fn timestamp(&self) -> u32 {
@@ -179,7 +215,7 @@ impl Card {
             controller: self.controller,
             owner: self.owner,
             revealed: self.revealed,
-            computed: Some(self.computed.clone()),
+            computed: Some((*self.computed).clone()),
             timestamp: self.timestamp,
             is_token: self.is_token,
         }
@@ -191,26 +227,6 @@ impl Card {
     }
 }
 
-impl Clone for Card {
-    fn clone(&self) -> Self {
-        Self {
-            id: self.id,
-            owner: self.owner,
-            zone: self.zone,
-            controller: self.controller,
-            archetype: self.archetype.clone(),
-            style: self.style,
-            computed: self.computed.clone(),
-            flags: self.flags,
-            event_filter: self.event_filter,
-            effect: self.effect(),
-            revealed: self.revealed,
-            timestamp: self.timestamp,
-            is_token: self.is_token,
-        }
-    }
-}
-
 impl CardId for Card {
     fn id(&self) -> ObjectId {
         self.id
@@ -238,16 +254,7 @@ impl AsMut<Card> for Card {
 
 impl Score for Card {
     fn score(&self) -> i32 {
-        self.computed.abilities.score()
-            + self.computed.anon_abilities.score()
-            + self.computed.power.map(|power| power.value()).unwrap_or(0) as i32 / 100
-            + self
-                .computed
-                .shields
-                .map(|shields| shields.value())
-                .unwrap_or(0) as i32
-                * 2
-            + if self.computed.is_creature() { 1 } else { 0 }
+        self.computed.score()
     }
 }
 
@@ -255,7 +262,7 @@ pub fn safe_name(name: &str) -> Result<String, idna::Errors> {
     idna::domain_to_ascii(&name.replace(' ', "-"))
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
 pub struct CardSnapshot {
     pub id: ObjectId,
     pub archetype_id: ArchetypeId,
@@ -281,6 +288,12 @@ impl CardId for CardSnapshot {
     }
 }
 
+impl Score for CardSnapshot {
+    fn score(&self) -> i32 {
+        self.computed.as_ref().map(Score::score).unwrap_or(0)
+    }
+}
+
 impl CardSnapshot {
     pub fn new(archetype: &CardArchetype) -> Self {
         Self {
@@ -296,9 +309,20 @@ impl CardSnapshot {
         }
     }
 
-    pub fn redacted(self, viewer: u8) -> Self {
-        if self.revealed.contains(viewer) {
-            self
+    pub fn redacted(self, viewer: impl Into<Viewer>) -> Self {
+        let viewer = viewer.into();
+        if viewer.can_see(self.revealed) {
+            if viewer.is_privileged(self.owner) {
+                self
+            } else {
+                Self {
+                    computed: self.computed.map(|mut computed| {
+                        computed.modifiers.clear();
+                        computed
+                    }),
+                    ..self
+                }
+            }
         } else {
             Self {
                 archetype_id: ArchetypeId::new(""),
@@ -321,7 +345,7 @@ impl CardSnapshot {
     }
 
     pub fn power(&self) -> Option<Linear<u32>> {
-        self.computed.as_ref().and_then(|c| c.power)
+        self.computed.as_ref().and_then(|c| c.power())
     }
 
     pub fn shields(&self) -> Option<Linear<u8>> {
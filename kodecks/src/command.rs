@@ -61,6 +61,9 @@ pub enum ActionCommand {
     BreakShield {
         target: TimedObjectId,
     },
+    DrawCard {
+        player: u8,
+    },
 }
 
 impl ActionCommand {
@@ -84,7 +87,7 @@ impl ActionCommand {
                 }
                 let piercing = source
                     .computed()
-                    .abilities
+                    .abilities()
                     .contains(&KeywordAbility::Piercing);
                 let shields = current_target
                     .computed()
@@ -192,6 +195,9 @@ impl ActionCommand {
                     card: current_target.id(),
                 }])])
             }
+            ActionCommand::DrawCard { player } => {
+                Ok(vec![OpcodeList::new(vec![Opcode::DrawCard { player }])])
+            }
         }
     }
 }
@@ -1,9 +1,11 @@
 use crate::{
     ability::{AbilityList, AnonymousAbility, KeywordAbility},
-    archetype::{CardArchetype, CardType, CreatureType},
+    archetype::{ArchetypeId, CardArchetype, CardType, CreatureType},
     card::Card,
     color::Color,
+    id::ObjectId,
     linear::Linear,
+    score::Score,
     zone::CardZone,
 };
 use bincode::{
@@ -13,6 +15,7 @@ use bincode::{
     BorrowDecode, Decode, Encode,
 };
 use bitflags::bitflags;
+use num::NumCast;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
@@ -23,13 +26,15 @@ pub struct ComputedAttribute {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub creature_type: Option<CreatureType>,
     #[serde(default, skip_serializing_if = "AbilityList::is_empty")]
-    pub abilities: AbilityList<KeywordAbility>,
+    abilities: AbilityList<KeywordAbility>,
     #[serde(default, skip_serializing_if = "AbilityList::is_empty")]
     pub anon_abilities: AbilityList<AnonymousAbility>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub power: Option<Linear<u32>>,
+    power: Option<Linear<u32>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shields: Option<Linear<u8>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<ComputedModifier>,
 }
 
 impl From<&CardArchetype> for ComputedAttribute {
@@ -43,10 +48,21 @@ impl From<&CardArchetype> for ComputedAttribute {
             anon_abilities: archetype.attribute.anon_abilities.iter().copied().collect(),
             power: archetype.attribute.power.map(Linear::from),
             shields: archetype.attribute.shields.map(Linear::from),
+            modifiers: vec![],
         }
     }
 }
 
+impl Score for ComputedAttribute {
+    fn score(&self) -> i32 {
+        self.abilities.score()
+            + self.anon_abilities.score()
+            + self.current_power() as i32 / 100
+            + self.current_shields() as i32 * 2
+            + if self.is_creature() { 1 } else { 0 }
+    }
+}
+
 impl ComputedAttribute {
     pub fn is_creature(&self) -> bool {
         matches!(self.card_type, CardType::Creature)
@@ -56,6 +72,14 @@ impl ComputedAttribute {
         matches!(self.card_type, CardType::Hex)
     }
 
+    pub fn abilities(&self) -> &AbilityList<KeywordAbility> {
+        &self.abilities
+    }
+
+    pub fn power(&self) -> Option<Linear<u32>> {
+        self.power
+    }
+
     pub fn current_power(&self) -> u32 {
         self.power.map(|power| power.value()).unwrap_or(0)
     }
@@ -63,6 +87,115 @@ impl ComputedAttribute {
     pub fn current_shields(&self) -> u8 {
         self.shields.map(|shields| shields.value()).unwrap_or(0)
     }
+
+    /// Adds to the cost and records the change as a modifier of the source.
+    pub fn add_cost<N>(&mut self, source: &Card, add: N)
+    where
+        N: NumCast,
+    {
+        let before = self.cost.value();
+        self.cost.add(add);
+        let after = self.cost.value();
+        if before != after {
+            self.record(source, ModifierKind::Cost { before, after });
+        }
+    }
+
+    /// Adds to the power and records the change as a modifier of the source.
+    pub fn add_power<N>(&mut self, source: &Card, add: N)
+    where
+        N: NumCast,
+    {
+        self.modify_power(source, |power| power.add(add));
+    }
+
+    /// Multiplies the power and records the change as a modifier of the source.
+    pub fn mul_power<N>(&mut self, source: &Card, mul: N)
+    where
+        N: NumCast,
+    {
+        self.modify_power(source, |power| power.mul(mul));
+    }
+
+    /// Sets the base power and records the change as a modifier of the source.
+    pub fn assign_power(&mut self, source: &Card, value: u32) {
+        self.modify_power(source, |power| power.assign(value));
+    }
+
+    /// Adds to the shields and records the change as a modifier of the source.
+    pub fn add_shields<N>(&mut self, source: &Card, add: N)
+    where
+        N: NumCast,
+    {
+        if let Some(shields) = &mut self.shields {
+            let before = shields.value();
+            shields.add(add);
+            let after = shields.value();
+            if before != after {
+                self.record(source, ModifierKind::Shields { before, after });
+            }
+        }
+    }
+
+    /// Grants the ability and records it as a modifier of the source.
+    pub fn add_ability(&mut self, source: &Card, ability: KeywordAbility) {
+        if !self.abilities.contains(&ability) {
+            self.abilities.add(ability);
+            self.record(source, ModifierKind::AbilityGranted { ability });
+        }
+    }
+
+    /// Removes the ability and records it as a modifier of the source.
+    pub fn remove_ability(&mut self, source: &Card, ability: KeywordAbility) {
+        if self.abilities.contains(&ability) {
+            self.abilities.remove(ability);
+            self.record(source, ModifierKind::AbilityRemoved { ability });
+        }
+    }
+
+    fn modify_power<F>(&mut self, source: &Card, f: F)
+    where
+        F: FnOnce(&mut Linear<u32>),
+    {
+        if let Some(power) = &mut self.power {
+            let before = power.value();
+            f(power);
+            let after = power.value();
+            if before != after {
+                self.record(source, ModifierKind::Power { before, after });
+            }
+        }
+    }
+
+    fn record(&mut self, source: &Card, kind: ModifierKind) {
+        self.modifiers.push(ComputedModifier {
+            effect: None,
+            source: source.id(),
+            archetype_id: source.archetype().id,
+            kind,
+        });
+    }
+}
+
+/// A change made to a [`ComputedAttribute`] by a continuous effect.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct ComputedModifier {
+    /// The continuous effect that made the change, if it was applied from one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effect: Option<ObjectId>,
+    pub source: ObjectId,
+    pub archetype_id: ArchetypeId,
+    pub kind: ModifierKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+#[serde(tag = "name", rename_all = "snake_case")]
+pub enum ModifierKind {
+    Cost { before: u8, after: u8 },
+    Power { before: u32, after: u32 },
+    Shields { before: u8, after: u8 },
+    AbilityGranted { ability: KeywordAbility },
+    AbilityRemoved { ability: KeywordAbility },
 }
 
 bitflags! {
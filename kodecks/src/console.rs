@@ -0,0 +1,203 @@
+//! A small command language for building [`ActionCommand`]s by hand.
+//!
+//! Commands are separated by `;` and take the following forms:
+//!
+//! - `damage <player> <amount>`
+//! - `destroy <card>`
+//! - `return <card>`
+//! - `break <card>`
+//! - `token <player> <archetype>`
+//! - `shards <player> <color> <amount>`
+//! - `draw <player> [count]` (up to [`MAX_DRAW_COUNT`] cards)
+//!
+//! A player is `p0`, `p1`, `0`, `1`, `me` or `opponent`.
+//! A card is `#<id>` or the name of a card on the field,
+//! and an archetype is its id, name or safe name.
+
+use crate::{
+    archetype::CardArchetype,
+    card::CardSnapshot,
+    catalog::Catalog,
+    color::Color,
+    command::ActionCommand,
+    env::LocalEnvironment,
+    error::ConsoleError,
+    event::EventReason,
+    id::{CardId, ObjectId},
+};
+use std::sync::Arc;
+
+/// Used for the tokens in parsed commands.
+/// The environment assigns a fresh id when the command is processed.
+pub const TOKEN_ID_PLACEHOLDER: u32 = u32::MAX;
+
+/// The largest number of cards a single `draw` command may draw.
+pub const MAX_DRAW_COUNT: usize = 10;
+
+/// Parses the input into commands, resolving names through the catalog and the state.
+pub fn parse(
+    input: &str,
+    env: &LocalEnvironment,
+    catalog: &Catalog,
+) -> Result<Vec<ActionCommand>, ConsoleError> {
+    let mut commands = vec![];
+    for line in input.split(';') {
+        let args = line.split_whitespace().collect::<Vec<_>>();
+        if let Some((name, args)) = args.split_first() {
+            commands.extend(parse_command(name, args, env, catalog)?);
+        }
+    }
+    Ok(commands)
+}
+
+fn parse_command(
+    name: &str,
+    args: &[&str],
+    env: &LocalEnvironment,
+    catalog: &Catalog,
+) -> Result<Vec<ActionCommand>, ConsoleError> {
+    let missing = |argument: &'static str| ConsoleError::MissingArgument {
+        command: name.to_string(),
+        argument,
+    };
+    let arg = |index: usize, argument: &'static str| {
+        args.get(index).copied().ok_or_else(|| missing(argument))
+    };
+    // Names may contain spaces, so they take the remaining arguments.
+    let rest = |index: usize, argument: &'static str| {
+        args.get(index..)
+            .filter(|rest| !rest.is_empty())
+            .map(|rest| rest.join(" "))
+            .ok_or_else(|| missing(argument))
+    };
+
+    match name.to_ascii_lowercase().as_str() {
+        "damage" => Ok(vec![ActionCommand::InflictDamage {
+            target: parse_player(arg(0, "player")?, env)?,
+            amount: parse_number(arg(1, "amount")?)?,
+        }]),
+        "destroy" => {
+            let card = find_card(&rest(0, "card")?, env, catalog)?;
+            Ok(vec![ActionCommand::DestroyCard {
+                source: card.id(),
+                target: card.timed_id(),
+                reason: EventReason::Effect,
+            }])
+        }
+        "return" => {
+            let card = find_card(&rest(0, "card")?, env, catalog)?;
+            Ok(vec![ActionCommand::ReturnCardToHand {
+                source: card.id(),
+                target: card.timed_id(),
+                reason: EventReason::Effect,
+            }])
+        }
+        "break" => {
+            let card = find_card(&rest(0, "card")?, env, catalog)?;
+            Ok(vec![ActionCommand::BreakShield {
+                target: card.timed_id(),
+            }])
+        }
+        "token" => {
+            let player = parse_player(arg(0, "player")?, env)?;
+            let name = rest(1, "archetype")?;
+            let archetype =
+                find_archetype(&name, catalog).ok_or(ConsoleError::UnknownArchetype { name })?;
+            Ok(vec![ActionCommand::GenerateCardToken {
+                token: ObjectId::try_from(TOKEN_ID_PLACEHOLDER).unwrap(),
+                archetype: archetype.id,
+                player,
+            }])
+        }
+        "shards" => {
+            let player = parse_player(arg(0, "player")?, env)?;
+            let color = arg(1, "color")?;
+            let color = color
+                .parse::<Color>()
+                .ok()
+                .filter(|color| !color.is_empty())
+                .ok_or_else(|| ConsoleError::UnknownColor {
+                    color: color.to_string(),
+                })?;
+            let amount = parse_number(arg(2, "amount")?)?;
+            // Shard logs require a source card.
+            let source = env
+                .players
+                .get(player)
+                .cards()
+                .next()
+                .ok_or(ConsoleError::NoSourceCard { player })?;
+            Ok(vec![ActionCommand::GenerateShards {
+                player,
+                source: source.id(),
+                color,
+                amount,
+            }])
+        }
+        "draw" => {
+            let player = parse_player(arg(0, "player")?, env)?;
+            let count = args.get(1).map(|n| parse_number(n)).unwrap_or(Ok(1))?;
+            if count > MAX_DRAW_COUNT {
+                return Err(ConsoleError::InvalidNumber {
+                    value: count.to_string(),
+                });
+            }
+            Ok(vec![ActionCommand::DrawCard { player }; count])
+        }
+        _ => Err(ConsoleError::UnknownCommand {
+            command: name.to_string(),
+        }),
+    }
+}
+
+fn parse_player(arg: &str, env: &LocalEnvironment) -> Result<u8, ConsoleError> {
+    let player = match arg.to_ascii_lowercase().as_str() {
+        "me" => Some(env.player),
+        "opponent" => Some(env.next_id(env.player)),
+        id => id.strip_prefix('p').unwrap_or(id).parse::<u8>().ok(),
+    };
+    player
+        .filter(|player| env.players.iter().any(|p| p.id == *player))
+        .ok_or_else(|| ConsoleError::UnknownPlayer {
+            player: arg.to_string(),
+        })
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str) -> Result<T, ConsoleError> {
+    arg.parse().map_err(|_| ConsoleError::InvalidNumber {
+        value: arg.to_string(),
+    })
+}
+
+fn find_archetype<'a>(name: &str, catalog: &'a Catalog) -> Option<&'a Arc<CardArchetype>> {
+    catalog.iter().find(|archetype| {
+        archetype.id.as_str().eq_ignore_ascii_case(name)
+            || archetype.name.eq_ignore_ascii_case(name)
+            || archetype.safe_name.eq_ignore_ascii_case(name)
+    })
+}
+
+fn find_card<'a>(
+    name: &str,
+    env: &'a LocalEnvironment,
+    catalog: &Catalog,
+) -> Result<&'a CardSnapshot, ConsoleError> {
+    if let Some(id) = name.strip_prefix('#') {
+        let id = parse_number::<u32>(id)?;
+        return ObjectId::try_from(id)
+            .ok()
+            .and_then(|id| env.find_card(id).ok())
+            .ok_or_else(|| ConsoleError::UnknownCard {
+                name: name.to_string(),
+            });
+    }
+    let archetype = find_archetype(name, catalog).map(|archetype| archetype.id);
+    env.players
+        .iter()
+        .flat_map(|player| player.field.iter())
+        .map(|item| &item.card)
+        .find(|card| Some(card.archetype_id) == archetype)
+        .ok_or_else(|| ConsoleError::UnknownCard {
+            name: name.to_string(),
+        })
+}
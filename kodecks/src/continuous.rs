@@ -1,5 +1,5 @@
 use crate::{
-    ability::{AbilityList, PlayerAbility},
+    ability::{AbilityList, KeywordAbility, PlayerAbility},
     card::Card,
     computed::ComputedAttribute,
     condition::Condition,
@@ -11,8 +11,29 @@ use dyn_clone::DynClone;
 use std::sync::Arc;
 use tracing::error;
 
+/// The order in which continuous effects are applied to a card.
+///
+/// Effects in an earlier layer are always applied before effects in a later layer,
+/// regardless of their timestamps. Within a layer, effects are applied in timestamp
+/// order unless one depends on another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ContinuousLayer {
+    /// Changes to color, card type and creature type.
+    Type,
+    /// Granting or removing abilities.
+    Abilities,
+    /// Setting the base power with [`Linear::assign`](crate::linear::Linear::assign).
+    PowerSet,
+    /// Modifying the power with [`Linear::mul`](crate::linear::Linear::mul)
+    /// and [`Linear::add`](crate::linear::Linear::add).
+    PowerModify,
+    /// Modifying the shields.
+    Shields,
+}
+
 #[derive(Clone)]
 pub struct ContinuousItem {
+    id: ObjectId,
     source: ObjectId,
     timestamp: u32,
     func: Arc<Box<dyn ContinuousEffect>>,
@@ -22,6 +43,7 @@ pub struct ContinuousItem {
 impl fmt::Debug for ContinuousItem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ContinuousEffect")
+            .field("id", &self.id)
             .field("source", &self.source)
             .field("timestamp", &self.timestamp)
             .finish()
@@ -29,6 +51,22 @@ impl fmt::Debug for ContinuousItem {
 }
 
 pub trait ContinuousEffect: Send + Sync + DynClone {
+    /// The layer this effect is applied in.
+    fn layer(&self) -> ContinuousLayer;
+
+    /// Keyword abilities this effect may grant to a card.
+    fn grants(&self) -> &[KeywordAbility] {
+        &[]
+    }
+
+    /// Keyword abilities this effect reads to decide what it does.
+    ///
+    /// An effect that requires an ability granted by another effect in the same layer
+    /// is applied after that effect, even if it has an earlier timestamp.
+    fn requires(&self) -> &[KeywordAbility] {
+        &[]
+    }
+
     fn apply_card(
         &mut self,
         _state: &GameState,
@@ -52,18 +90,23 @@ pub trait ContinuousEffect: Send + Sync + DynClone {
 dyn_clone::clone_trait_object!(ContinuousEffect);
 
 impl ContinuousItem {
-    pub fn new<F, C>(source: &Card, effect: F, condition: C) -> Self
+    pub fn new<F, C>(id: ObjectId, source: &Card, effect: F, condition: C) -> Self
     where
         F: ContinuousEffect + 'static,
         C: Condition + 'static,
     {
         Self {
+            id,
             source: source.id(),
             timestamp: source.timestamp(),
             func: Arc::new(Box::new(effect)),
             condition: Arc::new(Box::new(condition)),
         }
     }
+
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -89,28 +132,62 @@ impl ContinuousEffectList {
 
     pub fn apply_card(&mut self, state: &GameState, card: &Card) -> ComputedAttribute {
         let mut computed = ComputedAttribute::from(&**card.archetype());
-        for effect in self.effects.iter_mut().rev() {
-            if let Err(err) = state
-                .find_card(effect.source)
-                .map_err(|err| err.into())
-                .and_then(|source| {
-                    dyn_clone::arc_make_mut(&mut effect.func).apply_card(
-                        state,
-                        source,
-                        card,
-                        &mut computed,
-                    )
-                })
-            {
+        for index in self.application_order() {
+            let effect = &mut self.effects[index];
+            let source = match state.find_card(effect.source) {
+                Ok(source) => source,
+                Err(err) => {
+                    error!("Failed to apply continuous effect: {:?}", err);
+                    continue;
+                }
+            };
+            let recorded = computed.modifiers.len();
+            if let Err(err) = dyn_clone::arc_make_mut(&mut effect.func).apply_card(
+                state,
+                source,
+                card,
+                &mut computed,
+            ) {
                 error!("Failed to apply continuous effect: {:?}", err);
             }
+            for modifier in &mut computed.modifiers[recorded..] {
+                modifier.effect = Some(effect.id);
+            }
         }
         computed
     }
 
+    fn application_order(&self) -> Vec<usize> {
+        let mut order = (0..self.effects.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| {
+            let effect = &self.effects[index];
+            (effect.func.layer(), effect.timestamp)
+        });
+
+        let mut start = 0;
+        while start < order.len() {
+            let layer = self.effects[order[start]].func.layer();
+            let end = order[start..]
+                .iter()
+                .position(|&index| self.effects[index].func.layer() != layer)
+                .map_or(order.len(), |len| start + len);
+            sort_by_dependencies(&mut order[start..end], |a, b| {
+                let granted = self.effects[b].func.grants();
+                self.effects[a]
+                    .func
+                    .requires()
+                    .iter()
+                    .any(|ability| granted.contains(ability))
+            });
+            start = end;
+        }
+        order
+    }
+
     pub fn apply_player(&mut self, state: &GameState, player: u8) -> AbilityList<PlayerAbility> {
         let mut abilities = AbilityList::new();
-        for effect in self.effects.iter_mut().rev() {
+        for index in self.application_order() {
+            let effect = &mut self.effects[index];
             if let Err(err) = dyn_clone::arc_make_mut(&mut effect.func).apply_player(
                 state,
                 player,
@@ -126,3 +203,316 @@ impl ContinuousEffectList {
         self.effects.retain(|effect| effect.condition.is_met(state));
     }
 }
+
+/// Reorders `items` so that each item comes after the items it depends on.
+///
+/// The relative order of independent items is preserved. Items that depend on each
+/// other are kept in their original order, and if the remaining items form a longer
+/// dependency cycle, the first of them is taken as is.
+fn sort_by_dependencies<T, F>(items: &mut [T], depends_on: F)
+where
+    T: Copy + PartialEq,
+    F: Fn(T, T) -> bool,
+{
+    let mut pending = items.to_vec();
+    for slot in items.iter_mut() {
+        let next = pending
+            .iter()
+            .position(|&a| {
+                !pending
+                    .iter()
+                    .any(|&b| a != b && depends_on(a, b) && !depends_on(b, a))
+            })
+            .unwrap_or(0);
+        *slot = pending.remove(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        archetype::{ArchetypeId, CardArchetype, CardAttribute, CardType},
+        catalog::Catalog,
+        computed::ModifierKind,
+        condition::OnField,
+        env::Environment,
+        field::FieldState,
+        id::ObjectId,
+    };
+    use std::sync::LazyLock;
+
+    fn creature() -> &'static CardArchetype {
+        static CARD: LazyLock<CardArchetype> = LazyLock::new(|| CardArchetype {
+            id: ArchetypeId::new("crea"),
+            name: "Creature".to_string(),
+            safe_name: "creature".to_string(),
+            attribute: CardAttribute {
+                card_type: CardType::Creature,
+                power: Some(300),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        &CARD
+    }
+
+    fn id(id: u32) -> ObjectId {
+        ObjectId::try_from(id).unwrap()
+    }
+
+    /// Builds a game with three creatures on the field, one per id.
+    fn environment() -> Environment {
+        Environment::builder(Arc::new(Catalog::new(&[creature])))
+            .with_player(0, |player| {
+                player
+                    .with_field(1, "crea", FieldState::Active)
+                    .with_field(2, "crea", FieldState::Active)
+                    .with_field(3, "crea", FieldState::Active)
+            })
+            .with_player(1, |player| player)
+            .build()
+    }
+
+    fn item<F>(env: &Environment, source: u32, timestamp: u32, effect: F) -> ContinuousItem
+    where
+        F: ContinuousEffect + 'static,
+    {
+        let mut source = env.state.find_card(id(source)).unwrap().clone();
+        source.set_timestamp(timestamp);
+        ContinuousItem::new(id(100 + timestamp), &source, effect, OnField(source.id()))
+    }
+
+    #[derive(Clone)]
+    struct SetPower(u32);
+
+    impl ContinuousEffect for SetPower {
+        fn layer(&self) -> ContinuousLayer {
+            ContinuousLayer::PowerSet
+        }
+
+        fn apply_card(
+            &mut self,
+            _state: &GameState,
+            source: &Card,
+            _target: &Card,
+            computed: &mut ComputedAttribute,
+        ) -> anyhow::Result<()> {
+            computed.assign_power(source, self.0);
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct AddPower(u32);
+
+    impl ContinuousEffect for AddPower {
+        fn layer(&self) -> ContinuousLayer {
+            ContinuousLayer::PowerModify
+        }
+
+        fn apply_card(
+            &mut self,
+            _state: &GameState,
+            source: &Card,
+            _target: &Card,
+            computed: &mut ComputedAttribute,
+        ) -> anyhow::Result<()> {
+            computed.add_power(source, self.0);
+            Ok(())
+        }
+    }
+
+    /// Doubles the power only if it has already reached the threshold.
+    #[derive(Clone)]
+    struct DoubleIfAtLeast(u32);
+
+    impl ContinuousEffect for DoubleIfAtLeast {
+        fn layer(&self) -> ContinuousLayer {
+            ContinuousLayer::PowerModify
+        }
+
+        fn apply_card(
+            &mut self,
+            _state: &GameState,
+            source: &Card,
+            _target: &Card,
+            computed: &mut ComputedAttribute,
+        ) -> anyhow::Result<()> {
+            if computed.current_power() >= self.0 {
+                computed.mul_power(source, 2);
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct Grant(KeywordAbility);
+
+    impl ContinuousEffect for Grant {
+        fn layer(&self) -> ContinuousLayer {
+            ContinuousLayer::Abilities
+        }
+
+        fn grants(&self) -> &[KeywordAbility] {
+            std::slice::from_ref(&self.0)
+        }
+
+        fn apply_card(
+            &mut self,
+            _state: &GameState,
+            source: &Card,
+            _target: &Card,
+            computed: &mut ComputedAttribute,
+        ) -> anyhow::Result<()> {
+            computed.add_ability(source, self.0);
+            Ok(())
+        }
+    }
+
+    /// Grants piercing to a card that has toxic.
+    #[derive(Clone)]
+    struct PiercingIfToxic;
+
+    impl ContinuousEffect for PiercingIfToxic {
+        fn layer(&self) -> ContinuousLayer {
+            ContinuousLayer::Abilities
+        }
+
+        fn grants(&self) -> &[KeywordAbility] {
+            &[KeywordAbility::Piercing]
+        }
+
+        fn requires(&self) -> &[KeywordAbility] {
+            &[KeywordAbility::Toxic]
+        }
+
+        fn apply_card(
+            &mut self,
+            _state: &GameState,
+            source: &Card,
+            _target: &Card,
+            computed: &mut ComputedAttribute,
+        ) -> anyhow::Result<()> {
+            if computed.abilities().contains(&KeywordAbility::Toxic) {
+                computed.add_ability(source, KeywordAbility::Piercing);
+            }
+            Ok(())
+        }
+    }
+
+    /// Grants a player ability, but only if the player has no abilities yet.
+    #[derive(Clone)]
+    struct GrantIfNone(PlayerAbility);
+
+    impl ContinuousEffect for GrantIfNone {
+        fn layer(&self) -> ContinuousLayer {
+            ContinuousLayer::Abilities
+        }
+
+        fn apply_player(
+            &mut self,
+            _state: &GameState,
+            _player: u8,
+            abilities: &mut AbilityList<PlayerAbility>,
+        ) -> anyhow::Result<()> {
+            if abilities.is_empty() {
+                abilities.add(self.0);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_layer_order() {
+        let env = environment();
+        let target = env.state.find_card(id(1)).unwrap();
+
+        // The set-power effect is newer, but its layer comes first.
+        let mut list = ContinuousEffectList::new();
+        list.add(item(&env, 2, 1, AddPower(100)));
+        list.add(item(&env, 3, 2, SetPower(500)));
+        let computed = list.apply_card(&env.state, target);
+        assert_eq!(computed.current_power(), 600);
+        assert_eq!(
+            computed
+                .modifiers
+                .iter()
+                .map(|modifier| (modifier.effect, modifier.source, modifier.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    Some(id(102)),
+                    id(3),
+                    ModifierKind::Power {
+                        before: 300,
+                        after: 500
+                    }
+                ),
+                (
+                    Some(id(101)),
+                    id(2),
+                    ModifierKind::Power {
+                        before: 500,
+                        after: 600
+                    }
+                ),
+            ]
+        );
+
+        // Applied in timestamp order, the power would be below the threshold.
+        let mut list = ContinuousEffectList::new();
+        list.add(item(&env, 2, 1, DoubleIfAtLeast(500)));
+        list.add(item(&env, 3, 2, SetPower(500)));
+        let computed = list.apply_card(&env.state, target);
+        assert_eq!(computed.current_power(), 1000);
+    }
+
+    #[test]
+    fn test_dependency_order() {
+        let env = environment();
+        let target = env.state.find_card(id(1)).unwrap();
+
+        // The older effect depends on the ability granted by the newer one.
+        let mut list = ContinuousEffectList::new();
+        list.add(item(&env, 2, 1, PiercingIfToxic));
+        list.add(item(&env, 3, 2, Grant(KeywordAbility::Toxic)));
+        let computed = list.apply_card(&env.state, target);
+        assert!(computed.abilities().contains(&KeywordAbility::Toxic));
+        assert!(computed.abilities().contains(&KeywordAbility::Piercing));
+
+        // Without the dependency, the timestamp order is kept.
+        let mut list = ContinuousEffectList::new();
+        list.add(item(&env, 2, 1, Grant(KeywordAbility::Volatile)));
+        list.add(item(&env, 3, 2, PiercingIfToxic));
+        let computed = list.apply_card(&env.state, target);
+        assert!(!computed.abilities().contains(&KeywordAbility::Piercing));
+    }
+
+    #[test]
+    fn test_player_order() {
+        let env = environment();
+
+        // The older effect is applied first, even if it was added last.
+        let mut list = ContinuousEffectList::new();
+        list.add(item(&env, 3, 2, GrantIfNone(PlayerAbility::Propagate(1))));
+        list.add(item(&env, 2, 1, GrantIfNone(PlayerAbility::Draw)));
+        let abilities = list.apply_player(&env.state, 0);
+        assert_eq!(abilities.as_ref(), &[PlayerAbility::Draw]);
+    }
+
+    #[test]
+    fn test_sort_by_dependencies() {
+        let mut items = [0, 1, 2, 3];
+        sort_by_dependencies(&mut items, |_, _| false);
+        assert_eq!(items, [0, 1, 2, 3]);
+
+        let mut items = [0, 1, 2, 3];
+        sort_by_dependencies(&mut items, |a, b| a == 0 && b == 2);
+        assert_eq!(items, [1, 2, 0, 3]);
+
+        let mut items = [0, 1, 2, 3];
+        sort_by_dependencies(&mut items, |a, b| (a == 0 && b == 1) || (a == 1 && b == 0));
+        assert_eq!(items, [0, 1, 2, 3]);
+    }
+}
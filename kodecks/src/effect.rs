@@ -151,8 +151,9 @@ impl<'a> EffectTriggerContext<'a> {
         F: ContinuousEffect + 'static,
         C: Condition + 'static,
     {
+        let id = self.obj_counter.allocate(None);
         self.continuous
-            .push(ContinuousItem::new(self.source, effect, condition));
+            .push(ContinuousItem::new(id, self.source, effect, condition));
     }
 
     pub fn push_stack<F>(&mut self, id: &str, handler: F)
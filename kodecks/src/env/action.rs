@@ -1,9 +1,14 @@
 use super::Environment;
 use crate::{
-    action::{AvailableAction, PlayerAvailableActions},
+    action::{Action, AvailableAction, PlayerAvailableActions},
+    error::UnavailableReason,
+    field::FieldState,
     filter_vec,
+    id::ObjectId,
     message::Message,
     phase::Phase,
+    player::Player,
+    profile::DebugFlags,
     variable::VariableList,
     zone::CardZone,
 };
@@ -112,4 +117,132 @@ impl Environment {
             None
         }
     }
+
+    /// Returns the reason why `action` cannot be taken by `player`,
+    /// or `None` if the action is currently available.
+    pub fn unavailable_reason(&self, player: u8, action: &Action) -> Option<UnavailableReason> {
+        if self.endgame.is_ended() {
+            return Some(UnavailableReason::GameEnded);
+        }
+
+        match action {
+            Action::Concede => return None,
+            Action::DebugCommand { .. } => {
+                return if self.state.debug.flags.contains(DebugFlags::DEBUG_COMMAND) {
+                    None
+                } else {
+                    Some(UnavailableReason::DebugCommandDisabled)
+                };
+            }
+            Action::Undo => {
+                // The environment keeps no history by itself; see `history::History`.
+                return if self.state.debug.flags.contains(DebugFlags::UNDO) {
+                    Some(UnavailableReason::NothingToUndo)
+                } else {
+                    Some(UnavailableReason::UndoDisabled)
+                };
+            }
+            _ => {}
+        }
+
+        let available = match &self.last_available_actions {
+            Some(available) if available.player == player => available,
+            _ => return Some(UnavailableReason::NotInAction),
+        };
+        if available.validate(player, action) {
+            return None;
+        }
+
+        let phase_reason = if self.stack.is_empty() {
+            UnavailableReason::WrongPhase {
+                phase: self.state.phase,
+            }
+        } else {
+            UnavailableReason::EffectResolving
+        };
+        let player = self.state.players.get(player);
+
+        let reason = match action {
+            Action::CastCard { card } => {
+                if available.actions.castable_cards().is_empty() {
+                    phase_reason
+                } else {
+                    self.cast_unavailable_reason(player, *card)
+                }
+            }
+            Action::Attack { attackers } => {
+                let available = available.actions.attackers();
+                if available.is_empty() {
+                    phase_reason
+                } else {
+                    attackers
+                        .iter()
+                        .filter(|attacker| !available.contains(attacker))
+                        .map(|&attacker| Self::field_unavailable_reason(player, attacker))
+                        .next()
+                        .unwrap_or(phase_reason)
+                }
+            }
+            Action::Block { pairs } => {
+                let available = available.actions.blockers();
+                let opponent = self.state.players.next_player(player.id);
+                if available.is_empty() {
+                    phase_reason
+                } else if let Some(&(attacker, _)) = pairs.iter().find(|(attacker, _)| {
+                    !opponent
+                        .field
+                        .attacking_cards()
+                        .any(|c| c.id() == *attacker)
+                }) {
+                    UnavailableReason::NotAttacking { card: attacker }
+                } else {
+                    pairs
+                        .iter()
+                        .filter(|(_, blocker)| !available.contains(blocker))
+                        .map(|&(_, blocker)| Self::field_unavailable_reason(player, blocker))
+                        .next()
+                        .unwrap_or(phase_reason)
+                }
+            }
+            Action::SelectCard { card } => {
+                if available.actions.selectable_cards().is_empty() {
+                    phase_reason
+                } else {
+                    UnavailableReason::NotSelectable { card: *card }
+                }
+            }
+            _ => phase_reason,
+        };
+        Some(reason)
+    }
+
+    fn cast_unavailable_reason(&self, player: &Player, card: ObjectId) -> UnavailableReason {
+        let item = match player.hand.get_item(card).ok() {
+            Some(item) => item,
+            None => return UnavailableReason::CardNotInHand { card },
+        };
+        let computed = item.card.computed();
+        let cost = computed.cost.value();
+        let shards = player.shards.get(computed.color);
+        if !self.state.debug.flags.contains(DebugFlags::IGNORE_COST) && cost > shards {
+            UnavailableReason::InsufficientShards {
+                color: computed.color,
+                required: cost,
+                available: shards,
+            }
+        } else if computed.is_creature() && cost == 0 && player.counters.free_casted > 0 {
+            UnavailableReason::CreatureAlreadyFreeCasted
+        } else {
+            UnavailableReason::CastingRestricted { card }
+        }
+    }
+
+    fn field_unavailable_reason(player: &Player, card: ObjectId) -> UnavailableReason {
+        match player.field.get_item(card).ok() {
+            Some(item) if item.state == FieldState::Exhausted => {
+                UnavailableReason::Exhausted { card }
+            }
+            _ => UnavailableReason::CardNotOnField { card },
+        }
+    }
 }
@@ -0,0 +1,235 @@
+use super::{EndgameState, Environment, GameState};
+use crate::{
+    archetype::ArchetypeId,
+    card::CardEntry,
+    catalog::Catalog,
+    color::Color,
+    deck::DeckItem,
+    field::FieldState,
+    id::{ObjectId, ObjectIdCounter},
+    opcode::{Opcode, OpcodeList},
+    phase::Phase,
+    player::PlayerList,
+    profile::DebugConfig,
+    regulation::Regulation,
+    scenario::{PuzzleFieldItem, PuzzlePlayer},
+    stack::Stack,
+};
+use rand::{rngs::SmallRng, SeedableRng};
+use std::{collections::VecDeque, iter, sync::Arc};
+
+/// Builds an environment from a given board state without playing a game from the start.
+///
+/// Cards are placed with explicit ids. Ids allocated during the game, such as for tokens,
+/// come after the largest of them.
+pub struct EnvironmentBuilder {
+    catalog: Arc<Catalog>,
+    regulation: Regulation,
+    debug: DebugConfig,
+    players: Vec<PlayerBuilder>,
+    turn: u16,
+    player_in_turn: u8,
+    phase: Phase,
+    rng_seed: u64,
+}
+
+impl EnvironmentBuilder {
+    pub(super) fn new(catalog: Arc<Catalog>) -> Self {
+        Self {
+            catalog,
+            regulation: Regulation::default(),
+            debug: DebugConfig::default(),
+            players: vec![PlayerBuilder::default(), PlayerBuilder::default()],
+            turn: 1,
+            player_in_turn: 0,
+            phase: Phase::Main,
+            rng_seed: 0,
+        }
+    }
+
+    pub fn with_regulation(mut self, regulation: Regulation) -> Self {
+        self.regulation = regulation;
+        self
+    }
+
+    pub fn with_debug(mut self, debug: DebugConfig) -> Self {
+        self.debug = debug;
+        self
+    }
+
+    /// Sets up the player with the given id, adding empty players as needed.
+    pub fn with_player<F>(mut self, id: u8, f: F) -> Self
+    where
+        F: FnOnce(PlayerBuilder) -> PlayerBuilder,
+    {
+        let id = id as usize;
+        if self.players.len() <= id {
+            self.players.resize_with(id + 1, Default::default);
+        }
+        self.players[id] = f(std::mem::take(&mut self.players[id]));
+        self
+    }
+
+    /// Starts the game at the given turn and phase of the player.
+    pub fn with_turn(mut self, turn: u16, player: u8, phase: Phase) -> Self {
+        self.turn = turn;
+        self.player_in_turn = player;
+        self.phase = phase;
+        self
+    }
+
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        self.rng_seed = seed;
+        self
+    }
+
+    pub fn build(self) -> Environment {
+        let mut obj_counter = ObjectIdCounter::default();
+        for id in self.players.iter().flat_map(PlayerBuilder::ids) {
+            obj_counter.skip_past(id);
+        }
+        let players = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(id, player)| {
+                player
+                    .inner
+                    .build(id as u8, &self.catalog, &mut obj_counter)
+            })
+            .collect::<Vec<_>>();
+
+        let opcodes = iter::once(Opcode::StartGame)
+            .chain(players.iter().map(|player| Opcode::SetLife {
+                player: player.id,
+                life: player.stats.life,
+            }))
+            .chain(iter::once(Opcode::ChangeTurn {
+                turn: self.turn,
+                player: self.player_in_turn,
+                phase: self.phase,
+            }))
+            .collect::<Vec<_>>();
+
+        Environment {
+            state: GameState {
+                regulation: self.regulation,
+                debug: self.debug,
+                turn: 0,
+                phase: Phase::Standby,
+                players: PlayerList::new(self.player_in_turn, players),
+            },
+            opcodes: VecDeque::from([OpcodeList::new(opcodes)]),
+            stack: Stack::new(),
+            continuous: Default::default(),
+            endgame: EndgameState::InProgress,
+            timestamp: 0,
+            last_available_actions: None,
+            rng: SmallRng::seed_from_u64(self.rng_seed),
+            catalog: self.catalog,
+            obj_counter,
+        }
+    }
+}
+
+/// The board state of a player for [`EnvironmentBuilder`].
+#[derive(Debug, Clone, Default)]
+pub struct PlayerBuilder {
+    inner: PuzzlePlayer,
+}
+
+impl PlayerBuilder {
+    pub fn with_life(mut self, life: u32) -> Self {
+        self.inner.life = life;
+        self
+    }
+
+    /// Adds a card to the bottom of the deck.
+    pub fn with_deck(mut self, id: u32, archetype: &str) -> Self {
+        self.inner.deck.push(deck_item(id, archetype));
+        self
+    }
+
+    pub fn with_hand(mut self, id: u32, archetype: &str) -> Self {
+        self.inner.hand.push(deck_item(id, archetype));
+        self
+    }
+
+    pub fn with_field(mut self, id: u32, archetype: &str, state: FieldState) -> Self {
+        self.inner.field.push(PuzzleFieldItem {
+            card: deck_item(id, archetype),
+            state,
+        });
+        self
+    }
+
+    pub fn with_graveyard(mut self, id: u32, archetype: &str) -> Self {
+        self.inner.graveyard.push(deck_item(id, archetype));
+        self
+    }
+
+    pub fn with_shards(mut self, color: Color, amount: u8) -> Self {
+        self.inner.shards.add(color, amount);
+        self
+    }
+
+    fn ids(&self) -> impl Iterator<Item = ObjectId> + '_ {
+        self.inner
+            .deck
+            .iter()
+            .chain(&self.inner.hand)
+            .chain(self.inner.field.iter().map(|item| &item.card))
+            .chain(&self.inner.graveyard)
+            .filter_map(|item| item.base_id)
+    }
+}
+
+impl From<PuzzlePlayer> for PlayerBuilder {
+    fn from(inner: PuzzlePlayer) -> Self {
+        Self { inner }
+    }
+}
+
+fn deck_item(id: u32, archetype: &str) -> DeckItem {
+    DeckItem {
+        card: CardEntry {
+            archetype_id: ArchetypeId::new(archetype),
+            style: 0,
+        },
+        base_id: Some(ObjectId::try_from(id).expect("card id must not be zero")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archetype::{CardArchetype, CardAttribute, CardType};
+    use std::sync::LazyLock;
+
+    fn creature() -> &'static CardArchetype {
+        static CARD: LazyLock<CardArchetype> = LazyLock::new(|| CardArchetype {
+            id: ArchetypeId::new("crea"),
+            name: "Creature".to_string(),
+            safe_name: "creature".to_string(),
+            attribute: CardAttribute {
+                card_type: CardType::Creature,
+                power: Some(300),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        &CARD
+    }
+
+    #[test]
+    fn test_ids_past_reserved_range() {
+        let mut env = Environment::builder(Arc::new(Catalog::new(&[creature])))
+            .with_player(0, |player| {
+                player.with_field(150, "crea", FieldState::Active)
+            })
+            .with_player(1, |player| player.with_hand(120, "crea"))
+            .build();
+        let id = env.obj_counter.allocate(None);
+        assert_eq!(id, ObjectId::try_from(151).unwrap());
+    }
+}
@@ -0,0 +1,342 @@
+use super::{EndgameState, LocalEnvironment};
+use crate::{
+    action::PlayerAvailableActions,
+    card::CardSnapshot,
+    error::DeltaError,
+    field::FieldItem,
+    hand::HandItem,
+    id::{CardId, ObjectId},
+    log::GameLog,
+    phase::Phase,
+    player::{LocalPlayerState, PlayerItem, PlayerStats},
+    shard::ShardList,
+    stack::{LocalStackItem, Stack},
+};
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+/// Changes to a [`LocalEnvironment`] relative to an earlier state of the same viewer.
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct LocalEnvironmentDelta {
+    pub base: u32,
+    pub base_checksum: u64,
+    pub timestamp: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player_in_turn: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phase: Option<Phase>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Stack<LocalStackItem>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endgame: Option<EndgameState>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub players: Vec<LocalPlayerDelta>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct LocalPlayerDelta {
+    pub id: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deck: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hand: Option<ZoneDelta<HandItem<CardSnapshot>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graveyard: Option<ZoneDelta<CardSnapshot>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<ZoneDelta<FieldItem<CardSnapshot>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shards: Option<ShardList>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<PlayerStats>,
+}
+
+/// The new order of a zone and the cards that are new or have changed.
+/// Unchanged cards are taken from the base state.
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct ZoneDelta<T: 'static> {
+    pub order: Vec<ObjectId>,
+    pub changed: Vec<T>,
+}
+
+impl<T> ZoneDelta<T>
+where
+    T: CardId + Clone + Encode,
+{
+    fn new(base: &[T], current: &[T]) -> Option<Self> {
+        let base = base.iter().map(encode).collect::<Vec<_>>();
+        let encoded = current.iter().map(encode).collect::<Vec<_>>();
+        if base == encoded {
+            return None;
+        }
+        Some(Self {
+            order: current.iter().map(|item| item.id()).collect(),
+            changed: current
+                .iter()
+                .zip(&encoded)
+                .filter(|(_, bytes)| !base.contains(bytes))
+                .map(|(item, _)| item.clone())
+                .collect(),
+        })
+    }
+
+    fn apply(self, base: &[T]) -> Result<Vec<T>, DeltaError> {
+        self.order
+            .into_iter()
+            .map(|id| {
+                self.changed
+                    .iter()
+                    .chain(base)
+                    .find(|item| item.id() == id)
+                    .cloned()
+                    .ok_or(DeltaError::CardNotFound { id })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct LocalGameStateDelta {
+    pub env: LocalEnvironmentDelta,
+    pub logs: Vec<GameLog>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_actions: Option<PlayerAvailableActions>,
+}
+
+impl LocalEnvironment {
+    pub fn diff(&self, base: &LocalEnvironment) -> LocalEnvironmentDelta {
+        let player_in_turn = self.players.player_in_turn().id;
+        LocalEnvironmentDelta {
+            base: base.timestamp,
+            base_checksum: base.checksum(),
+            timestamp: self.timestamp,
+            turn: Some(self.turn).filter(|&turn| turn != base.turn),
+            player_in_turn: Some(player_in_turn)
+                .filter(|&player| player != base.players.player_in_turn().id),
+            phase: Some(self.phase).filter(|&phase| phase != base.phase),
+            stack: changed(&base.stack, &self.stack),
+            endgame: Some(self.endgame).filter(|&endgame| endgame != base.endgame),
+            players: self
+                .players
+                .iter()
+                .filter_map(|player| {
+                    let base = base.players.iter().find(|base| base.id == player.id);
+                    match base {
+                        Some(base) => player.diff(base),
+                        None => Some(LocalPlayerDelta::full(player)),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    pub fn apply_delta(&mut self, delta: LocalEnvironmentDelta) -> Result<(), DeltaError> {
+        if delta.base != self.timestamp || delta.base_checksum != self.checksum() {
+            return Err(DeltaError::BaseMismatch {
+                expected: delta.base,
+                actual: self.timestamp,
+            });
+        }
+
+        for player in delta.players {
+            let state = self
+                .players
+                .iter_mut()
+                .find(|state| state.id == player.id)
+                .ok_or(DeltaError::PlayerNotFound { player: player.id })?;
+            player.apply(state)?;
+        }
+
+        self.timestamp = delta.timestamp;
+        if let Some(turn) = delta.turn {
+            self.turn = turn;
+        }
+        if let Some(player) = delta.player_in_turn {
+            self.players.set_player_in_turn(player);
+        }
+        if let Some(phase) = delta.phase {
+            self.phase = phase;
+        }
+        if let Some(stack) = delta.stack {
+            self.stack = stack;
+        }
+        if let Some(endgame) = delta.endgame {
+            self.endgame = endgame;
+        }
+        Ok(())
+    }
+}
+
+impl LocalPlayerState {
+    fn diff(&self, base: &LocalPlayerState) -> Option<LocalPlayerDelta> {
+        let delta = LocalPlayerDelta {
+            id: self.id,
+            deck: Some(self.deck).filter(|&deck| deck != base.deck),
+            hand: ZoneDelta::new(&base.hand, &self.hand),
+            graveyard: ZoneDelta::new(&base.graveyard, &self.graveyard),
+            field: ZoneDelta::new(&base.field, &self.field),
+            shards: changed(&base.shards, &self.shards),
+            stats: changed(&base.stats, &self.stats),
+        };
+        if delta.is_empty() {
+            None
+        } else {
+            Some(delta)
+        }
+    }
+}
+
+impl LocalPlayerDelta {
+    fn full(state: &LocalPlayerState) -> Self {
+        Self {
+            id: state.id(),
+            deck: Some(state.deck),
+            hand: ZoneDelta::new(&[], &state.hand),
+            graveyard: ZoneDelta::new(&[], &state.graveyard),
+            field: ZoneDelta::new(&[], &state.field),
+            shards: Some(state.shards.clone()),
+            stats: Some(state.stats),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deck.is_none()
+            && self.hand.is_none()
+            && self.graveyard.is_none()
+            && self.field.is_none()
+            && self.shards.is_none()
+            && self.stats.is_none()
+    }
+
+    fn apply(self, state: &mut LocalPlayerState) -> Result<(), DeltaError> {
+        if let Some(deck) = self.deck {
+            state.deck = deck;
+        }
+        if let Some(hand) = self.hand {
+            state.hand = hand.apply(&state.hand)?;
+        }
+        if let Some(graveyard) = self.graveyard {
+            state.graveyard = graveyard.apply(&state.graveyard)?;
+        }
+        if let Some(field) = self.field {
+            state.field = field.apply(&state.field)?;
+        }
+        if let Some(shards) = self.shards {
+            state.shards = shards;
+        }
+        if let Some(stats) = self.stats {
+            state.stats = stats;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the current value if its encoding differs from the base.
+fn changed<T: Clone + Encode>(base: &T, current: &T) -> Option<T> {
+    Some(current)
+        .filter(|current| encode(*current) != encode(base))
+        .cloned()
+}
+
+/// Items are compared by their encoding, which is what the checksum is computed from.
+///
+/// `PartialEq` is not enough here, since [`Linear`](crate::linear::Linear) only compares
+/// the resulting values.
+fn encode<T: Encode>(item: &T) -> Vec<u8> {
+    bincode::encode_to_vec(item, bincode::config::standard()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        archetype::CardArchetype, color::Color, field::FieldState, id::ObjectIdCounter,
+        player::PlayerList,
+    };
+
+    fn card(counter: &mut ObjectIdCounter) -> CardSnapshot {
+        CardSnapshot {
+            id: counter.allocate(None),
+            ..CardSnapshot::new(&CardArchetype::default())
+        }
+    }
+
+    #[test]
+    fn test_apply_delta() {
+        let mut counter = ObjectIdCounter::default();
+        let hand = (0..3).map(|_| card(&mut counter)).collect::<Vec<_>>();
+        let player = |id| LocalPlayerState {
+            id,
+            deck: 10,
+            hand: hand
+                .iter()
+                .cloned()
+                .map(|card| HandItem {
+                    card,
+                    cost_delta: 0,
+                })
+                .collect(),
+            graveyard: vec![],
+            field: vec![],
+            shards: ShardList::new(),
+            stats: Default::default(),
+        };
+        let base = LocalEnvironment {
+            player: 0,
+            turn: 1,
+            timestamp: 10,
+            players: PlayerList::new(0, vec![player(0), player(1)]),
+            phase: Phase::Main,
+            stack: Stack::new(),
+            endgame: EndgameState::InProgress,
+        };
+
+        let mut current = base.clone();
+        current.timestamp = 12;
+        current.phase = Phase::Block;
+        let state = current.players.get_mut(0);
+        let casted = state.hand.remove(1);
+        state.field.push(FieldItem {
+            card: casted.card,
+            state: FieldState::Active,
+            battle: None,
+        });
+        state.shards.add(Color::RED, 2);
+
+        let delta = current.diff(&base);
+        assert_eq!(delta.players.len(), 1);
+        assert!(delta.turn.is_none());
+        let hand = delta.players[0].hand.as_ref().unwrap();
+        assert_eq!(hand.order.len(), 2);
+        assert!(hand.changed.is_empty());
+
+        let mut env = base.clone();
+        env.apply_delta(delta).unwrap();
+        assert_eq!(env.checksum(), current.checksum());
+
+        let mut env = current.clone();
+        assert!(env.apply_delta(current.diff(&base)).is_err());
+
+        // Changes the modifiers of the cost, but not the value.
+        let mut modified = current.clone();
+        modified.timestamp = 13;
+        let cost = &mut modified.players.get_mut(0).hand[0]
+            .card
+            .computed
+            .as_mut()
+            .unwrap()
+            .cost;
+        cost.add(1);
+        cost.add(-1);
+        assert_ne!(modified.checksum(), current.checksum());
+
+        let delta = modified.diff(&current);
+        let hand = delta.players[0].hand.as_ref().unwrap();
+        assert_eq!(hand.changed.len(), 1);
+
+        let mut env = current.clone();
+        env.apply_delta(delta).unwrap();
+        assert_eq!(env.checksum(), modified.checksum());
+    }
+}
@@ -0,0 +1,182 @@
+use super::{Environment, GameState, LocalEnvironment};
+use crate::{
+    action::PlayerAvailableActions,
+    archetype::ArchetypeId,
+    card::{Card, CardEntry, CardSnapshot},
+    catalog::Catalog,
+    deck::DeckItem,
+    effect::EffectTriggerContext,
+    id::{ObjectId, ObjectIdCounter},
+    player::{Player, PlayerList, PlayerZone},
+    regulation::Regulation,
+    sequence::CardSequence,
+    stack::Stack,
+    zone::{CardZone, Zone},
+};
+use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+use std::{collections::VecDeque, sync::Arc};
+use tracing::error;
+
+/// The cards a player keeps hidden from the viewer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HiddenCards {
+    /// The archetypes of the hidden hand cards, by object id.
+    pub hand: Vec<(ObjectId, ArchetypeId)>,
+    /// The archetypes of the deck cards, from bottom to top.
+    pub deck: Vec<ArchetypeId>,
+}
+
+impl Environment {
+    /// Returns a copy in which the information hidden from the viewer is resampled.
+    ///
+    /// The hand cards unknown to the viewer are redrawn together with the deck,
+    /// every deck is shuffled and the random number generator is reseeded,
+    /// so the copy is a plausible game from the viewer's point of view.
+    pub fn determinize(&self, viewer: u8, rng: &mut impl Rng) -> Self {
+        let mut env = self.clone();
+        env.rng = SmallRng::seed_from_u64(rng.gen());
+        for player in env.state.players.iter_mut() {
+            let hidden = player
+                .hand
+                .iter()
+                .filter(|card| !card.revealed().contains(viewer))
+                .map(|card| card.id())
+                .collect::<Vec<_>>();
+            let mut pool = hidden
+                .iter()
+                .filter_map(|id| player.hand.remove(*id))
+                .collect::<Vec<Card>>();
+            while let Some(card) = player.deck.remove_top() {
+                pool.push(card);
+            }
+            pool.shuffle(rng);
+
+            let hand = pool.split_off(pool.len() - hidden.len());
+            for mut card in hand {
+                card.set_zone(PlayerZone::new(player.id, Zone::Hand));
+                player.hand.push(card);
+            }
+            for mut card in pool {
+                card.set_zone(PlayerZone::new(player.id, Zone::Deck));
+                player.deck.add_top(card);
+            }
+        }
+        env
+    }
+
+    /// Builds a game from the local view of the viewer, with the hidden cards guessed.
+    ///
+    /// Nothing but the view is read, so the game tells the viewer nothing they could not know.
+    /// The effects waiting on the stack are triggered again from their sources,
+    /// while the continuous effects and the inner state of card effects start afresh.
+    /// Hidden cards missing from `hidden` are left out, and so are decks missing from it.
+    pub fn from_local(
+        local: &LocalEnvironment,
+        catalog: Arc<Catalog>,
+        regulation: Regulation,
+        hidden: &[(u8, HiddenCards)],
+        available_actions: Option<PlayerAvailableActions>,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let mut obj_counter = ObjectIdCounter::default();
+        if let Some(id) = local.cards().map(|card| card.id).max() {
+            obj_counter.skip_past(id);
+        }
+
+        let players = local
+            .players
+            .iter()
+            .map(|state| {
+                let cards = hidden
+                    .iter()
+                    .find(|(id, _)| *id == state.id)
+                    .map(|(_, cards)| cards);
+                let restore = |snapshot: &CardSnapshot, zone: Zone| {
+                    let archetype_id = if snapshot.archetype_id.is_empty() {
+                        cards?.hand.iter().find(|(id, _)| *id == snapshot.id)?.1
+                    } else {
+                        snapshot.archetype_id
+                    };
+                    let archetype = catalog.get(archetype_id)?.clone();
+                    let zone = PlayerZone::new(state.id, zone);
+                    Some(Card::from_snapshot(snapshot, archetype, zone))
+                };
+
+                let mut player = Player::new(state.id);
+                let mut cost_deltas = vec![];
+                for item in &state.hand {
+                    if let Some(card) = restore(&item.card, Zone::Hand) {
+                        player.hand.push(card);
+                        cost_deltas.push(item.cost_delta);
+                    }
+                }
+                for (item, cost_delta) in player.hand.items_mut().zip(cost_deltas) {
+                    item.cost_delta = cost_delta;
+                }
+                for card in &state.graveyard {
+                    if let Some(card) = restore(card, Zone::Graveyard) {
+                        player.graveyard.push(card);
+                    }
+                }
+                for item in &state.field {
+                    if let Some(card) = restore(&item.card, Zone::Field) {
+                        player.field.push(card);
+                        player.field.set_card_state(item.card.id, item.state);
+                        player
+                            .field
+                            .set_card_battle_state(item.card.id, item.battle);
+                    }
+                }
+                for archetype_id in cards.iter().flat_map(|cards| &cards.deck) {
+                    let item = DeckItem {
+                        card: CardEntry {
+                            archetype_id: *archetype_id,
+                            style: 0,
+                        },
+                        base_id: None,
+                    };
+                    let archetype = catalog[*archetype_id].clone();
+                    let card = Card::new(&mut obj_counter, &item, archetype, 0, state.id);
+                    player.deck.add_top(card);
+                }
+                player.shards = state.shards.clone();
+                player.stats = state.stats;
+                player
+            })
+            .collect::<Vec<_>>();
+
+        let mut env = Self {
+            state: GameState {
+                regulation,
+                debug: Default::default(),
+                turn: local.turn,
+                phase: local.phase,
+                players: PlayerList::new(local.players.player_in_turn().id, players),
+            },
+            opcodes: VecDeque::new(),
+            stack: Stack::new(),
+            continuous: Default::default(),
+            endgame: local.endgame,
+            timestamp: local.timestamp,
+            last_available_actions: available_actions,
+            rng: SmallRng::seed_from_u64(rng.gen()),
+            catalog,
+            obj_counter,
+        };
+
+        for item in local.stack.iter() {
+            let Ok(source) = env.state.find_card(item.source) else {
+                continue;
+            };
+            let mut ctx = EffectTriggerContext::new(&env.state, &mut env.obj_counter, source);
+            let mut effect = dyn_clone::clone_box(source.effect());
+            if let Err(err) = effect.trigger(item.id.as_str().into(), &mut ctx) {
+                error!("Error triggering effect: {:?}", err);
+            }
+            let (_, stack) = ctx.into_inner();
+            env.stack
+                .extend(stack.into_iter().filter(|pending| pending.id == item.id));
+        }
+        env
+    }
+}
@@ -33,11 +33,11 @@ impl Environment {
                 let to = PlayerZone::new(target.owner(), Zone::Graveyard);
                 let volatile = target
                     .computed()
-                    .abilities
+                    .abilities()
                     .contains(&KeywordAbility::Volatile);
                 let devour = source
                     .computed()
-                    .abilities
+                    .abilities()
                     .contains(&KeywordAbility::Devour);
                 let token = target.is_token();
                 Ok(filter_vec![
@@ -7,7 +7,7 @@ use crate::{
     field::{FieldItem, FieldState},
     id::ObjectId,
     phase::Phase,
-    player::{LocalPlayerState, PlayerList, PlayerZone},
+    player::{LocalPlayerState, PlayerList, PlayerZone, Viewer},
     stack::{LocalStackItem, Stack},
     zone::CardZone,
 };
@@ -58,6 +58,18 @@ impl LocalEnvironment {
         self.players.next_player(player)
     }
 
+    /// Returns a deterministic checksum of the state visible to the viewer.
+    ///
+    /// The checksum is computed from the bincode encoding of the environment,
+    /// so it does not depend on the platform or the build target.
+    pub fn checksum(&self) -> u64 {
+        let config = bincode::config::standard();
+        match bincode::encode_to_vec(self, config) {
+            Ok(bytes) => fnv1a(&bytes),
+            Err(_) => 0,
+        }
+    }
+
     pub fn tick(&mut self, action: Action) -> Report {
         if let Action::CastCard { card, .. } = action {
             let player = self.players.get_mut(self.player);
@@ -80,12 +92,17 @@ impl LocalEnvironment {
             logs: vec![],
             endgame: self.endgame,
             timestamp: self.timestamp,
+            rejected: None,
         }
     }
 }
 
 impl Environment {
-    pub fn local(&self, viewer: u8) -> LocalEnvironment {
+    /// Returns the state visible to the viewer.
+    ///
+    /// Spectators see the board from the side of the first player.
+    pub fn local(&self, viewer: impl Into<Viewer>) -> LocalEnvironment {
+        let viewer = viewer.into();
         let players = PlayerList::new(
             self.state.players.player_in_turn().id,
             self.state
@@ -95,7 +112,7 @@ impl Environment {
         );
         let stack = self.stack.iter().map(|item| item.clone().into()).collect();
         LocalEnvironment {
-            player: viewer,
+            player: viewer.player().unwrap_or_default(),
             turn: self.state.turn,
             players,
             phase: self.state.phase,
@@ -105,3 +122,23 @@ impl Environment {
         }
     }
 }
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+    }
+}
@@ -3,17 +3,20 @@ use crate::{
     archetype::ArchetypeId,
     card::Card,
     catalog::Catalog,
+    command::ActionCommand,
     computed::ComputedSequence,
+    console,
     continuous::ContinuousEffectList,
     effect::EffectTriggerContext,
-    error::ActionError,
+    error::{ActionError, UnavailableReason},
     filter_vec,
     id::{ObjectId, ObjectIdCounter},
     log::GameLog,
     opcode::OpcodeList,
     phase::Phase,
-    player::{Player, PlayerEndgameState, PlayerList, PlayerZone},
+    player::{Player, PlayerEndgameState, PlayerList, PlayerZone, Viewer},
     profile::{DebugFlags, GameProfile},
+    scenario::Puzzle,
     sequence::CardSequence,
     stack::{Stack, StackItem},
     zone::{CardZone, Zone},
@@ -28,12 +31,18 @@ use strum::Display;
 use tracing::{error, warn};
 
 mod action;
+mod builder;
+mod delta;
+mod determinize;
 mod event;
 mod local;
 mod opcode;
 mod phase;
 mod state;
 
+pub use builder::{EnvironmentBuilder, PlayerBuilder};
+pub use delta::*;
+pub use determinize::HiddenCards;
 pub use local::LocalEnvironment;
 pub use state::*;
 
@@ -107,6 +116,29 @@ impl Environment {
         }
     }
 
+    pub fn builder(catalog: Arc<Catalog>) -> EnvironmentBuilder {
+        EnvironmentBuilder::new(catalog)
+    }
+
+    pub fn catalog(&self) -> &Arc<Catalog> {
+        &self.catalog
+    }
+
+    /// Creates an environment with the starting board of the puzzle.
+    ///
+    /// The game starts in the main phase of the first turn of the first player.
+    pub fn from_puzzle(puzzle: &Puzzle, catalog: Arc<Catalog>) -> Self {
+        puzzle
+            .players
+            .iter()
+            .enumerate()
+            .fold(
+                Self::builder(catalog).with_regulation(puzzle.regulation.clone()),
+                |builder, (id, player)| builder.with_player(id as u8, |_| player.clone().into()),
+            )
+            .build()
+    }
+
     fn compute_effects(&mut self) -> Result<(), ActionError> {
         let sides = self
             .state
@@ -148,11 +180,15 @@ impl Environment {
             }
             _ => {
                 warn!("Invalid action: {:?} for player: {}", action, player);
+                let rejected = action.map(|action| ActionRejected {
+                    reason: self
+                        .unavailable_reason(player, &action)
+                        .unwrap_or(UnavailableReason::InvalidAction),
+                    action,
+                });
                 Report {
-                    available_actions: self.last_available_actions.clone(),
-                    logs: vec![],
-                    endgame: self.endgame,
-                    timestamp: self.timestamp,
+                    rejected,
+                    ..self.report()
                 }
             }
         };
@@ -160,6 +196,23 @@ impl Environment {
         report
     }
 
+    /// Processes the action, then keeps processing until a player needs to act
+    /// or the game ends. The logs of all the steps are returned in one report.
+    pub fn advance(&mut self, player: u8, action: Option<Action>) -> Report {
+        let mut report = self.process(player, action);
+        while report.available_actions.is_none()
+            && report.rejected.is_none()
+            && !report.endgame.is_ended()
+        {
+            let next = self.process(player, None);
+            report.logs.extend(next.logs);
+            report.available_actions = next.available_actions;
+            report.endgame = next.endgame;
+            report.timestamp = next.timestamp;
+        }
+        report
+    }
+
     fn process_turn(&mut self, player: u8, mut action: Option<Action>) -> Report {
         let action = match action.take() {
             Some(Action::Concede) => {
@@ -170,7 +223,13 @@ impl Environment {
             Some(Action::DebugCommand { commands })
                 if self.state.debug.flags.contains(DebugFlags::DEBUG_COMMAND) =>
             {
-                for command in commands {
+                for mut command in commands {
+                    // Clients cannot see the id counter, so debug tokens get fresh ids.
+                    if let ActionCommand::GenerateCardToken { token, .. } = &mut command {
+                        if ObjectId::try_from(console::TOKEN_ID_PLACEHOLDER) == Ok(*token) {
+                            *token = self.obj_counter.allocate(None);
+                        }
+                    }
                     match command.into_opcodes(self) {
                         Ok(log) => self
                             .opcodes
@@ -254,6 +313,7 @@ impl Environment {
                         logs,
                         endgame: self.endgame,
                         timestamp: self.timestamp,
+                        rejected: None,
                     };
                 }
                 Err(err) => {
@@ -309,6 +369,22 @@ impl Environment {
             logs,
             endgame: self.endgame,
             timestamp: self.timestamp,
+            rejected: None,
+        }
+    }
+
+    pub fn checksum(&self, viewer: impl Into<Viewer>) -> u64 {
+        self.local(viewer).checksum()
+    }
+
+    /// Returns a report of the current state without any logs.
+    pub fn report(&self) -> Report {
+        Report {
+            available_actions: self.last_available_actions.clone(),
+            logs: vec![],
+            endgame: self.endgame,
+            timestamp: self.timestamp,
+            rejected: None,
         }
     }
 
@@ -316,6 +392,11 @@ impl Environment {
         self.last_available_actions.as_ref()
     }
 
+    /// Returns true if no effect is waiting to be resolved.
+    pub fn is_stack_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
     pub fn game_condition(&self) -> EndgameState {
         self.endgame
     }
@@ -429,6 +510,7 @@ pub enum EndgameReason {
     LifeZero,
     DeckOut,
     SimultaneousEnd,
+    TurnLimit,
 }
 
 #[derive(Debug, Clone)]
@@ -437,4 +519,30 @@ pub struct Report {
     pub logs: Vec<GameLog>,
     pub endgame: EndgameState,
     pub timestamp: u32,
+    pub rejected: Option<ActionRejected>,
+}
+
+impl Report {
+    /// Returns the first log matching the predicate.
+    pub fn find_log<F>(&self, mut f: F) -> Option<&GameLog>
+    where
+        F: FnMut(&GameLog) -> bool,
+    {
+        self.logs.iter().find(|log| f(log))
+    }
+
+    /// Returns the number of logs matching the predicate.
+    pub fn count_logs<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(&GameLog) -> bool,
+    {
+        self.logs.iter().filter(|log| f(log)).count()
+    }
+}
+
+/// An action that was not processed because it was not available.
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct ActionRejected {
+    pub action: Action,
+    pub reason: UnavailableReason,
 }
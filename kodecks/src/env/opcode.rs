@@ -9,7 +9,7 @@ use crate::{
     log::GameLog,
     opcode::Opcode,
     player::{PlayerEndgameState, PlayerZone},
-    prelude::{ComputedAttribute, ContinuousEffect, ContinuousItem},
+    prelude::{ComputedAttribute, ContinuousEffect, ContinuousItem, ContinuousLayer},
     sequence::CardSequence,
     target::Target,
     zone::{CardZone, MoveReason, Zone},
@@ -103,6 +103,7 @@ impl Environment {
             Opcode::BreakShield { card } => {
                 let card = self.state.find_card(card)?;
                 self.continuous.add(ContinuousItem::new(
+                    self.obj_counter.allocate(None),
                     card,
                     ShieldBroken,
                     condition::OnField(card.id()),
@@ -220,7 +221,7 @@ impl Environment {
                 let target = self.state.find_card(target)?;
                 let mut ctx = EffectActivateContext::new(&self.state, source, target);
 
-                if let Err(err) = target.effect().activate(event, &mut ctx) {
+                if let Err(err) = dyn_clone::clone_box(target.effect()).activate(event, &mut ctx) {
                     error!("Error triggering effect: {:?}", err);
                 };
 
@@ -234,7 +235,7 @@ impl Environment {
                     .collect::<Vec<_>>();
 
                 let mut ctx = EffectTriggerContext::new(&self.state, &mut self.obj_counter, target);
-                let mut effect = target.effect();
+                let mut effect = dyn_clone::clone_box(target.effect());
                 for id in stack.into_iter().chain(continuous) {
                     if let Err(err) = effect.trigger(id, &mut ctx) {
                         error!("Error triggering effect: {:?}", err);
@@ -316,6 +317,10 @@ impl Environment {
 struct ShieldBroken;
 
 impl ContinuousEffect for ShieldBroken {
+    fn layer(&self) -> ContinuousLayer {
+        ContinuousLayer::Shields
+    }
+
     fn apply_card(
         &mut self,
         _state: &GameState,
@@ -324,9 +329,7 @@ impl ContinuousEffect for ShieldBroken {
         computed: &mut ComputedAttribute,
     ) -> anyhow::Result<()> {
         if target.id() == source.id() {
-            if let Some(shields) = &mut computed.shields {
-                shields.add(-1);
-            }
+            computed.add_shields(source, -1);
         }
         Ok(())
     }
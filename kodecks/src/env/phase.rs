@@ -309,7 +309,7 @@ impl Environment {
 
                 if let Some(attacker) = attacker {
                     let blocker = target.field.find_blocker(attacker.id());
-                    let attacker_power = attacker.computed().power.unwrap_or_default().value();
+                    let attacker_power = attacker.computed().power().unwrap_or_default().value();
 
                     let mut logs = vec![];
                     if let Ok(log) = self.apply_event(CardEvent::Attacking, attacker, attacker) {
@@ -369,9 +369,9 @@ impl Environment {
                         let mut destroyed_logs = vec![];
                         let blocker_has_toxic = blocker
                             .computed()
-                            .abilities
+                            .abilities()
                             .contains(&KeywordAbility::Toxic);
-                        let blocker_power = blocker.computed().power.unwrap_or_default().value();
+                        let blocker_power = blocker.computed().power().unwrap_or_default().value();
                         if (blocker_power > 0 && attacker_power <= blocker_power)
                             || blocker_has_toxic
                         {
@@ -388,7 +388,7 @@ impl Environment {
 
                         let attacker_has_toxic = attacker
                             .computed()
-                            .abilities
+                            .abilities()
                             .contains(&KeywordAbility::Toxic);
                         if (attacker_power > 0 && blocker_power <= attacker_power)
                             || attacker_has_toxic
@@ -1,6 +1,7 @@
 use crate::{
     color::Color,
     id::{ObjectId, TimedObjectId},
+    phase::Phase,
 };
 use bincode::{Decode, Encode};
 use fluent_bundle::FluentArgs;
@@ -27,6 +28,12 @@ pub enum Error {
     },
     #[error("Invalid deck")]
     InvalidDeck,
+    #[error("Game not found")]
+    GameNotFound,
+    #[error("Cannot spectate a game you are playing")]
+    SpectatingOwnGame,
+    #[error("Permission denied")]
+    PermissionDenied,
 }
 
 impl<'a> From<Error> for Request<'a, FluentArgs<'a>> {
@@ -37,6 +44,9 @@ impl<'a> From<Error> for Request<'a, FluentArgs<'a>> {
             Error::ClientVersionOutdated { .. } => "error-client-version-outdated",
             Error::ServerVersionOutdated { .. } => "error-server-version-outdated",
             Error::InvalidDeck => "error-invalid-deck",
+            Error::GameNotFound => "error-game-not-found",
+            Error::SpectatingOwnGame => "error-spectating-own-game",
+            Error::PermissionDenied => "error-permission-denied",
         };
         match error {
             Error::ClientVersionOutdated {
@@ -67,6 +77,132 @@ impl<'a> From<Error> for Request<'a, FluentArgs<'a>> {
     }
 }
 
+/// The reason why an action cannot be taken by a player.
+#[derive(Debug, Error, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
+#[serde(tag = "name", rename_all = "snake_case")]
+pub enum UnavailableReason {
+    #[error("The game has ended")]
+    GameEnded,
+    #[error("Not in action")]
+    NotInAction,
+    #[error("An effect is resolving")]
+    EffectResolving,
+    #[error("Wrong phase: {phase}")]
+    WrongPhase { phase: Phase },
+    #[error("Card not in hand: {card}")]
+    CardNotInHand { card: ObjectId },
+    #[error("Card not on field: {card}")]
+    CardNotOnField { card: ObjectId },
+    #[error("Insufficient shards: {color} {required} (available: {available})")]
+    InsufficientShards {
+        color: Color,
+        required: u8,
+        available: u8,
+    },
+    #[error("Creature already free-casted")]
+    CreatureAlreadyFreeCasted,
+    #[error("Casting restricted by the card effect: {card}")]
+    CastingRestricted { card: ObjectId },
+    #[error("Creature exhausted: {card}")]
+    Exhausted { card: ObjectId },
+    #[error("Creature not attacking: {card}")]
+    NotAttacking { card: ObjectId },
+    #[error("Card not selectable: {card}")]
+    NotSelectable { card: ObjectId },
+    #[error("Debug commands are disabled")]
+    DebugCommandDisabled,
+    #[error("Undo is disabled")]
+    UndoDisabled,
+    #[error("Nothing to undo")]
+    NothingToUndo,
+    #[error("Restricted by the scenario")]
+    RestrictedByScenario,
+    #[error("Invalid action")]
+    InvalidAction,
+}
+
+impl<'a> From<UnavailableReason> for Request<'a, FluentArgs<'a>> {
+    fn from(reason: UnavailableReason) -> Request<'a, FluentArgs<'a>> {
+        let mut args = FluentArgs::new();
+        let id = match reason {
+            UnavailableReason::GameEnded => "unavailable-game-ended",
+            UnavailableReason::NotInAction => "unavailable-not-in-action",
+            UnavailableReason::EffectResolving => "unavailable-effect-resolving",
+            UnavailableReason::WrongPhase { .. } => "unavailable-wrong-phase",
+            UnavailableReason::CardNotInHand { .. } => "unavailable-card-not-in-hand",
+            UnavailableReason::CardNotOnField { .. } => "unavailable-card-not-on-field",
+            UnavailableReason::InsufficientShards { .. } => "unavailable-insufficient-shards",
+            UnavailableReason::CreatureAlreadyFreeCasted => {
+                "unavailable-creature-already-free-casted"
+            }
+            UnavailableReason::CastingRestricted { .. } => "unavailable-casting-restricted",
+            UnavailableReason::Exhausted { .. } => "unavailable-exhausted",
+            UnavailableReason::NotAttacking { .. } => "unavailable-not-attacking",
+            UnavailableReason::NotSelectable { .. } => "unavailable-not-selectable",
+            UnavailableReason::DebugCommandDisabled => "unavailable-debug-command-disabled",
+            UnavailableReason::UndoDisabled => "unavailable-undo-disabled",
+            UnavailableReason::NothingToUndo => "unavailable-nothing-to-undo",
+            UnavailableReason::RestrictedByScenario => "unavailable-restricted-by-scenario",
+            UnavailableReason::InvalidAction => "unavailable-invalid-action",
+        };
+        match reason {
+            UnavailableReason::WrongPhase { phase } => {
+                args.set("phase", phase.to_string().to_ascii_lowercase());
+            }
+            UnavailableReason::InsufficientShards {
+                color,
+                required,
+                available,
+            } => {
+                args.set("color", color.to_string().to_ascii_lowercase());
+                args.set("required", required);
+                args.set("available", available);
+            }
+            _ => {}
+        }
+        Request {
+            id,
+            attr: None,
+            args: Some(args),
+        }
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum DeltaError {
+    #[error("Base state mismatch: expected {expected} actual {actual}")]
+    BaseMismatch { expected: u32, actual: u32 },
+    #[error("Player not found: {player}")]
+    PlayerNotFound { player: u8 },
+    #[error("Card not found: {id}")]
+    CardNotFound { id: ObjectId },
+    #[error("Checksum mismatch")]
+    ChecksumMismatch,
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ConsoleError {
+    #[error("Unknown command: {command}")]
+    UnknownCommand { command: String },
+    #[error("Missing argument for {command}: {argument}")]
+    MissingArgument {
+        command: String,
+        argument: &'static str,
+    },
+    #[error("Unknown player: {player}")]
+    UnknownPlayer { player: String },
+    #[error("Invalid number: {value}")]
+    InvalidNumber { value: String },
+    #[error("Unknown color: {color}")]
+    UnknownColor { color: String },
+    #[error("Card not found: {name}")]
+    UnknownCard { name: String },
+    #[error("Unknown archetype: {name}")]
+    UnknownArchetype { name: String },
+    #[error("Player {player} has no card to be the source")]
+    NoSourceCard { player: u8 },
+}
+
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum ActionError {
     #[error("Insufficient shards: {color} {amount}")]
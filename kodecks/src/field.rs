@@ -61,7 +61,7 @@ impl CardList<FieldItem<Card>> {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
 pub struct FieldItem<T: CardId> {
     pub card: T,
     pub state: FieldState,
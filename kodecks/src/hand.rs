@@ -5,7 +5,7 @@ use crate::{
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
 pub struct HandItem<T: CardId> {
     pub card: T,
     pub cost_delta: i8,
@@ -0,0 +1,68 @@
+//! Snapshots of the environment at decision points, used to honor [`Action::Undo`].
+//!
+//! [`Action::Undo`]: crate::action::Action::Undo
+
+use crate::{env::Environment, error::UnavailableReason, profile::DebugFlags};
+use std::{collections::VecDeque, sync::Arc};
+
+const MAX_SNAPSHOTS: usize = 256;
+
+/// Keeps the environments in which players made their decisions.
+///
+/// Snapshots are only recorded when [`DebugFlags::UNDO`] is set.
+/// Since cards are shared between clones, a snapshot costs little
+/// more than the state changed after it.
+#[derive(Clone, Default)]
+pub struct History {
+    snapshots: VecDeque<Arc<Environment>>,
+}
+
+impl History {
+    /// Records the environment before an accepted action of the player in action.
+    pub fn push(&mut self, snapshot: Arc<Environment>) {
+        if !snapshot.state.debug.flags.contains(DebugFlags::UNDO)
+            || snapshot.last_available_actions().is_none()
+        {
+            return;
+        }
+        if self.snapshots.len() >= MAX_SNAPSHOTS {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// Returns the environment at the last decision of the player.
+    ///
+    /// Later snapshots are discarded, including the decisions of the opponent.
+    pub fn undo(
+        &mut self,
+        env: &Environment,
+        player: u8,
+    ) -> Result<Arc<Environment>, UnavailableReason> {
+        if !env.state.debug.flags.contains(DebugFlags::UNDO) {
+            return Err(UnavailableReason::UndoDisabled);
+        }
+        if env.game_condition().is_ended() {
+            return Err(UnavailableReason::GameEnded);
+        }
+        let pos = self
+            .snapshots
+            .iter()
+            .rposition(|snapshot| {
+                snapshot
+                    .last_available_actions()
+                    .is_some_and(|actions| actions.player == player)
+            })
+            .ok_or(UnavailableReason::NothingToUndo)?;
+        self.snapshots.truncate(pos + 1);
+        Ok(self.snapshots.pop_back().unwrap())
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
@@ -44,6 +44,11 @@ impl ObjectIdCounter {
             }
         }
     }
+
+    /// Makes sure that the ids allocated from now on come after the given one.
+    pub fn skip_past(&mut self, id: ObjectId) {
+        self.0 = self.0.max(id.0.get());
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Hash, Encode, Decode)]
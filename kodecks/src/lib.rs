@@ -9,6 +9,7 @@ pub mod color;
 pub mod command;
 pub mod computed;
 pub mod condition;
+pub mod console;
 pub mod continuous;
 pub mod deck;
 pub mod dsl;
@@ -18,6 +19,7 @@ pub mod error;
 pub mod event;
 pub mod field;
 pub mod hand;
+pub mod history;
 pub mod id;
 pub mod linear;
 pub mod list;
@@ -30,6 +32,7 @@ pub mod pool;
 pub mod prelude;
 pub mod profile;
 pub mod regulation;
+pub mod replay;
 pub mod scenario;
 pub mod score;
 pub mod sequence;
@@ -57,3 +60,31 @@ macro_rules! filter_vec {
         }
     };
 }
+
+/// Asserts that a [`Report`](env::Report) contains a log matching the pattern.
+#[macro_export]
+macro_rules! assert_log {
+    ($report:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+        let report = &$report;
+        assert!(
+            report.logs.iter().any(|log| matches!(log, $pattern $(if $guard)?)),
+            "no log matches `{}`\nlogs: {:#?}",
+            stringify!($pattern $(if $guard)?),
+            report.logs
+        );
+    };
+}
+
+/// Asserts that a [`Report`](env::Report) contains no log matching the pattern.
+#[macro_export]
+macro_rules! assert_no_log {
+    ($report:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+        let report = &$report;
+        assert!(
+            !report.logs.iter().any(|log| matches!(log, $pattern $(if $guard)?)),
+            "unexpected log matches `{}`\nlogs: {:#?}",
+            stringify!($pattern $(if $guard)?),
+            report.logs
+        );
+    };
+}
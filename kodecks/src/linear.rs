@@ -181,3 +181,53 @@ enum SerializedLinear<T> {
     Value(T),
     Modified(T, f64, f64, Option<T>),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_add() {
+        let mut power = Linear::new(300u32);
+        assert!(!power.is_modified());
+        power.add(100);
+        power.add(-50);
+        assert_eq!(power.value(), 350);
+        assert_eq!(power.diff(), Ordering::Greater);
+
+        power.add(-1000);
+        assert_eq!(power.value(), 0);
+        assert_eq!(power.diff(), Ordering::Less);
+    }
+
+    #[test]
+    fn test_linear_mul() {
+        let mut power = Linear::new(300u32);
+        power.mul(2);
+        power.add(100);
+        assert_eq!(power.value(), 700);
+
+        let mut power = Linear::new(300u32);
+        power.add(100);
+        power.mul(2);
+        assert_eq!(power.value(), 700);
+    }
+
+    #[test]
+    fn test_linear_assign() {
+        let mut power = Linear::new(300u32);
+        power.add(100);
+        power.assign(500);
+        assert_eq!(power.value(), 600);
+
+        power.assign(0);
+        assert_eq!(power.value(), 100);
+        assert_eq!(power.diff(), Ordering::Less);
+
+        let mut shields = Linear::new(1u8);
+        shields.assign(1);
+        assert!(!shields.is_modified());
+        shields.add(u16::MAX);
+        assert_eq!(shields.value(), u8::MAX);
+    }
+}
@@ -6,22 +6,32 @@ use crate::{
     zone::CardZone,
 };
 use rand::seq::SliceRandom;
+use std::sync::Arc;
+
+/// A list of cards shared between clones until it is modified.
 #[derive(Debug, Clone)]
 pub struct CardList<T> {
-    cards: Vec<T>,
+    cards: Arc<Vec<T>>,
 }
 
 impl<T> CardList<T> {
     pub fn new() -> Self {
-        Self { cards: Vec::new() }
+        Self {
+            cards: Arc::new(Vec::new()),
+        }
     }
 
     pub fn items(&self) -> impl Iterator<Item = &T> {
         self.cards.iter()
     }
+}
 
+impl<T> CardList<T>
+where
+    T: Clone,
+{
     pub fn items_mut(&mut self) -> impl Iterator<Item = &mut T> {
-        self.cards.iter_mut()
+        Arc::make_mut(&mut self.cards).iter_mut()
     }
 }
 
@@ -45,7 +55,7 @@ impl<T> Default for CardList<T> {
 
 impl<T> CardZone for CardList<T>
 where
-    T: CardId + AsRef<Card> + AsMut<Card> + From<Card> + Into<Card>,
+    T: Clone + CardId + AsRef<Card> + AsMut<Card> + From<Card> + Into<Card>,
 {
     type Item = Card;
 
@@ -62,38 +72,41 @@ where
     }
 
     fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Card> {
-        self.cards.iter_mut().map(|card| card.as_mut())
+        Arc::make_mut(&mut self.cards)
+            .iter_mut()
+            .map(|card| card.as_mut())
     }
 
     fn push(&mut self, card: Card) {
-        self.cards.push(card.into());
+        Arc::make_mut(&mut self.cards).push(card.into());
     }
 
     fn remove(&mut self, id: ObjectId) -> Option<Card> {
         let index = self.cards.iter().position(|card| card.id() == id)?;
-        Some(self.cards.remove(index).into())
+        Some(Arc::make_mut(&mut self.cards).remove(index).into())
     }
 }
 
 impl<T> CardSequence for CardList<T>
 where
-    T: CardId + AsRef<Card> + AsMut<Card> + From<Card> + Into<Card>,
+    T: Clone + CardId + AsRef<Card> + AsMut<Card> + From<Card> + Into<Card>,
 {
     fn remove_top(&mut self) -> Option<Card> {
-        self.cards.pop().map(|card| card.into())
+        Arc::make_mut(&mut self.cards).pop().map(|card| card.into())
     }
 
     fn add_top(&mut self, card: Card) {
-        self.cards.push(card.into());
+        Arc::make_mut(&mut self.cards).push(card.into());
     }
 }
 
 impl CardList<Card> {
     pub fn shuffle(&mut self, counter: &mut ObjectIdCounter, rng: &mut impl rand::Rng) {
-        self.cards.shuffle(rng);
-        for card in &mut self.cards {
+        let cards = Arc::make_mut(&mut self.cards);
+        cards.shuffle(rng);
+        for card in cards.iter_mut() {
             card.renew_id(counter);
         }
-        self.cards.shuffle(rng);
+        cards.shuffle(rng);
     }
 }
@@ -1,6 +1,11 @@
 use crate::{
-    card::CardSnapshot, color::Color, effect::EffectId, env::EndgameReason, phase::Phase,
-    player::PlayerZone, zone::MoveReason,
+    card::CardSnapshot,
+    color::Color,
+    effect::EffectId,
+    env::EndgameReason,
+    phase::Phase,
+    player::{PlayerZone, Viewer},
+    zone::MoveReason,
 };
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
@@ -81,7 +86,8 @@ pub enum GameLog {
 }
 
 impl GameLog {
-    pub fn redacted(self, viewer: u8) -> Self {
+    pub fn redacted(self, viewer: impl Into<Viewer>) -> Self {
+        let viewer = viewer.into();
         match self {
             Self::AttackDeclared { attacker } => Self::AttackDeclared {
                 attacker: attacker.redacted(viewer),
@@ -242,7 +242,7 @@ impl Player {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
 pub struct PlayerStats {
     pub life: u32,
 }
@@ -296,7 +296,8 @@ impl PlayerItem for LocalPlayerState {
 }
 
 impl LocalPlayerState {
-    pub fn new(state: &Player, viewer: u8) -> Self {
+    pub fn new(state: &Player, viewer: impl Into<Viewer>) -> Self {
+        let viewer = viewer.into();
         Self {
             id: state.id,
             deck: state.deck.len(),
@@ -495,4 +496,71 @@ impl PlayerMask {
     pub fn set_all(&mut self, value: bool) {
         self.0 = if value { 0xff } else { 0 };
     }
+
+    pub fn is_public(&self) -> bool {
+        self.0 == 0xff
+    }
+}
+
+/// The perspective from which a game state is observed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Viewer {
+    /// A player sees public cards and the cards revealed to them.
+    Player { id: u8 },
+    /// A spectator sees only public cards.
+    Observer,
+    /// A caster sees every card, including hands and decks.
+    Omniscient,
+}
+
+impl Viewer {
+    pub fn player(&self) -> Option<u8> {
+        match self {
+            Self::Player { id } => Some(*id),
+            _ => None,
+        }
+    }
+
+    pub fn can_see(&self, revealed: PlayerMask) -> bool {
+        match self {
+            Self::Player { id } => revealed.contains(*id),
+            Self::Observer => revealed.is_public(),
+            Self::Omniscient => true,
+        }
+    }
+
+    /// Returns true if the viewer can see private details of the owner's cards.
+    pub fn is_privileged(&self, owner: u8) -> bool {
+        match self {
+            Self::Player { id } => *id == owner,
+            Self::Observer => false,
+            Self::Omniscient => true,
+        }
+    }
+}
+
+impl From<u8> for Viewer {
+    fn from(id: u8) -> Self {
+        Self::Player { id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewer_can_see() {
+        let mut mask = PlayerMask::new();
+        mask.set(0, true);
+        assert!(Viewer::from(0).can_see(mask));
+        assert!(!Viewer::from(1).can_see(mask));
+        assert!(!Viewer::Observer.can_see(mask));
+        assert!(Viewer::Omniscient.can_see(mask));
+
+        mask.set_all(true);
+        assert!(Viewer::from(1).can_see(mask));
+        assert!(Viewer::Observer.can_see(mask));
+    }
 }
@@ -49,6 +49,24 @@ impl CardPool {
         }
         count.is_empty()
     }
+
+    /// Returns the number of copies of the card allowed in a deck, ignoring the regulation.
+    pub fn max_copies(&self, id: ArchetypeId, catalog: &Catalog) -> u8 {
+        self.0
+            .iter()
+            .find(|(entry, _)| match entry {
+                CardPoolEntry::CoreSet => catalog.contains(id),
+                CardPoolEntry::Card(card) => *card == id,
+            })
+            .map_or(0, |(_, status)| (*status).into())
+    }
+}
+
+impl FromIterator<(CardPoolEntry, PoolStatus)> for CardPool {
+    /// Collects the entries in order of priority, as the first entry matching a card applies.
+    fn from_iter<T: IntoIterator<Item = (CardPoolEntry, PoolStatus)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
 }
 
 impl Default for CardPool {
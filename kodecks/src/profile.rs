@@ -31,6 +31,8 @@ bitflags! {
     pub struct DebugFlags: u8 {
         const DEBUG_COMMAND = 0b00000001;
         const IGNORE_COST = 0b00000010;
+        const UNDO = 0b00000100;
+        const BOT_TRACE = 0b00001000;
     }
 }
 
@@ -56,4 +58,172 @@ impl<'de> BorrowDecode<'de> for DebugFlags {
 #[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct BotConfig {
     pub player: u8,
+    #[serde(default)]
+    pub difficulty: BotDifficulty,
+    #[serde(default)]
+    pub personality: BotPersonality,
+    /// The time in milliseconds a bot may spend on a decision.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit_ms: Option<u32>,
+    #[serde(default)]
+    pub kind: BotKind,
+    /// Replaces the default weights of the evaluation, such as the ones tuned by `bot-tune`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<EvalWeights>,
+}
+
+impl BotConfig {
+    pub fn new(player: u8) -> Self {
+        Self {
+            player,
+            difficulty: BotDifficulty::default(),
+            personality: BotPersonality::default(),
+            time_limit_ms: None,
+            kind: BotKind::default(),
+            weights: None,
+        }
+    }
+}
+
+/// The program playing as a bot.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BotKind {
+    #[default]
+    Builtin,
+    /// The builtin bot searching with Monte Carlo tree search instead.
+    /// The difficulty and the personality are ignored.
+    Mcts,
+    /// Another process speaking JSON lines on its stdin and stdout.
+    /// The difficulty and the personality are ignored.
+    External {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+/// How hard a bot searches and how often it makes mistakes.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BotDifficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl BotDifficulty {
+    pub const ALL: [BotDifficulty; 3] = [Self::Easy, Self::Normal, Self::Hard];
+}
+
+/// The play style a bot favors when evaluating the game.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BotPersonality {
+    #[default]
+    Balanced,
+    /// Prefers dealing damage to the opponent.
+    Aggressive,
+    /// Prefers keeping its life and creatures over racing.
+    Control,
+}
+
+/// The weights of the terms in the evaluation of a game by the builtin bots.
+///
+/// Life is counted in units of 100, and the opponent's terms are subtracted.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Encode, Decode)]
+#[serde(default)]
+pub struct EvalWeights {
+    pub life: f32,
+    pub opponent_life: f32,
+    pub shards: f32,
+    pub opponent_shards: f32,
+    pub hand: f32,
+    pub opponent_hand: f32,
+    pub field: f32,
+    pub opponent_field: f32,
+    pub abilities: f32,
+    pub opponent_abilities: f32,
+    /// The ratio of the initial life below which the penalty applies.
+    pub low_life_threshold: f32,
+    pub low_life_penalty: f32,
+}
+
+impl Default for EvalWeights {
+    fn default() -> Self {
+        Self {
+            life: 1.0,
+            opponent_life: 2.0,
+            shards: 1.0,
+            opponent_shards: 1.0,
+            hand: 0.5,
+            opponent_hand: 0.5,
+            field: 1.0,
+            opponent_field: 1.0,
+            abilities: 1.0,
+            opponent_abilities: 1.0,
+            low_life_threshold: 0.2,
+            low_life_penalty: 100.0,
+        }
+    }
+}
+
+impl EvalWeights {
+    pub const LEN: usize = 12;
+
+    /// Reweights the terms for the play style of the personality.
+    pub fn with_personality(mut self, personality: BotPersonality) -> Self {
+        match personality {
+            BotPersonality::Balanced => {}
+            BotPersonality::Aggressive => {
+                self.opponent_life += 2.0;
+                self.life -= 0.5;
+            }
+            BotPersonality::Control => {
+                self.life += 1.0;
+                self.field += 0.5;
+                self.opponent_field += 0.5;
+            }
+        }
+        self
+    }
+
+    pub fn to_array(&self) -> [f32; Self::LEN] {
+        [
+            self.life,
+            self.opponent_life,
+            self.shards,
+            self.opponent_shards,
+            self.hand,
+            self.opponent_hand,
+            self.field,
+            self.opponent_field,
+            self.abilities,
+            self.opponent_abilities,
+            self.low_life_threshold,
+            self.low_life_penalty,
+        ]
+    }
+
+    pub fn from_array(values: [f32; Self::LEN]) -> Self {
+        Self {
+            life: values[0],
+            opponent_life: values[1],
+            shards: values[2],
+            opponent_shards: values[3],
+            hand: values[4],
+            opponent_hand: values[5],
+            field: values[6],
+            opponent_field: values[7],
+            abilities: values[8],
+            opponent_abilities: values[9],
+            low_life_threshold: values[10],
+            low_life_penalty: values[11],
+        }
+    }
 }
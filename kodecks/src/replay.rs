@@ -0,0 +1,42 @@
+use crate::{action::Action, catalog::Catalog, env::Environment, profile::GameProfile};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A recorded game which can be played back deterministically.
+///
+/// The profile must have a fixed `rng_seed` for the playback to be reproducible.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub profile: GameProfile,
+    pub steps: Vec<ReplayStep>,
+}
+
+/// A single call to [`Environment::process`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayStep {
+    pub player: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<Action>,
+}
+
+impl Replay {
+    pub fn new(profile: GameProfile) -> Self {
+        Self {
+            profile,
+            steps: vec![],
+        }
+    }
+
+    pub fn push(&mut self, player: u8, action: Option<Action>) {
+        self.steps.push(ReplayStep { player, action });
+    }
+
+    /// Plays back all steps and returns the resulting environment.
+    pub fn play(&self, catalog: Arc<Catalog>) -> Environment {
+        let mut env = Environment::new(self.profile.clone(), catalog);
+        for step in &self.steps {
+            env.process(step.player, step.action.clone());
+        }
+        env
+    }
+}
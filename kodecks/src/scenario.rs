@@ -1,4 +1,23 @@
-use crate::{action::PlayerAvailableActions, env::Environment};
+use crate::{
+    action::{Action, AvailableAction, PlayerAvailableActions},
+    card::Card,
+    catalog::Catalog,
+    deck::{DeckItem, DeckList},
+    env::{EndgameReason, Environment},
+    field::FieldState,
+    id::ObjectIdCounter,
+    message::{MessageBox, MessageDialog},
+    phase::Phase,
+    player::{Player, PlayerConfig, PlayerEndgameState, PlayerStats, PlayerZone},
+    profile::{DebugConfig, GameProfile},
+    regulation::Regulation,
+    sequence::CardSequence,
+    shard::ShardList,
+    zone::{CardZone, Zone},
+};
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use std::iter;
 
 pub trait Scenario: 'static + Send + Sync {
     fn override_actions(
@@ -6,4 +25,271 @@ pub trait Scenario: 'static + Send + Sync {
         env: &Environment,
         actions: Option<PlayerAvailableActions>,
     ) -> Option<PlayerAvailableActions>;
+
+    /// Returns the players who have won or lost by the rules of the scenario.
+    fn endgame(&self, _env: &Environment) -> Vec<(u8, PlayerEndgameState)> {
+        vec![]
+    }
+
+    /// Called after an action of `player` has been accepted.
+    fn on_action(&mut self, _player: u8, _action: &Action) {}
+}
+
+/// A puzzle with a fixed starting board.
+///
+/// The first player solves the puzzle and takes the first turn.
+/// The other players follow the script.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Encode, Decode)]
+pub struct Puzzle {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub regulation: Regulation,
+    pub players: Vec<PuzzlePlayer>,
+    #[serde(default)]
+    pub goal: PuzzleGoal,
+    /// Actions taken by the opponent, in order of preference.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub script: Vec<Action>,
+    /// Messages shown to the solver at the start of the puzzle.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hints: Vec<MessageBox>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct PuzzlePlayer {
+    #[serde(default = "default_life")]
+    pub life: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hand: Vec<DeckItem>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub field: Vec<PuzzleFieldItem>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub graveyard: Vec<DeckItem>,
+    /// Cards in the deck, from top to bottom.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deck: Vec<DeckItem>,
+    #[serde(default)]
+    pub shards: ShardList,
+}
+
+impl Default for PuzzlePlayer {
+    fn default() -> Self {
+        Self {
+            life: default_life(),
+            hand: vec![],
+            field: vec![],
+            graveyard: vec![],
+            deck: vec![],
+            shards: ShardList::new(),
+        }
+    }
+}
+
+fn default_life() -> u32 {
+    PlayerStats::default().life
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct PuzzleFieldItem {
+    #[serde(flatten)]
+    pub card: DeckItem,
+    #[serde(default)]
+    pub state: FieldState,
+}
+
+impl PuzzlePlayer {
+    pub(crate) fn build(&self, id: u8, catalog: &Catalog, counter: &mut ObjectIdCounter) -> Player {
+        let mut player = Player::new(id);
+        let mut new_card = |item: &DeckItem, zone: Zone| {
+            let archetype = &catalog[item.card.archetype_id];
+            let mut card = Card::new(counter, item, archetype.clone(), item.card.style, id);
+            card.set_zone(PlayerZone::new(id, zone));
+            card
+        };
+        for item in self.deck.iter().rev() {
+            player.deck.add_top(new_card(item, Zone::Deck));
+        }
+        for item in &self.hand {
+            player.hand.push(new_card(item, Zone::Hand));
+        }
+        for item in &self.field {
+            let card = new_card(&item.card, Zone::Field);
+            let card_id = card.id();
+            player.field.push(card);
+            player.field.set_card_state(card_id, item.state);
+        }
+        for item in &self.graveyard {
+            player.graveyard.push(new_card(item, Zone::Graveyard));
+        }
+        player.shards = self.shards.clone();
+        player.stats.life = self.life;
+        player
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+#[serde(rename_all = "snake_case")]
+pub enum PuzzleGoal {
+    /// Win the game before the first turn ends.
+    #[default]
+    WinThisTurn,
+}
+
+pub struct PuzzleScenario {
+    puzzle: Puzzle,
+    hints_shown: bool,
+}
+
+impl PuzzleScenario {
+    pub const SOLVER: u8 = 0;
+
+    pub fn new(puzzle: Puzzle) -> Self {
+        Self {
+            puzzle,
+            hints_shown: false,
+        }
+    }
+}
+
+impl Scenario for PuzzleScenario {
+    fn override_actions(
+        &mut self,
+        _env: &Environment,
+        actions: Option<PlayerAvailableActions>,
+    ) -> Option<PlayerAvailableActions> {
+        actions.map(|mut actions| {
+            if actions.player == Self::SOLVER && !self.hints_shown {
+                self.hints_shown = true;
+                if !self.puzzle.hints.is_empty() {
+                    actions.message_dialog = Some(MessageDialog {
+                        messages: self.puzzle.hints.clone(),
+                        allowed_action: None,
+                    });
+                }
+            }
+            actions
+        })
+    }
+
+    fn endgame(&self, env: &Environment) -> Vec<(u8, PlayerEndgameState)> {
+        match self.puzzle.goal {
+            PuzzleGoal::WinThisTurn if env.state.turn > 1 => vec![(
+                Self::SOLVER,
+                PlayerEndgameState::Lose(EndgameReason::TurnLimit),
+            )],
+            _ => vec![],
+        }
+    }
+}
+
+/// A guided tutorial played against a scripted opponent.
+///
+/// The first player is the learner and takes the first turn.
+/// Decks are neither shuffled nor randomized, so steps can refer to fixed card ids.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Encode, Decode)]
+pub struct Tutorial {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub regulation: Regulation,
+    /// Decks of the players, each listed from top to bottom.
+    pub decks: Vec<Vec<DeckItem>>,
+    /// Actions taken by the opponent, in order of preference.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub script: Vec<Action>,
+    pub steps: Vec<TutorialStep>,
+}
+
+impl Tutorial {
+    pub fn profile(&self) -> GameProfile {
+        let players = self
+            .decks
+            .iter()
+            .map(|cards| PlayerConfig {
+                deck: DeckList {
+                    id: self.id.clone(),
+                    name: self.title.clone(),
+                    cards: cards.iter().rev().cloned().collect(),
+                },
+            })
+            .collect();
+        GameProfile {
+            regulation: self.regulation.clone(),
+            debug: Some(DebugConfig {
+                no_deck_shuffle: true,
+                no_player_shuffle: true,
+                ..Default::default()
+            }),
+            players,
+            rng_seed: Some(0),
+            ..Default::default()
+        }
+    }
+}
+
+/// A step of a tutorial, shown when the learner gets to act in the given turn and phase.
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct TutorialStep {
+    pub turn: u16,
+    pub phase: Phase,
+    pub messages: Vec<MessageBox>,
+    /// The only action the learner can take during the step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_action: Option<AvailableAction>,
+}
+
+pub struct TutorialScenario {
+    tutorial: Tutorial,
+    current: usize,
+    active: bool,
+}
+
+impl TutorialScenario {
+    pub const LEARNER: u8 = 0;
+
+    pub fn new(tutorial: Tutorial) -> Self {
+        Self {
+            tutorial,
+            current: 0,
+            active: false,
+        }
+    }
+}
+
+impl Scenario for TutorialScenario {
+    fn override_actions(
+        &mut self,
+        env: &Environment,
+        actions: Option<PlayerAvailableActions>,
+    ) -> Option<PlayerAvailableActions> {
+        actions.map(|mut actions| {
+            if actions.player != Self::LEARNER {
+                return actions;
+            }
+            let step = self
+                .tutorial
+                .steps
+                .get(self.current)
+                .filter(|step| step.turn == env.state.turn && step.phase == env.state.phase);
+            self.active = step.is_some();
+            if let Some(step) = step {
+                if let Some(allowed) = &step.allowed_action {
+                    actions.actions = iter::once(allowed.clone()).collect();
+                }
+                actions.message_dialog = Some(MessageDialog {
+                    messages: step.messages.clone(),
+                    allowed_action: step.allowed_action.clone(),
+                });
+            }
+            actions
+        })
+    }
+
+    fn on_action(&mut self, player: u8, _action: &Action) {
+        if player == Self::LEARNER && self.active {
+            self.active = false;
+            self.current += 1;
+        }
+    }
 }
@@ -25,7 +25,7 @@ impl From<StackItem> for LocalStackItem {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
 pub struct Stack<T: 'static> {
     items: Vec<T>,
 }